@@ -22,6 +22,23 @@ const KEPT_PATHS: &[&str] = &[
     "/api/esc/environments/{orgName}/{projectName}/{envName}",
     "/api/esc/environments/{orgName}/{projectName}/{envName}/open",
     "/api/esc/environments/{orgName}/{projectName}/{envName}/open/{openSessionID}",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/clone",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/referrers",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/rotate",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/rotate/history",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/schedules",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/schedules/{scheduleID}/pause",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/schedules/{scheduleID}/resume",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/versions",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/versions/tags",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/versions/tags/{tagName}",
+    "/api/esc/environments/{orgName}/{projectName}/{envName}/versions/{version}/retract",
+    "/api/change-requests/{orgName}",
+    "/api/change-requests/{orgName}/{changeRequestID}",
+    "/api/change-requests/{orgName}/{changeRequestID}/apply",
+    "/api/change-requests/{orgName}/{changeRequestID}/approve",
+    "/api/change-requests/{orgName}/{changeRequestID}/close",
+    "/api/change-requests/{orgName}/{changeRequestID}/submit",
     "/api/preview/agents/{orgName}/tasks",
     "/api/preview/agents/{orgName}/tasks/{taskID}",
     "/api/preview/agents/{orgName}/tasks/{taskID}/events",
@@ -162,6 +179,25 @@ fn trim_spec(full: &serde_json::Value) -> serde_json::Value {
     // for non-machine-token users. Make it optional.
     patch_nullable_fields(&mut kept_schemas);
 
+    // 6c. Progenitor ignores discriminator mappings, so fields that only exist
+    // on a subtype would be dropped. Merge the ones we read into their base.
+    if let Some(schemas) = all_schemas {
+        // The environment a change request targets
+        merge_subtype_properties(
+            &mut kept_schemas,
+            schemas,
+            "TargetEntity",
+            "TargetEntityEnvironment",
+        );
+        // Approvers of a change gate
+        merge_subtype_properties(
+            &mut kept_schemas,
+            schemas,
+            "ChangeGateRuleEvaluation",
+            "ChangeGateApprovalRuleEvaluation",
+        );
+    }
+
     // 7. Build the trimmed spec
     let mut trimmed = serde_json::Map::new();
     trimmed.insert(
@@ -336,6 +372,39 @@ fn patch_nullable_fields(schemas: &mut serde_json::Map<String, serde_json::Value
     set_nullable(schemas, "AgentTask", "entities");
 }
 
+/// Helper: add the properties of a discriminator subtype (declared with
+/// `allOf: [base, {...}]`) to the base schema, without making them required.
+fn merge_subtype_properties(
+    kept: &mut serde_json::Map<String, serde_json::Value>,
+    all: &serde_json::Map<String, serde_json::Value>,
+    base: &str,
+    subtype: &str,
+) {
+    let Some(parts) = all
+        .get(subtype)
+        .and_then(|s| s.get("allOf"))
+        .and_then(|a| a.as_array())
+    else {
+        return;
+    };
+    let Some(properties) = kept
+        .get_mut(base)
+        .and_then(|s| s.get_mut("properties"))
+        .and_then(|p| p.as_object_mut())
+    else {
+        return;
+    };
+    for part in parts {
+        if let Some(extra) = part.get("properties").and_then(|p| p.as_object()) {
+            for (name, property) in extra {
+                properties
+                    .entry(name.clone())
+                    .or_insert_with(|| property.clone());
+            }
+        }
+    }
+}
+
 /// Helper: remove a field from a schema's `required` array.
 fn remove_required(
     schemas: &mut serde_json::Map<String, serde_json::Value>,
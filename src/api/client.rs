@@ -5,10 +5,14 @@
 //! missing from the OpenAPI spec) are handled with raw reqwest.
 
 use super::domain::{
-    ApiConfig, EscEnvironmentDetails, EscEnvironmentSummary, EscOpenResponse, NeoCreateTaskMessage,
-    NeoMessage, NeoMessageType, NeoSlashCommand, NeoSlashCommandPayload, NeoTask, NeoTaskResponse,
-    NeoToolCall, NeoUpdateTaskRequest, RegistryPackage, RegistryTemplate, Resource,
-    ResourceSummaryPoint, Service, Stack, StackUpdate, User,
+    ApiConfig, EscChangeRequest, EscCloneRequest, EscDiagnostic, EscDraft, EscEnvironmentDetails,
+    EscEnvironmentSummary, EscOpenResponse, EscReferrer, EscRevision, EscRevisionTag,
+    EscRotationEvent, EscSchedule, NeoCreateTaskMessage, NeoEntity, NeoEntityDiff, NeoEventCursor,
+    NeoEventsPage, NeoMessage, NeoMessageType, NeoSlashCommand, NeoSlashCommandPayload, NeoTask,
+    NeoTaskPage, NeoTaskResponse, NeoToolCall, NeoUpdateTaskRequest, RegistryPackage,
+    RegistryTemplate, ResourceSearchPage, ResourceSearchParams, ResourceSummaryPoint, Service,
+    Stack, StackResource, StackUpdate, UpdateCommit, UpdateDetails, UpdateEvent, UpdateEventKind,
+    UpdatePerson, User,
 };
 use super::generated;
 use color_eyre::Result;
@@ -31,6 +35,12 @@ pub enum ApiError {
     #[error("Conflict: command was modified elsewhere. Please refresh and try again.")]
     Conflict,
 
+    #[error("Conflict: environment was modified elsewhere since it was loaded. Reload it and re-apply your changes.")]
+    EnvironmentConflict,
+
+    #[error("Rotators reported errors: {0}")]
+    Rotation(String),
+
     #[error("Parse error: {0}")]
     Parse(String),
 }
@@ -62,6 +72,455 @@ fn map_gen_err(e: generated::Error) -> ApiError {
     }
 }
 
+/// Read a response header as a string, if present and valid UTF-8
+fn header_str(response: &reqwest::Response, name: impl header::AsHeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Derive the console URL from an API URL (e.g., `https://api.pulumi.com` → `https://app.pulumi.com`)
+fn console_url_for(api_url: &str) -> String {
+    let trimmed = api_url.trim_end_matches('/');
+    match trimmed.split_once("://api.") {
+        Some((scheme, host)) => format!("{}://app.{}", scheme, host),
+        None => trimmed.to_string(),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Neo event wire types (polymorphic content — raw reqwest)
+// ─────────────────────────────────────────────────────────────
+
+#[derive(serde::Deserialize, Debug)]
+struct ToolCallRaw {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    args: Option<serde_json::Value>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct EventBody {
+    #[serde(rename = "type")]
+    #[serde(default)]
+    body_type: String,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_content")]
+    content: String,
+    #[serde(default)]
+    timestamp: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCallRaw>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    tool_call_id: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    is_error: bool,
+    /// ID of an approval request, which its confirmation refers to
+    #[serde(default)]
+    id: Option<String>,
+}
+
+fn deserialize_content<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+    let value = serde_json::Value::deserialize(deserializer)?;
+    match value {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Null => Ok(String::new()),
+        other => Ok(other.to_string()),
+    }
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct TaskEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    event_body: Option<EventBody>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct EventsResponse {
+    #[serde(default)]
+    events: Vec<TaskEvent>,
+    #[serde(default)]
+    continuation_token: Option<String>,
+}
+
+/// Entity diff attaching `entities` to a new task, if there are any
+fn attach_entities(entities: &[NeoEntity]) -> Option<NeoEntityDiff> {
+    (!entities.is_empty()).then(|| NeoEntityDiff {
+        add: entities.to_vec(),
+        remove: Vec::new(),
+    })
+}
+
+/// A `user_confirmation` event answering the approval request `approval_request_id`;
+/// a comment is sent as instructions to the agent
+fn confirmation_event(
+    approval_request_id: &str,
+    approved: bool,
+    comment: Option<&str>,
+    timestamp: &str,
+) -> serde_json::Value {
+    let mut event = serde_json::json!({
+        "type": "user_confirmation",
+        "approval_request_id": approval_request_id,
+        "ok": approved,
+        "timestamp": timestamp
+    });
+    if let Some(comment) = comment.filter(|c| !c.trim().is_empty()) {
+        event["instructions"] = serde_json::Value::String(comment.to_string());
+    }
+    event
+}
+
+fn event_to_message(event: TaskEvent) -> Option<NeoMessage> {
+    event
+        .event_body
+        .and_then(|body| match body.body_type.as_str() {
+            "user_message" => Some(NeoMessage {
+                role: "user".to_string(),
+                content: body.content,
+                message_type: NeoMessageType::UserMessage,
+                timestamp: body.timestamp,
+                tool_calls: vec![],
+                tool_name: None,
+                tool_call_id: None,
+                approval_request_id: None,
+            }),
+            "assistant_message" => {
+                let tool_calls: Vec<NeoToolCall> = body
+                    .tool_calls
+                    .into_iter()
+                    .map(|tc| NeoToolCall {
+                        id: tc.id,
+                        name: tc.name,
+                        args: tc.args,
+                    })
+                    .collect();
+                Some(NeoMessage {
+                    role: "assistant".to_string(),
+                    content: body.content,
+                    message_type: NeoMessageType::AssistantMessage,
+                    timestamp: body.timestamp,
+                    tool_calls,
+                    tool_name: None,
+                    tool_call_id: None,
+                    approval_request_id: None,
+                })
+            }
+            "exec_tool_call" => Some(NeoMessage {
+                role: "tool".to_string(),
+                content: format!("Executing: {}", body.name.as_deref().unwrap_or("unknown")),
+                message_type: NeoMessageType::ToolCall,
+                timestamp: body.timestamp,
+                tool_calls: vec![],
+                tool_name: body.name,
+                tool_call_id: body.tool_call_id,
+                approval_request_id: None,
+            }),
+            "tool_response" => {
+                let is_error = body.is_error;
+                let display_content = if is_error {
+                    body.content.clone()
+                } else if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body.content) {
+                    if let Some(result) = json.get("result") {
+                        result.to_string()
+                    } else {
+                        body.content.clone()
+                    }
+                } else {
+                    body.content.clone()
+                };
+                Some(NeoMessage {
+                    role: "tool_result".to_string(),
+                    content: display_content,
+                    message_type: if is_error {
+                        NeoMessageType::ToolError
+                    } else {
+                        NeoMessageType::ToolResponse
+                    },
+                    timestamp: body.timestamp,
+                    tool_calls: vec![],
+                    tool_name: body.name,
+                    tool_call_id: body.tool_call_id,
+                    approval_request_id: None,
+                })
+            }
+            "user_approval_request" => Some(NeoMessage {
+                role: "system".to_string(),
+                content: body
+                    .message
+                    .unwrap_or_else(|| "Approval requested".to_string()),
+                message_type: NeoMessageType::ApprovalRequest,
+                timestamp: body.timestamp,
+                tool_calls: vec![],
+                tool_name: None,
+                tool_call_id: None,
+                approval_request_id: body.id,
+            }),
+            "set_task_name" => Some(NeoMessage {
+                role: "system".to_string(),
+                content: format!("Task: {}", body.name.clone().unwrap_or_default()),
+                message_type: NeoMessageType::TaskNameChange,
+                timestamp: body.timestamp,
+                tool_calls: vec![],
+                tool_name: None,
+                tool_call_id: None,
+                approval_request_id: None,
+            }),
+            _ => None,
+        })
+}
+
+// ─────────────────────────────────────────────────────────────
+// ESC check wire types (YAML request body — raw reqwest)
+// ─────────────────────────────────────────────────────────────
+
+#[derive(serde::Deserialize, Debug, Default)]
+struct EscCheckResponse {
+    #[serde(default)]
+    diagnostics: Vec<EscDiagnosticRaw>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct EscDiagnosticRaw {
+    #[serde(default)]
+    range: Option<EscRangeRaw>,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct EscRangeRaw {
+    begin: EscPosRaw,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct EscPosRaw {
+    line: usize,
+    column: usize,
+}
+
+impl From<EscDiagnosticRaw> for EscDiagnostic {
+    fn from(raw: EscDiagnosticRaw) -> Self {
+        Self {
+            summary: raw.summary,
+            path: raw.path.filter(|p| !p.is_empty()),
+            line: raw.range.as_ref().map(|r| r.begin.line),
+            column: raw.range.as_ref().map(|r| r.begin.column),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Stack resource wire types (spec types inputs/outputs as maps of objects — raw reqwest)
+// ─────────────────────────────────────────────────────────────
+
+#[derive(serde::Deserialize, Debug)]
+struct StackResourcesResponse {
+    #[serde(default)]
+    resources: Vec<StackResourceEntry>,
+}
+
+/// The spec wraps each resource in `{ "resource": ... }`; the live API returns it bare
+#[derive(serde::Deserialize, Debug)]
+#[serde(untagged)]
+enum StackResourceEntry {
+    Wrapped { resource: StackResource },
+    Bare(StackResource),
+}
+
+impl From<StackResourceEntry> for StackResource {
+    fn from(entry: StackResourceEntry) -> Self {
+        match entry {
+            StackResourceEntry::Wrapped { resource } | StackResourceEntry::Bare(resource) => {
+                resource
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Update engine event wire types (deeply nested, lenient parsing — raw reqwest)
+// ─────────────────────────────────────────────────────────────
+
+#[derive(serde::Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct UpdateEventsResponse {
+    #[serde(default)]
+    events: Vec<EngineEventRaw>,
+    #[serde(default)]
+    continuation_token: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct EngineEventRaw {
+    #[serde(default)]
+    timestamp: i64,
+    #[serde(default)]
+    resource_pre_event: Option<StepEventRaw>,
+    #[serde(default)]
+    res_outputs_event: Option<StepEventRaw>,
+    #[serde(default)]
+    res_op_failed_event: Option<StepEventRaw>,
+    #[serde(default)]
+    diagnostic_event: Option<DiagnosticEventRaw>,
+    #[serde(default)]
+    stdout_event: Option<DiagnosticEventRaw>,
+    #[serde(default)]
+    summary_event: Option<SummaryEventRaw>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct StepEventRaw {
+    metadata: StepMetadataRaw,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct StepMetadataRaw {
+    #[serde(default)]
+    op: String,
+    #[serde(default)]
+    urn: String,
+    #[serde(default, rename = "type")]
+    resource_type: String,
+    #[serde(default)]
+    diffs: Option<Vec<String>>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct DiagnosticEventRaw {
+    #[serde(default)]
+    urn: Option<String>,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    severity: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SummaryEventRaw {
+    #[serde(default)]
+    duration_seconds: i64,
+    #[serde(default)]
+    resource_changes: std::collections::BTreeMap<String, i64>,
+}
+
+/// Remove Pulumi's `<{%fg 1%}>`-style color directives from engine output
+fn strip_color_directives(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("<{%") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("%}>") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Path segment the engine events of an update of `kind` live under
+fn update_events_path(kind: &str) -> &'static str {
+    match kind {
+        "preview" => "preview",
+        "refresh" => "refresh",
+        "destroy" => "destroy",
+        _ => "update",
+    }
+}
+
+fn engine_event_to_update_event(event: EngineEventRaw) -> Option<UpdateEvent> {
+    let step = |kind, step: StepEventRaw| UpdateEvent {
+        timestamp: event.timestamp,
+        kind,
+        urn: Some(step.metadata.urn),
+        resource_type: Some(step.metadata.resource_type),
+        op: Some(step.metadata.op),
+        diffs: step.metadata.diffs.unwrap_or_default(),
+        severity: None,
+        message: String::new(),
+    };
+    let diagnostic = |kind, diag: DiagnosticEventRaw| UpdateEvent {
+        timestamp: event.timestamp,
+        kind,
+        urn: diag.urn.filter(|u| !u.is_empty()),
+        resource_type: None,
+        op: None,
+        diffs: Vec::new(),
+        severity: diag.severity,
+        message: strip_color_directives(&diag.message).trim_end().to_string(),
+    };
+
+    if let Some(pre) = event.resource_pre_event {
+        Some(step(UpdateEventKind::ResourcePre, pre))
+    } else if let Some(outputs) = event.res_outputs_event {
+        Some(step(UpdateEventKind::ResourceOutputs, outputs))
+    } else if let Some(failed) = event.res_op_failed_event {
+        Some(step(UpdateEventKind::ResourceFailed, failed))
+    } else if let Some(diag) = event.diagnostic_event {
+        Some(diagnostic(UpdateEventKind::Diagnostic, diag))
+    } else if let Some(stdout) = event.stdout_event {
+        Some(diagnostic(UpdateEventKind::Stdout, stdout))
+    } else {
+        event.summary_event.map(|summary| {
+            let changes: Vec<String> = summary
+                .resource_changes
+                .iter()
+                .map(|(op, count)| format!("{} {}", count, op))
+                .collect();
+            UpdateEvent {
+                timestamp: event.timestamp,
+                kind: UpdateEventKind::Summary,
+                urn: None,
+                resource_type: None,
+                op: None,
+                diffs: Vec::new(),
+                severity: None,
+                message: format!(
+                    "Finished in {}s: {}",
+                    summary.duration_seconds,
+                    changes.join(", ")
+                ),
+            }
+        })
+    }
+}
+
+/// Parse one line of a `pulumi --event-log` file.
+/// The CLI writes the same engine events the update events endpoint returns.
+pub fn parse_engine_event(line: &str) -> Option<UpdateEvent> {
+    serde_json::from_str::<EngineEventRaw>(line)
+        .ok()
+        .and_then(engine_event_to_update_event)
+}
+
 /// Pulumi API client
 #[derive(Debug, Clone)]
 pub struct PulumiClient {
@@ -83,11 +542,21 @@ impl PulumiClient {
 
         let base_url =
             env::var("PULUMI_API_URL").unwrap_or_else(|_| "https://api.pulumi.com".to_string());
+        let organization = env::var("PULUMI_ORG").ok();
+
+        Self::from_config(ApiConfig {
+            base_url,
+            access_token,
+            organization,
+        })
+    }
 
+    /// Create a client from an explicit configuration
+    pub fn from_config(config: ApiConfig) -> Result<Self, ApiError> {
         let mut headers = header::HeaderMap::new();
         headers.insert(
             header::AUTHORIZATION,
-            header::HeaderValue::from_str(&format!("token {}", access_token))
+            header::HeaderValue::from_str(&format!("token {}", config.access_token))
                 .map_err(|e| ApiError::Parse(e.to_string()))?,
         );
         headers.insert(
@@ -104,17 +573,12 @@ impl PulumiClient {
             .build()
             .map_err(ApiError::Http)?;
 
-        let gen = generated::Client::new_with_client(&base_url, reqwest_client.clone());
-        let organization = env::var("PULUMI_ORG").ok();
+        let gen = generated::Client::new_with_client(&config.base_url, reqwest_client.clone());
 
         Ok(Self {
             client: reqwest_client,
             gen,
-            config: ApiConfig {
-                base_url,
-                access_token,
-                organization,
-            },
+            config,
         })
     }
 
@@ -142,6 +606,16 @@ impl PulumiClient {
         &self.config.base_url
     }
 
+    /// Get the Pulumi Cloud console URL that belongs to the configured API URL
+    pub fn console_url(&self) -> String {
+        console_url_for(&self.config.base_url)
+    }
+
+    /// Get the console link for a Neo task
+    pub fn neo_task_url(&self, org: &str, task_id: &str) -> String {
+        format!("{}/{}/neo/tasks/{}", self.console_url(), org, task_id)
+    }
+
     fn org_or_default<'a>(&'a self, org: Option<&'a str>) -> Result<&'a str, ApiError> {
         org.or(self.config.organization.as_deref())
             .ok_or(ApiError::Parse("No organization specified".to_string()))
@@ -245,6 +719,169 @@ impl PulumiClient {
             .collect())
     }
 
+    /// Get a single update by version (summary, resource changes, people involved).
+    /// Nested update metadata is parsed leniently — raw reqwest.
+    pub async fn get_stack_update(
+        &self,
+        org: &str,
+        project: &str,
+        stack: &str,
+        version: i32,
+    ) -> Result<UpdateDetails, ApiError> {
+        let url = format!(
+            "{}/api/stacks/{}/{}/{}/updates/{}",
+            self.config.base_url, org, project, stack, version
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse { status, message });
+        }
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct UpdateResponse {
+            #[serde(default, rename = "updateID")]
+            update_id: String,
+            #[serde(default)]
+            version: i32,
+            #[serde(default)]
+            info: UpdateInfo,
+            #[serde(default)]
+            requested_by: Option<UpdatePerson>,
+            #[serde(default)]
+            github_commit_info: Option<UpdateCommit>,
+        }
+
+        #[derive(serde::Deserialize, Default)]
+        #[serde(rename_all = "camelCase")]
+        struct UpdateInfo {
+            #[serde(default)]
+            kind: String,
+            #[serde(default)]
+            result: String,
+            #[serde(default)]
+            message: String,
+            #[serde(default)]
+            start_time: Option<i64>,
+            #[serde(default)]
+            end_time: Option<i64>,
+            #[serde(default)]
+            resource_changes: Option<super::domain::ResourceChanges>,
+            #[serde(default)]
+            resource_count: Option<i32>,
+        }
+
+        let data: UpdateResponse = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+
+        Ok(UpdateDetails {
+            update_id: data.update_id,
+            version: data.version,
+            kind: data.info.kind,
+            result: data.info.result,
+            message: data.info.message,
+            start_time: data.info.start_time,
+            end_time: data.info.end_time,
+            resource_changes: data.info.resource_changes,
+            resource_count: data.info.resource_count,
+            requested_by: data.requested_by,
+            commit: data.github_commit_info.filter(|c| !c.sha.is_empty()),
+        })
+    }
+
+    /// Get the engine event log of an update of `kind` (update, preview, ...), oldest first.
+    /// Not typed usefully in the OpenAPI spec — raw reqwest.
+    pub async fn get_update_events(
+        &self,
+        org: &str,
+        project: &str,
+        stack: &str,
+        kind: &str,
+        update_id: &str,
+    ) -> Result<Vec<UpdateEvent>, ApiError> {
+        let mut events: Vec<UpdateEvent> = Vec::new();
+        let mut continuation_token: Option<String> = None;
+        let max_pages = 20;
+
+        for _ in 0..max_pages {
+            let mut url = format!(
+                "{}/api/stacks/{}/{}/{}/{}/{}/events",
+                self.config.base_url,
+                org,
+                project,
+                stack,
+                update_events_path(kind),
+                update_id
+            );
+            if let Some(ref token) = continuation_token {
+                url.push_str("?continuationToken=");
+                url.push_str(&urlencoding::encode(token));
+            }
+
+            let response = self.client.get(&url).send().await?;
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let message = response.text().await.unwrap_or_default();
+                return Err(ApiError::ApiResponse { status, message });
+            }
+
+            let data: UpdateEventsResponse = response
+                .json()
+                .await
+                .map_err(|e| ApiError::Parse(e.to_string()))?;
+
+            let page_len = data.events.len();
+            events.extend(
+                data.events
+                    .into_iter()
+                    .filter_map(engine_event_to_update_event),
+            );
+
+            match data.continuation_token {
+                Some(next) if page_len > 0 => continuation_token = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Get the resources in a stack's latest checkpoint.
+    /// Spec types resource inputs/outputs too narrowly — raw reqwest.
+    pub async fn get_stack_resources(
+        &self,
+        org: &str,
+        project: &str,
+        stack: &str,
+    ) -> Result<Vec<StackResource>, ApiError> {
+        let url = format!(
+            "{}/api/stacks/{}/{}/{}/resources/latest",
+            self.config.base_url, org, project, stack
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse { status, message });
+        }
+
+        let data: StackResourcesResponse = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+
+        Ok(data.resources.into_iter().map(Into::into).collect())
+    }
+
     /// Get recent updates across all stacks in the organization.
     /// Uses the console API which is NOT in the OpenAPI spec — raw reqwest.
     pub async fn get_org_recent_updates(
@@ -402,6 +1039,82 @@ impl PulumiClient {
         Ok(all_environments)
     }
 
+    /// Create an empty ESC environment
+    pub async fn create_esc_environment(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .create_environment_esc_environments()
+            .org_name(org)
+            .body(generated::types::CreateEnvironmentRequest {
+                project: project.to_string(),
+                name: env.to_string(),
+            })
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        log::info!("Created ESC environment {}/{}/{}", org, project, env);
+        Ok(())
+    }
+
+    /// Clone an ESC environment to a new project/name
+    pub async fn clone_esc_environment(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        request: &EscCloneRequest,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .clone_environment()
+            .org_name(org)
+            .project_name(project)
+            .env_name(env)
+            .body(generated::types::CloneEnvironmentRequest {
+                project: Some(request.project.clone()),
+                name: request.name.clone(),
+                version: None,
+                preserve_history: Some(request.preserve_history),
+                preserve_access: None,
+                preserve_environment_tags: Some(request.preserve_tags),
+                preserve_revision_tags: Some(request.preserve_tags),
+            })
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        log::info!(
+            "Cloned ESC environment {}/{}/{} to {}/{}",
+            org,
+            project,
+            env,
+            request.project,
+            request.name
+        );
+        Ok(())
+    }
+
+    /// Delete an ESC environment
+    pub async fn delete_esc_environment(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .delete_environment_esc_environments()
+            .org_name(org)
+            .project_name(project)
+            .env_name(env)
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        log::info!("Deleted ESC environment {}/{}/{}", org, project, env);
+        Ok(())
+    }
+
     /// Get ESC environment details (YAML definition).
     /// The API returns YAML text — not in OpenAPI spec, raw reqwest.
     pub async fn get_esc_environment(
@@ -424,9 +1137,12 @@ impl PulumiClient {
             return Err(ApiError::ApiResponse { status, message });
         }
 
+        let etag = header_str(&response, header::ETAG);
+        let revision = header_str(&response, "Pulumi-ESC-Revision").and_then(|r| r.parse().ok());
         let text = response.text().await?;
         log::debug!(
-            "ESC environment details response: {}",
+            "ESC environment details response (etag {:?}): {}",
+            etag,
             &text[..text.len().min(500)]
         );
 
@@ -435,38 +1151,103 @@ impl PulumiClient {
             definition: None,
             created: None,
             modified: None,
-            revision: None,
+            revision,
+            etag,
             extra: std::collections::HashMap::new(),
         })
     }
 
-    /// Open an ESC environment to get resolved values
-    pub async fn open_esc_environment(
+    /// Validate an environment definition without saving it.
+    /// Posts the YAML as application/x-yaml — raw reqwest.
+    pub async fn check_esc_environment(
         &self,
         org: &str,
         project: &str,
         env: &str,
-    ) -> Result<EscOpenResponse, ApiError> {
-        // Step 1: Open the environment session
-        let open_url = format!(
-            "{}/api/esc/environments/{}/{}/{}/open",
+        yaml_content: &str,
+    ) -> Result<Vec<EscDiagnostic>, ApiError> {
+        let url = format!(
+            "{}/api/esc/environments/{}/{}/{}/check",
             self.config.base_url, org, project, env
         );
 
-        log::debug!("POST ESC environment open: {}", open_url);
-        let response = self.client.post(&open_url).send().await?;
+        log::debug!("POST ESC environment check: {}", url);
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/x-yaml")
+            .body(yaml_content.to_string())
+            .send()
+            .await?;
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
+        // Invalid definitions come back as 400 with the diagnostics in the body
+        let status = response.status();
+        if !status.is_success() && status != reqwest::StatusCode::BAD_REQUEST {
             let message = response.text().await.unwrap_or_default();
-            return Err(ApiError::ApiResponse { status, message });
+            return Err(ApiError::ApiResponse {
+                status: status.as_u16(),
+                message,
+            });
         }
 
-        #[derive(serde::Deserialize, Debug)]
-        struct DiagnosticItem {
-            #[serde(default)]
-            summary: Option<String>,
-            #[serde(default)]
+        let text = response.text().await?;
+        let check: EscCheckResponse = serde_json::from_str(&text)
+            .map_err(|e| ApiError::Parse(format!("Failed to parse check response: {}", e)))?;
+        Ok(check.diagnostics.into_iter().map(Into::into).collect())
+    }
+
+    /// Open an ESC environment to get resolved values
+    pub async fn open_esc_environment(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<EscOpenResponse, ApiError> {
+        let open_url = format!(
+            "{}/api/esc/environments/{}/{}/{}/open",
+            self.config.base_url, org, project, env
+        );
+        self.open_esc_session(&open_url, org, project, env).await
+    }
+
+    /// Open a draft change to an ESC environment, resolving the values it would have
+    pub async fn open_esc_draft(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        change_request_id: &str,
+    ) -> Result<EscOpenResponse, ApiError> {
+        let open_url = format!(
+            "{}/api/esc/environments/{}/{}/{}/drafts/{}/open",
+            self.config.base_url, org, project, env, change_request_id
+        );
+        self.open_esc_session(&open_url, org, project, env).await
+    }
+
+    /// Start an open session at `open_url`, then read its resolved values
+    async fn open_esc_session(
+        &self,
+        open_url: &str,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<EscOpenResponse, ApiError> {
+        // Step 1: Open the environment session
+        log::debug!("POST ESC environment open: {}", open_url);
+        let response = self.client.post(open_url).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse { status, message });
+        }
+
+        #[derive(serde::Deserialize, Debug)]
+        struct DiagnosticItem {
+            #[serde(default)]
+            summary: Option<String>,
+            #[serde(default)]
             path: Option<String>,
         }
 
@@ -557,6 +1338,10 @@ impl PulumiClient {
     }
 
     /// Update an ESC environment definition (YAML content).
+    /// With `if_match` (the ETag from loading), a concurrent edit fails with
+    /// `ApiError::EnvironmentConflict` instead of being overwritten.
+    /// Returns the tag of the saved version, fetched again when the response
+    /// has no ETag so that the next save still sends If-Match.
     /// Uses application/x-yaml content type — raw reqwest.
     pub async fn update_esc_environment(
         &self,
@@ -564,21 +1349,37 @@ impl PulumiClient {
         project: &str,
         env: &str,
         yaml_content: &str,
-    ) -> Result<(), ApiError> {
+        if_match: Option<&str>,
+    ) -> Result<Option<String>, ApiError> {
         let url = format!(
             "{}/api/esc/environments/{}/{}/{}",
             self.config.base_url, org, project, env
         );
 
-        log::debug!("PATCH ESC environment: {}", url);
+        log::debug!("PATCH ESC environment: {} (If-Match {:?})", url, if_match);
 
-        let response = self
+        let mut request = self
             .client
             .patch(&url)
             .header("Content-Type", "application/x-yaml")
-            .body(yaml_content.to_string())
-            .send()
-            .await?;
+            .body(yaml_content.to_string());
+        if let Some(tag) = if_match {
+            request = request.header(header::IF_MATCH, tag);
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::CONFLICT
+            || status == reqwest::StatusCode::PRECONDITION_FAILED
+        {
+            log::warn!(
+                "ESC environment update conflict: {}/{}/{}",
+                org,
+                project,
+                env
+            );
+            return Err(ApiError::EnvironmentConflict);
+        }
 
         if !response.status().is_success() {
             let status = response.status().as_u16();
@@ -593,55 +1394,601 @@ impl PulumiClient {
             project,
             env
         );
-        Ok(())
+        if let Some(etag) = header_str(&response, header::ETAG) {
+            return Ok(Some(etag));
+        }
+        log::debug!("ESC environment update returned no ETag, fetching it");
+        Ok(self
+            .get_esc_environment(org, project, env)
+            .await?
+            .if_match())
     }
 
-    // ─────────────────────────────────────────────────────────────
-    // Neo API (Preview Agents API)
-    // ─────────────────────────────────────────────────────────────
+    /// Save a definition as a draft change request instead of updating the
+    /// environment. With `if_match`, a concurrent edit fails with
+    /// `ApiError::EnvironmentConflict`. Posts application/x-yaml — raw reqwest.
+    pub async fn create_esc_draft(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        yaml_content: &str,
+        if_match: Option<&str>,
+    ) -> Result<EscDraft, ApiError> {
+        let url = format!(
+            "{}/api/esc/environments/{}/{}/{}/drafts",
+            self.config.base_url, org, project, env
+        );
 
-    /// List Neo tasks (with pagination to get all results)
-    pub async fn list_neo_tasks(&self, org: Option<&str>) -> Result<Vec<NeoTask>, ApiError> {
-        let org = self.org_or_default(org)?;
+        log::debug!(
+            "POST ESC environment draft: {} (If-Match {:?})",
+            url,
+            if_match
+        );
 
-        let mut all_tasks = Vec::new();
-        let mut continuation_token: Option<String> = None;
-        let page_size: i64 = 100;
+        let mut request = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/x-yaml")
+            .body(yaml_content.to_string());
+        if let Some(tag) = if_match {
+            request = request.header(header::IF_MATCH, tag);
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::CONFLICT
+            || status == reqwest::StatusCode::PRECONDITION_FAILED
+        {
+            return Err(ApiError::EnvironmentConflict);
+        }
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse {
+                status: status.as_u16(),
+                message,
+            });
+        }
+
+        #[derive(serde::Deserialize, Debug)]
+        #[serde(rename_all = "camelCase")]
+        struct ChangeRequestRef {
+            change_request_id: String,
+            #[serde(default)]
+            latest_revision_number: i64,
+        }
+
+        let text = response.text().await?;
+        let draft: ChangeRequestRef = serde_json::from_str(&text)
+            .map_err(|e| ApiError::Parse(format!("Failed to parse draft response: {}", e)))?;
+        log::info!(
+            "ESC draft {} created for {}/{}/{}",
+            draft.change_request_id,
+            org,
+            project,
+            env
+        );
+        Ok(EscDraft {
+            change_request_id: draft.change_request_id,
+            revision: draft.latest_revision_number,
+        })
+    }
+
+    /// Get the proposed YAML definition of a draft.
+    /// The API returns YAML text — raw reqwest.
+    pub async fn get_esc_draft(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        change_request_id: &str,
+    ) -> Result<String, ApiError> {
+        let url = format!(
+            "{}/api/esc/environments/{}/{}/{}/drafts/{}",
+            self.config.base_url, org, project, env, change_request_id
+        );
+
+        log::debug!("GET ESC environment draft: {}", url);
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse { status, message });
+        }
+        Ok(response.text().await?)
+    }
+
+    /// List every revision of an ESC environment, newest first
+    pub async fn list_esc_revisions(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<Vec<EscRevision>, ApiError> {
+        const PAGE_SIZE: i64 = 100;
+        let mut revisions: Vec<EscRevision> = Vec::new();
 
         loop {
-            let mut req = self.gen.list_tasks().org_name(org).page_size(page_size);
-            if let Some(ref token) = continuation_token {
-                req = req.continuation_token(token.as_str());
+            let mut req = self
+                .gen
+                .list_environment_revisions_esc_environments()
+                .org_name(org)
+                .project_name(project)
+                .env_name(env)
+                .count(PAGE_SIZE);
+            if let Some(oldest) = revisions.last() {
+                req = req.before(oldest.number);
             }
 
-            let resp = req.send().await.map_err(map_gen_err)?;
-            let data = resp.into_inner();
+            let page = req.send().await.map_err(map_gen_err)?.into_inner();
+            let fetched = page.len();
+            revisions.extend(page.into_iter().map(EscRevision::from));
+            if fetched < PAGE_SIZE as usize {
+                break;
+            }
+        }
 
-            let fetched_count = data.tasks.len();
-            log::debug!(
-                "Neo tasks: fetched {} tasks, continuation_token: {:?}",
-                fetched_count,
-                data.continuation_token
-            );
+        revisions.sort_by_key(|r| std::cmp::Reverse(r.number));
+        log::debug!(
+            "ESC revisions: {} revisions for {}/{}/{}",
+            revisions.len(),
+            org,
+            project,
+            env
+        );
+        Ok(revisions)
+    }
 
-            let tasks: Vec<NeoTask> = data.tasks.into_iter().map(Into::into).collect();
-            all_tasks.extend(tasks);
+    /// List the version tags of an ESC environment
+    pub async fn list_esc_revision_tags(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<Vec<EscRevisionTag>, ApiError> {
+        let mut tags = Vec::new();
+        let mut after: Option<String> = None;
 
-            match data.continuation_token {
-                Some(token) if !token.is_empty() => {
-                    continuation_token = Some(token);
-                }
+        loop {
+            let mut req = self
+                .gen
+                .list_revision_tags_esc_environments_versions()
+                .org_name(org)
+                .project_name(project)
+                .env_name(env);
+            if let Some(ref token) = after {
+                req = req.after(token.as_str());
+            }
+
+            let page = req.send().await.map_err(map_gen_err)?.into_inner();
+            tags.extend(page.tags.into_iter().map(EscRevisionTag::from));
+            match page.next_token {
+                token if !token.is_empty() => after = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(tags)
+    }
+
+    /// List what refers to an environment's latest revision: importing
+    /// environments, stacks (latest version only) and Insights accounts
+    pub async fn list_esc_referrers(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<Vec<EscReferrer>, ApiError> {
+        let mut referrers = Vec::new();
+        let mut continuation: Option<String> = None;
+
+        loop {
+            let mut req = self
+                .gen
+                .list_environment_referrers_esc_environments()
+                .org_name(org)
+                .project_name(project)
+                .env_name(env)
+                .latest_stack_version_only(true)
+                .count(500);
+            if let Some(ref token) = continuation {
+                req = req.continuation_token(token.as_str());
+            }
+
+            let page = req.send().await.map_err(map_gen_err)?.into_inner();
+            referrers.extend(
+                page.referrers
+                    .into_values()
+                    .flatten()
+                    .filter_map(|r| EscReferrer::try_from(r).ok()),
+            );
+            match page.continuation_token {
+                Some(token) if !token.is_empty() => continuation = Some(token),
                 _ => break,
             }
+        }
+
+        referrers.sort();
+        referrers.dedup();
+        Ok(referrers)
+    }
+
+    /// Get the YAML definition of an environment at a given revision.
+    /// The API returns YAML text — raw reqwest.
+    pub async fn get_esc_environment_revision(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        revision: i64,
+    ) -> Result<String, ApiError> {
+        let url = format!(
+            "{}/api/esc/environments/{}/{}/{}/versions/{}",
+            self.config.base_url, org, project, env, revision
+        );
+
+        log::debug!("GET ESC environment revision: {}", url);
+        let response = self.client.get(&url).send().await?;
 
-            if all_tasks.len() > 10000 {
-                log::warn!("Neo tasks pagination safety limit reached");
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse { status, message });
+        }
+
+        Ok(response.text().await?)
+    }
+
+    /// Create a version tag pointing at `revision`
+    pub async fn create_esc_revision_tag(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        tag: &str,
+        revision: i64,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .create_revision_tag_esc_environments_versions_tags()
+            .org_name(org)
+            .project_name(project)
+            .env_name(env)
+            .body(generated::types::CreateEnvironmentRevisionTagRequest {
+                name: tag.to_string(),
+                revision: Some(revision),
+            })
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        Ok(())
+    }
+
+    /// Move an existing version tag to `revision`
+    pub async fn update_esc_revision_tag(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        tag: &str,
+        revision: i64,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .update_revision_tag_esc_environments()
+            .org_name(org)
+            .project_name(project)
+            .env_name(env)
+            .tag_name(tag)
+            .body(generated::types::UpdateEnvironmentRevisionTagRequest {
+                revision: Some(revision),
+            })
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        Ok(())
+    }
+
+    /// Retract a revision; the server serves the previous revision in its place
+    pub async fn retract_esc_revision(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        revision: i64,
+        reason: Option<&str>,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .retract_environment_revision_esc_environments()
+            .org_name(org)
+            .project_name(project)
+            .env_name(env)
+            .version(revision.to_string())
+            .body(generated::types::RetractEnvironmentRevisionRequest {
+                reason: reason.map(str::to_string),
+                replacement: None,
+            })
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        Ok(())
+    }
+
+    /// Rotate secrets now. `paths` selects rotators (e.g. `dbCreds`); empty rotates all.
+    /// Diagnostics (e.g. a rotator that failed to evaluate) are returned as an error.
+    pub async fn rotate_esc_environment(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        paths: Vec<String>,
+    ) -> Result<EscRotationEvent, ApiError> {
+        let response = self
+            .gen
+            .rotate_environment()
+            .org_name(org)
+            .project_name(project)
+            .env_name(env)
+            .body(generated::types::RotateEnvironmentRequest { paths })
+            .send()
+            .await
+            .map_err(map_gen_err)?
+            .into_inner();
+
+        if !response.diagnostics.is_empty() {
+            let message = response
+                .diagnostics
+                .iter()
+                .map(|d| {
+                    let summary = d.summary.as_deref().unwrap_or("Unknown error");
+                    match d.path.as_deref() {
+                        Some(path) if !path.is_empty() => format!("{} at {}", summary, path),
+                        _ => summary.to_string(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(ApiError::Rotation(message));
+        }
+        Ok(response.secret_rotation_event.into())
+    }
+
+    /// List secret rotation runs of an environment, newest first
+    pub async fn list_esc_rotation_history(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<Vec<EscRotationEvent>, ApiError> {
+        let response = self
+            .gen
+            .list_environment_secret_rotation_history()
+            .org_name(org)
+            .project_name(project)
+            .env_name(env)
+            .send()
+            .await
+            .map_err(map_gen_err)?
+            .into_inner();
+
+        let mut events: Vec<EscRotationEvent> =
+            response.events.into_iter().map(Into::into).collect();
+        events.sort_by(|a, b| b.created.cmp(&a.created));
+        Ok(events)
+    }
+
+    /// List the schedules attached to an environment
+    pub async fn list_esc_schedules(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+    ) -> Result<Vec<EscSchedule>, ApiError> {
+        let response = self
+            .gen
+            .list_environment_schedule()
+            .org_name(org)
+            .project_name(project)
+            .env_name(env)
+            .send()
+            .await
+            .map_err(map_gen_err)?
+            .into_inner();
+        Ok(response.schedules.into_iter().map(Into::into).collect())
+    }
+
+    /// Pause (`paused: true`) or resume an environment schedule
+    pub async fn set_esc_schedule_paused(
+        &self,
+        org: &str,
+        project: &str,
+        env: &str,
+        schedule_id: &str,
+        paused: bool,
+    ) -> Result<(), ApiError> {
+        if paused {
+            self.gen
+                .pause_environment_schedule()
+                .org_name(org)
+                .project_name(project)
+                .env_name(env)
+                .schedule_id(schedule_id)
+                .send()
+                .await
+                .map_err(map_gen_err)?;
+        } else {
+            self.gen
+                .resume_environment_schedule()
+                .org_name(org)
+                .project_name(project)
+                .env_name(env)
+                .schedule_id(schedule_id)
+                .send()
+                .await
+                .map_err(map_gen_err)?;
+        }
+        Ok(())
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Change requests
+    // ─────────────────────────────────────────────────────────────
+
+    /// List the organization's change requests for ESC environments, newest first
+    pub async fn list_esc_change_requests(
+        &self,
+        org: &str,
+    ) -> Result<Vec<EscChangeRequest>, ApiError> {
+        let mut requests: Vec<EscChangeRequest> = Vec::new();
+        let mut continuation: Option<String> = None;
+
+        loop {
+            let mut req = self
+                .gen
+                .list_change_requests()
+                .org_name(org)
+                .entity_type("environment")
+                .count(100);
+            if let Some(ref token) = continuation {
+                req = req.continuation_token(token.as_str());
+            }
+
+            let page = req.send().await.map_err(map_gen_err)?.into_inner();
+            requests.extend(page.change_requests.into_iter().map(Into::into));
+            if page.continuation_token.is_empty() {
                 break;
             }
+            continuation = Some(page.continuation_token);
         }
 
-        log::info!("Neo tasks: total {} tasks fetched", all_tasks.len());
-        Ok(all_tasks)
+        requests.sort_by(|a, b| b.created.cmp(&a.created));
+        Ok(requests)
+    }
+
+    /// Get a change request with the state of its approval gates
+    pub async fn get_esc_change_request(
+        &self,
+        org: &str,
+        change_request_id: &str,
+    ) -> Result<EscChangeRequest, ApiError> {
+        let response = self
+            .gen
+            .get()
+            .org_name(org)
+            .change_request_id(change_request_id)
+            .send()
+            .await
+            .map_err(map_gen_err)?
+            .into_inner();
+        Ok(response.into())
+    }
+
+    /// Submit a draft change request for approval
+    pub async fn submit_esc_change_request(
+        &self,
+        org: &str,
+        change_request_id: &str,
+        description: Option<&str>,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .submit()
+            .org_name(org)
+            .change_request_id(change_request_id)
+            .body(generated::types::SubmitChangeRequestRequest {
+                description: description.map(String::from),
+            })
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        Ok(())
+    }
+
+    /// Approve a revision of a change request
+    pub async fn approve_esc_change_request(
+        &self,
+        org: &str,
+        change_request_id: &str,
+        revision: i64,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .approve()
+            .org_name(org)
+            .change_request_id(change_request_id)
+            .body(generated::types::ApproveChangeRequestRequest {
+                comment: None,
+                revision_number: revision,
+            })
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        Ok(())
+    }
+
+    /// Apply an approved change request to its environment.
+    /// Returns the server's note about the result, if any.
+    pub async fn apply_esc_change_request(
+        &self,
+        org: &str,
+        change_request_id: &str,
+    ) -> Result<Option<String>, ApiError> {
+        let result = self
+            .gen
+            .apply()
+            .org_name(org)
+            .change_request_id(change_request_id)
+            .send()
+            .await
+            .map_err(map_gen_err)?
+            .into_inner();
+        Ok(result.message.filter(|m| !m.is_empty()))
+    }
+
+    /// Close a change request without applying it
+    pub async fn close_esc_change_request(
+        &self,
+        org: &str,
+        change_request_id: &str,
+    ) -> Result<(), ApiError> {
+        self.gen
+            .close()
+            .org_name(org)
+            .change_request_id(change_request_id)
+            .body(generated::types::CloseChangeRequestRequest { comment: None })
+            .send()
+            .await
+            .map_err(map_gen_err)?;
+        Ok(())
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Neo API (Preview Agents API)
+    // ─────────────────────────────────────────────────────────────
+
+    /// List a page of Neo tasks, newest first, starting at `continuation_token`
+    pub async fn list_neo_tasks(
+        &self,
+        org: Option<&str>,
+        continuation_token: Option<&str>,
+    ) -> Result<NeoTaskPage, ApiError> {
+        let org = self.org_or_default(org)?;
+        let page_size: i64 = 50;
+
+        let mut req = self.gen.list_tasks().org_name(org).page_size(page_size);
+        if let Some(token) = continuation_token {
+            req = req.continuation_token(token);
+        }
+
+        let resp = req.send().await.map_err(map_gen_err)?;
+        let data = resp.into_inner();
+        log::debug!(
+            "Neo tasks: fetched {} tasks, continuation_token: {:?}",
+            data.tasks.len(),
+            data.continuation_token
+        );
+
+        Ok(NeoTaskPage {
+            tasks: data.tasks.into_iter().map(Into::into).collect(),
+            continuation_token: data.continuation_token.filter(|token| !token.is_empty()),
+        })
     }
 
     /// Get a single Neo task's metadata by ID
@@ -659,7 +2006,6 @@ impl PulumiClient {
     }
 
     /// Update a Neo task's settings (e.g., sharing)
-    #[allow(dead_code)]
     pub async fn update_neo_task(
         &self,
         org: &str,
@@ -684,22 +2030,23 @@ impl PulumiClient {
         Ok(resp.into_inner().into())
     }
 
-    /// Create a new Neo task
+    /// Create a new Neo task, attaching `entities` (e.g. the stack it is about) to it
     pub async fn create_neo_task(
         &self,
         org: &str,
         query: &str,
+        entities: &[NeoEntity],
     ) -> Result<NeoTaskResponse, ApiError> {
-        let url = format!("{}/api/preview/agents/{}/tasks", self.config.base_url, org);
-
-        let timestamp = chrono::Utc::now().to_rfc3339();
-        let body = serde_json::json!({
-            "message": {
-                "type": "user_message",
-                "content": query,
-                "timestamp": timestamp
-            }
-        });
+        let url = format!("{}/api/preview/agents/{}/tasks", self.config.base_url, org);
+
+        let message = NeoCreateTaskMessage {
+            message_type: "user_message".to_string(),
+            content: query.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            commands: None,
+            entity_diff: attach_entities(entities),
+        };
+        let body = serde_json::json!({ "message": message });
 
         let response = self.client.post(&url).json(&body).send().await?;
 
@@ -768,13 +2115,14 @@ impl PulumiClient {
         })
     }
 
-    /// Send a user confirmation event
-    #[allow(dead_code)]
+    /// Send a user confirmation event (approve or reject a pending approval request)
     pub async fn confirm_neo_task(
         &self,
         org: &str,
         task_id: &str,
+        approval_request_id: &str,
         approved: bool,
+        comment: Option<&str>,
     ) -> Result<NeoTaskResponse, ApiError> {
         let url = format!(
             "{}/api/preview/agents/{}/tasks/{}",
@@ -782,13 +2130,8 @@ impl PulumiClient {
         );
 
         let timestamp = chrono::Utc::now().to_rfc3339();
-        let body = serde_json::json!({
-            "event": {
-                "type": "user_confirmation",
-                "approved": approved,
-                "timestamp": timestamp
-            }
-        });
+        let event = confirmation_event(approval_request_id, approved, comment, &timestamp);
+        let body = serde_json::json!({ "event": event });
 
         log::debug!(
             "POST Neo task confirmation: {} (approved: {})",
@@ -814,7 +2157,6 @@ impl PulumiClient {
     }
 
     /// Send a user cancel event
-    #[allow(dead_code)]
     pub async fn cancel_neo_task(
         &self,
         org: &str,
@@ -1116,13 +2458,14 @@ impl PulumiClient {
         self.get_neo_slash_command(org, command_name).await
     }
 
-    /// Create a new Neo task with slash commands.
+    /// Create a new Neo task with slash commands, attaching `entities` to it.
     /// Not in OpenAPI spec — raw reqwest.
     pub async fn create_neo_task_with_commands(
         &self,
         org: &str,
         content: &str,
         commands: &[NeoSlashCommand],
+        entities: &[NeoEntity],
     ) -> Result<NeoTaskResponse, ApiError> {
         let url = format!("{}/api/preview/agents/{}/tasks", self.config.base_url, org);
 
@@ -1159,6 +2502,7 @@ impl PulumiClient {
             content: processed_content,
             timestamp,
             commands: Some(commands_map),
+            entity_diff: attach_entities(entities),
         };
 
         let body = serde_json::json!({ "message": message });
@@ -1194,192 +2538,48 @@ impl PulumiClient {
         })
     }
 
-    /// Get Neo task events (messages).
-    /// Custom deserialization for polymorphic content — raw reqwest.
+    /// Get all Neo task events (messages).
     pub async fn get_neo_task_events(
         &self,
         org: &str,
         task_id: &str,
     ) -> Result<NeoTaskResponse, ApiError> {
-        #[derive(serde::Deserialize, Debug)]
-        struct ToolCallRaw {
-            #[serde(default)]
-            id: String,
-            #[serde(default)]
-            name: String,
-            #[serde(default)]
-            args: Option<serde_json::Value>,
-        }
-
-        #[derive(serde::Deserialize, Debug)]
-        #[serde(rename_all = "camelCase")]
-        #[allow(dead_code)]
-        struct EventBody {
-            #[serde(rename = "type")]
-            #[serde(default)]
-            body_type: String,
-            #[serde(default)]
-            #[serde(deserialize_with = "deserialize_content")]
-            content: String,
-            #[serde(default)]
-            timestamp: Option<String>,
-            #[serde(default)]
-            tool_calls: Vec<ToolCallRaw>,
-            #[serde(default)]
-            name: Option<String>,
-            #[serde(default)]
-            tool_call_id: Option<String>,
-            #[serde(default)]
-            message: Option<String>,
-            #[serde(default)]
-            is_error: bool,
-        }
-
-        fn deserialize_content<'de, D>(deserializer: D) -> Result<String, D::Error>
-        where
-            D: serde::Deserializer<'de>,
-        {
-            use serde::Deserialize;
-            let value = serde_json::Value::deserialize(deserializer)?;
-            match value {
-                serde_json::Value::String(s) => Ok(s),
-                serde_json::Value::Null => Ok(String::new()),
-                other => Ok(other.to_string()),
-            }
-        }
-
-        #[derive(serde::Deserialize, Debug)]
-        #[serde(rename_all = "camelCase")]
-        #[allow(dead_code)]
-        struct TaskEvent {
-            #[serde(rename = "type")]
-            event_type: String,
-            #[serde(default)]
-            event_body: Option<EventBody>,
-        }
+        let page = self
+            .get_neo_task_events_since(org, task_id, &NeoEventCursor::default())
+            .await?;
 
-        #[derive(serde::Deserialize, Debug)]
-        #[serde(rename_all = "camelCase")]
-        struct EventsResponse {
-            #[serde(default)]
-            events: Vec<TaskEvent>,
-            #[serde(default)]
-            continuation_token: Option<String>,
-        }
-
-        fn event_to_message(event: TaskEvent) -> Option<NeoMessage> {
-            event
-                .event_body
-                .and_then(|body| match body.body_type.as_str() {
-                    "user_message" => Some(NeoMessage {
-                        role: "user".to_string(),
-                        content: body.content,
-                        message_type: NeoMessageType::UserMessage,
-                        timestamp: body.timestamp,
-                        tool_calls: vec![],
-                        tool_name: None,
-                    }),
-                    "assistant_message" => {
-                        let tool_calls: Vec<NeoToolCall> = body
-                            .tool_calls
-                            .into_iter()
-                            .map(|tc| NeoToolCall {
-                                id: tc.id,
-                                name: tc.name,
-                                args: tc.args,
-                            })
-                            .collect();
-                        Some(NeoMessage {
-                            role: "assistant".to_string(),
-                            content: body.content,
-                            message_type: NeoMessageType::AssistantMessage,
-                            timestamp: body.timestamp,
-                            tool_calls,
-                            tool_name: None,
-                        })
-                    }
-                    "exec_tool_call" => Some(NeoMessage {
-                        role: "tool".to_string(),
-                        content: format!(
-                            "Executing: {}",
-                            body.name.as_deref().unwrap_or("unknown")
-                        ),
-                        message_type: NeoMessageType::ToolCall,
-                        timestamp: body.timestamp,
-                        tool_calls: vec![],
-                        tool_name: body.name,
-                    }),
-                    "tool_response" => {
-                        let is_error = body.is_error;
-                        let display_content = if is_error {
-                            body.content.clone()
-                        } else if let Ok(json) =
-                            serde_json::from_str::<serde_json::Value>(&body.content)
-                        {
-                            if let Some(result) = json.get("result") {
-                                let result_str = result.to_string();
-                                if result_str.len() > 200 {
-                                    format!("{}...", &result_str[..200])
-                                } else {
-                                    result_str
-                                }
-                            } else {
-                                body.content.clone()
-                            }
-                        } else {
-                            body.content.clone()
-                        };
-                        Some(NeoMessage {
-                            role: "tool_result".to_string(),
-                            content: display_content,
-                            message_type: if is_error {
-                                NeoMessageType::ToolError
-                            } else {
-                                NeoMessageType::ToolResponse
-                            },
-                            timestamp: body.timestamp,
-                            tool_calls: vec![],
-                            tool_name: body.name,
-                        })
-                    }
-                    "user_approval_request" => Some(NeoMessage {
-                        role: "system".to_string(),
-                        content: body
-                            .message
-                            .unwrap_or_else(|| "Approval requested".to_string()),
-                        message_type: NeoMessageType::ApprovalRequest,
-                        timestamp: body.timestamp,
-                        tool_calls: vec![],
-                        tool_name: None,
-                    }),
-                    "set_task_name" => Some(NeoMessage {
-                        role: "system".to_string(),
-                        content: format!("Task: {}", body.name.clone().unwrap_or_default()),
-                        message_type: NeoMessageType::TaskNameChange,
-                        timestamp: body.timestamp,
-                        tool_calls: vec![],
-                        tool_name: None,
-                    }),
-                    _ => None,
-                })
-        }
+        Ok(NeoTaskResponse {
+            task_id: task_id.to_string(),
+            status: None,
+            messages: page.messages,
+            has_more: false,
+            requires_approval: false,
+        })
+    }
 
-        let mut all_messages: Vec<NeoMessage> = Vec::new();
-        let mut continuation_token: Option<String> = None;
+    /// Get Neo task events after `cursor`, returning only the new messages and
+    /// the cursor to resume from on the next call.
+    /// Custom deserialization for polymorphic content — raw reqwest.
+    pub async fn get_neo_task_events_since(
+        &self,
+        org: &str,
+        task_id: &str,
+        cursor: &NeoEventCursor,
+    ) -> Result<NeoEventsPage, ApiError> {
+        let mut messages: Vec<NeoMessage> = Vec::new();
+        let mut page_token = cursor.page_token.clone();
+        let mut seen_in_page = cursor.seen_in_page;
         let max_pages = 10;
 
         for _ in 0..max_pages {
-            let url = if let Some(ref token) = continuation_token {
-                format!(
-                    "{}/api/preview/agents/{}/tasks/{}/events?pageSize=100&continuationToken={}",
-                    self.config.base_url, org, task_id, token
-                )
-            } else {
-                format!(
-                    "{}/api/preview/agents/{}/tasks/{}/events?pageSize=100",
-                    self.config.base_url, org, task_id
-                )
-            };
+            let mut url = format!(
+                "{}/api/preview/agents/{}/tasks/{}/events?pageSize=100",
+                self.config.base_url, org, task_id
+            );
+            if let Some(ref token) = page_token {
+                url.push_str("&continuationToken=");
+                url.push_str(&urlencoding::encode(token));
+            }
 
             let response = self.client.get(&url).send().await?;
 
@@ -1394,26 +2594,34 @@ impl PulumiClient {
                 continuation_token: None,
             });
 
-            let page_messages: Vec<NeoMessage> = data
-                .events
-                .into_iter()
-                .filter_map(event_to_message)
-                .collect();
-
-            all_messages.extend(page_messages);
+            // Skip events we already consumed from this page on a previous call
+            let page_len = data.events.len();
+            messages.extend(
+                data.events
+                    .into_iter()
+                    .skip(seen_in_page)
+                    .filter_map(event_to_message),
+            );
 
-            if data.continuation_token.is_none() {
-                break;
+            match data.continuation_token {
+                Some(next) if page_len > 0 => {
+                    page_token = Some(next);
+                    seen_in_page = 0;
+                }
+                _ => {
+                    // Last page: remember how far we got so the next call only sees new events
+                    seen_in_page = seen_in_page.max(page_len);
+                    break;
+                }
             }
-            continuation_token = data.continuation_token;
         }
 
-        Ok(NeoTaskResponse {
-            task_id: task_id.to_string(),
-            status: None,
-            messages: all_messages,
-            has_more: false,
-            requires_approval: false,
+        Ok(NeoEventsPage {
+            messages,
+            cursor: NeoEventCursor {
+                page_token,
+                seen_in_page,
+            },
         })
     }
 
@@ -1421,53 +2629,50 @@ impl PulumiClient {
     // Resource Search API (via generated client)
     // ─────────────────────────────────────────────────────────────
 
-    /// Search resources (with pagination)
+    /// Fetch one page of resources matching a Pulumi search query
     pub async fn search_resources(
         &self,
         org: Option<&str>,
-        query: &str,
-    ) -> Result<Vec<Resource>, ApiError> {
+        params: &ResourceSearchParams,
+    ) -> Result<ResourceSearchPage, ApiError> {
         let org = self.org_or_default(org)?;
 
-        let mut all_resources = Vec::new();
-        let mut page: i64 = 1;
-        let page_size: i64 = 100;
-
-        loop {
-            let resp = self
-                .gen
-                .get_org_resource_search_v2_query()
-                .org_name(org)
-                .query(query)
-                .page(page)
-                .size(page_size)
-                .send()
-                .await
-                .map_err(map_gen_err)?;
-
-            let data = resp.into_inner();
-            let fetched_count = data.resources.len();
-            let resources: Vec<Resource> = data.resources.into_iter().map(Into::into).collect();
-            all_resources.extend(resources);
-
-            let has_next = data
-                .pagination
-                .as_ref()
-                .and_then(|p| p.next.as_ref())
-                .is_some();
-
-            if !has_next || fetched_count < page_size as usize {
-                break;
-            }
-
-            page += 1;
-
-            if page > 100 {
-                break;
-            }
+        let mut request = self
+            .gen
+            .get_org_resource_search_v2_query()
+            .org_name(org)
+            .query(params.query.as_str())
+            .page(params.page)
+            .size(params.page_size);
+        if let Some(ref sort) = params.sort {
+            request = request.sort(vec![sort.clone()]).asc(params.ascending);
         }
 
-        Ok(all_resources)
+        let data = request.send().await.map_err(map_gen_err)?.into_inner();
+        let has_more = data
+            .pagination
+            .as_ref()
+            .and_then(|p| p.next.as_ref())
+            .is_some();
+
+        Ok(ResourceSearchPage {
+            resources: data.resources.into_iter().map(Into::into).collect(),
+            total: data.total.and_then(|t| u64::try_from(t).ok()),
+            has_more,
+        })
+    }
+
+    /// Count all resources in the organization without downloading them
+    pub async fn count_resources(&self, org: Option<&str>) -> Result<u64, ApiError> {
+        let params = ResourceSearchParams {
+            query: String::new(),
+            page: 1,
+            page_size: 1,
+            sort: None,
+            ascending: true,
+        };
+        let page = self.search_resources(org, &params).await?;
+        Ok(page.total.unwrap_or(page.resources.len() as u64))
     }
 
     // ─────────────────────────────────────────────────────────────
@@ -1501,7 +2706,6 @@ impl PulumiClient {
     }
 
     /// Get current user info
-    #[allow(dead_code)]
     pub async fn get_current_user(&self) -> Result<User, ApiError> {
         let resp = self
             .gen
@@ -1717,6 +2921,16 @@ mod tests {
         assert!(msg.contains("Conflict"), "should mention conflict: {msg}");
     }
 
+    #[test]
+    fn api_error_rotation_display() {
+        let err = ApiError::Rotation("connection refused at dbCreds".to_string());
+        let msg = format!("{}", err);
+        assert_eq!(
+            msg,
+            "Rotators reported errors: connection refused at dbCreds"
+        );
+    }
+
     #[test]
     fn api_error_api_response_display() {
         let err = ApiError::ApiResponse {
@@ -1738,6 +2952,30 @@ mod tests {
         );
     }
 
+    // ─────────────────────────────────────────────────────────────
+    // Console URL derivation
+    // ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn console_url_for_maps_api_host_to_app_host() {
+        assert_eq!(
+            console_url_for("https://api.pulumi.com"),
+            "https://app.pulumi.com"
+        );
+        assert_eq!(
+            console_url_for("https://api.pulumi.example.com/"),
+            "https://app.pulumi.example.com"
+        );
+    }
+
+    #[test]
+    fn console_url_for_keeps_unknown_hosts() {
+        assert_eq!(
+            console_url_for("http://localhost:8080"),
+            "http://localhost:8080"
+        );
+    }
+
     // ─────────────────────────────────────────────────────────────
     // PulumiClient::new validation
     // ─────────────────────────────────────────────────────────────
@@ -1851,6 +3089,556 @@ mod tests {
         }
     }
 
+    #[test]
+    fn stack_resources_response_accepts_wrapped_and_bare_entries() {
+        let json = r#"{
+            "resources": [
+                { "resource": { "urn": "urn:pulumi:dev::app::pulumi:pulumi:Stack::app-dev", "type": "pulumi:pulumi:Stack" } },
+                {
+                    "urn": "urn:pulumi:dev::app::aws:s3/bucket:Bucket::logs",
+                    "type": "aws:s3/bucket:Bucket",
+                    "id": "logs-1234",
+                    "custom": true,
+                    "provider": "urn:pulumi:dev::app::pulumi:providers:aws::default_6_0_0::abc",
+                    "inputs": { "acl": "private" },
+                    "outputs": { "arn": "arn:aws:s3:::logs-1234", "tags": null }
+                }
+            ],
+            "region": "",
+            "version": 3
+        }"#;
+
+        let data: StackResourcesResponse = serde_json::from_str(json).expect("should parse");
+        let resources: Vec<StackResource> = data.resources.into_iter().map(Into::into).collect();
+
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].name(), "app-dev");
+        assert!(resources[0].inputs.is_null());
+        assert_eq!(resources[1].id.as_deref(), Some("logs-1234"));
+        assert_eq!(resources[1].provider_label().as_deref(), Some("aws"));
+        assert_eq!(resources[1].inputs["acl"], "private");
+    }
+
+    #[test]
+    fn create_task_message_attaches_entities() {
+        let stack = NeoEntity {
+            entity_type: Some("stack".to_string()),
+            name: Some("prod".to_string()),
+            project: Some("web".to_string()),
+            ..NeoEntity::default()
+        };
+        let message = NeoCreateTaskMessage {
+            message_type: "user_message".to_string(),
+            content: "Why did update 12 of this stack fail?".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            commands: None,
+            entity_diff: attach_entities(&[stack]),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            serde_json::json!({
+                "type": "user_message",
+                "content": "Why did update 12 of this stack fail?",
+                "timestamp": "2026-01-01T00:00:00Z",
+                "entity_diff": {
+                    "add": [{"type": "stack", "name": "prod", "project": "web"}],
+                    "remove": [],
+                },
+            })
+        );
+        assert!(attach_entities(&[]).is_none());
+    }
+
+    #[test]
+    fn strip_color_directives_removes_markup() {
+        assert_eq!(
+            strip_color_directives("<{%fg 1%}>error: <{%reset%}>bucket exists<{%reset%}>"),
+            "error: bucket exists"
+        );
+        assert_eq!(
+            strip_color_directives("plain <{%unterminated"),
+            "plain <{%unterminated"
+        );
+    }
+
+    #[test]
+    fn update_events_path_follows_update_kind() {
+        assert_eq!(update_events_path("update"), "update");
+        assert_eq!(update_events_path("preview"), "preview");
+        assert_eq!(update_events_path("refresh"), "refresh");
+        assert_eq!(update_events_path("destroy"), "destroy");
+        assert_eq!(update_events_path("import"), "update");
+    }
+
+    #[test]
+    fn update_events_response_maps_engine_events() {
+        let json = r#"{
+            "events": [
+                { "sequence": 1, "timestamp": 100, "type": "preludeEvent", "preludeEvent": { "config": {} } },
+                { "sequence": 2, "timestamp": 101, "type": "resourcePreEvent", "resourcePreEvent": {
+                    "metadata": { "op": "update", "urn": "urn:pulumi:dev::app::aws:s3/bucket:Bucket::logs",
+                                  "type": "aws:s3/bucket:Bucket", "diffs": ["tags", "acl"] } } },
+                { "sequence": 3, "timestamp": 102, "type": "diagnosticEvent", "diagnosticEvent": {
+                    "urn": "urn:pulumi:dev::app::aws:s3/bucket:Bucket::logs",
+                    "message": "<{%fg 1%}>access denied<{%reset%}>\n", "severity": "error", "color": "raw" } },
+                { "sequence": 4, "timestamp": 103, "type": "summaryEvent", "summaryEvent": {
+                    "durationSeconds": 7, "resourceChanges": { "same": 4, "update": 1 } } }
+            ],
+            "continuationToken": null
+        }"#;
+
+        let data: UpdateEventsResponse = serde_json::from_str(json).expect("should parse");
+        let events: Vec<UpdateEvent> = data
+            .events
+            .into_iter()
+            .filter_map(engine_event_to_update_event)
+            .collect();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].kind, UpdateEventKind::ResourcePre);
+        assert_eq!(events[0].op.as_deref(), Some("update"));
+        assert_eq!(events[0].resource_name(), Some("logs"));
+        assert_eq!(events[0].diffs, vec!["tags", "acl"]);
+        assert_eq!(events[1].kind, UpdateEventKind::Diagnostic);
+        assert_eq!(events[1].message, "access denied");
+        assert_eq!(events[1].severity.as_deref(), Some("error"));
+        assert_eq!(events[2].message, "Finished in 7s: 4 same, 1 update");
+    }
+
+    // ═════════════════════════════════════════════════════════════
+    // Neo event paging against a local mock server
+    // ═════════════════════════════════════════════════════════════
+
+    const MOCK_PAGE_SIZE: usize = 2;
+
+    fn user_event(content: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "agentResponse",
+            "eventBody": { "type": "user_message", "content": content }
+        })
+    }
+
+    fn assistant_event(content: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "agentResponse",
+            "eventBody": { "type": "assistant_message", "content": content }
+        })
+    }
+
+    /// Serve the Neo events endpoint from `events`, two events per page.
+    /// Continuation tokens are the offset of the next page.
+    async fn spawn_mock_events_server(
+        events: std::sync::Arc<std::sync::Mutex<Vec<serde_json::Value>>>,
+    ) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server address");
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let events = events.clone();
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+
+                    let request = String::from_utf8_lossy(&buf);
+                    let path = request.split_whitespace().nth(1).unwrap_or("/");
+                    let start = path
+                        .split_once("continuationToken=")
+                        .and_then(|(_, t)| t.split('&').next())
+                        .and_then(|t| t.parse::<usize>().ok())
+                        .unwrap_or(0);
+
+                    let body = {
+                        let events = events.lock().unwrap();
+                        let end = (start + MOCK_PAGE_SIZE).min(events.len());
+                        let page = events.get(start..end).unwrap_or_default().to_vec();
+                        let token = (end < events.len()).then(|| end.to_string());
+                        serde_json::json!({ "events": page, "continuationToken": token })
+                            .to_string()
+                    };
+
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn mock_client(base_url: String) -> PulumiClient {
+        PulumiClient::from_config(ApiConfig {
+            base_url,
+            access_token: "pul-test-token".to_string(),
+            organization: Some("test-org".to_string()),
+        })
+        .expect("client should build")
+    }
+
+    #[tokio::test]
+    async fn neo_events_since_follows_pages_then_returns_only_new_events() {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(vec![
+            user_event("hello"),
+            assistant_event("hi there"),
+            assistant_event("working on it"),
+        ]));
+        let client = mock_client(spawn_mock_events_server(events.clone()).await);
+
+        // First call walks every page
+        let first = client
+            .get_neo_task_events_since("test-org", "task-1", &NeoEventCursor::default())
+            .await
+            .expect("first fetch");
+        let contents: Vec<_> = first.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["hello", "hi there", "working on it"]);
+        assert_eq!(first.cursor.page_token.as_deref(), Some("2"));
+        assert_eq!(first.cursor.seen_in_page, 1);
+
+        // Nothing new yet
+        let idle = client
+            .get_neo_task_events_since("test-org", "task-1", &first.cursor)
+            .await
+            .expect("idle fetch");
+        assert!(idle.messages.is_empty());
+        assert_eq!(idle.cursor, first.cursor);
+
+        // New events land partly in the last seen page and partly in a new page
+        events
+            .lock()
+            .unwrap()
+            .extend([assistant_event("step two"), assistant_event("done")]);
+
+        let second = client
+            .get_neo_task_events_since("test-org", "task-1", &idle.cursor)
+            .await
+            .expect("second fetch");
+        let contents: Vec<_> = second.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["step two", "done"]);
+        assert_eq!(second.cursor.page_token.as_deref(), Some("4"));
+        assert_eq!(second.cursor.seen_in_page, 1);
+    }
+
+    #[tokio::test]
+    async fn neo_task_events_returns_full_history() {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(vec![
+            user_event("a"),
+            assistant_event("b"),
+            assistant_event("c"),
+            assistant_event("d"),
+            assistant_event("e"),
+        ]));
+        let client = mock_client(spawn_mock_events_server(events).await);
+
+        let response = client
+            .get_neo_task_events("test-org", "task-1")
+            .await
+            .expect("fetch");
+        assert_eq!(response.messages.len(), 5);
+    }
+
+    #[test]
+    fn neo_confirmation_answers_the_approval_request_by_id() {
+        let event: TaskEvent = serde_json::from_value(serde_json::json!({
+            "type": "agentResponse",
+            "eventBody": {
+                "type": "user_approval_request",
+                "id": "approval-1",
+                "message": "Run pulumi up on dev?"
+            }
+        }))
+        .expect("parse event");
+        let message = event_to_message(event).expect("approval request message");
+        assert_eq!(message.approval_request_id.as_deref(), Some("approval-1"));
+
+        assert_eq!(
+            confirmation_event(
+                "approval-1",
+                false,
+                Some("Preview first"),
+                "2026-01-01T00:00:00Z"
+            ),
+            serde_json::json!({
+                "type": "user_confirmation",
+                "approval_request_id": "approval-1",
+                "ok": false,
+                "instructions": "Preview first",
+                "timestamp": "2026-01-01T00:00:00Z"
+            })
+        );
+        assert_eq!(
+            confirmation_event("approval-1", true, Some("  "), "2026-01-01T00:00:00Z"),
+            serde_json::json!({
+                "type": "user_confirmation",
+                "approval_request_id": "approval-1",
+                "ok": true,
+                "timestamp": "2026-01-01T00:00:00Z"
+            })
+        );
+    }
+
+    /// Serve one ESC environment: GET returns the YAML with an ETag, /check flags
+    /// definitions containing `bad:`, and PATCH only succeeds with a matching If-Match
+    async fn spawn_mock_esc_server(yaml: &str) -> String {
+        spawn_mock_esc_server_with(yaml, true).await
+    }
+
+    /// Like `spawn_mock_esc_server`, with or without an ETag on PATCH responses
+    async fn spawn_mock_esc_server_with(yaml: &str, patch_etag: bool) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server address");
+        // (definition, revision, draft)
+        let env = std::sync::Arc::new(std::sync::Mutex::new((
+            yaml.to_string(),
+            1u32,
+            None::<String>,
+        )));
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let env = env.clone();
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    let header_end = loop {
+                        if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                            break i + 4;
+                        }
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    };
+                    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+                    let header = |name: &str| {
+                        head.lines().find_map(|l| {
+                            let (k, v) = l.split_once(':')?;
+                            k.eq_ignore_ascii_case(name).then(|| v.trim().to_string())
+                        })
+                    };
+                    let length: usize = header("content-length")
+                        .and_then(|l| l.parse().ok())
+                        .unwrap_or(0);
+                    while buf.len() < header_end + length {
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+                    let body = String::from_utf8_lossy(&buf[header_end..]).to_string();
+                    let mut parts = head.split_whitespace();
+                    let method = parts.next().unwrap_or_default();
+                    let path = parts.next().unwrap_or_default();
+
+                    let (status, etag, body) = {
+                        let mut env = env.lock().unwrap();
+                        let current = format!("\"{}\"", env.1);
+                        match method {
+                            "GET" if path.contains("/drafts/") => match env.2.clone() {
+                                Some(draft) => ("200 OK", None, draft),
+                                None => ("404 Not Found", None, String::new()),
+                            },
+                            "GET" => ("200 OK", Some(current), env.0.clone()),
+                            "POST" if path.ends_with("/drafts") => {
+                                if header("if-match").is_some_and(|tag| tag != current) {
+                                    ("409 Conflict", None, String::new())
+                                } else {
+                                    env.2 = Some(body);
+                                    (
+                                        "200 OK",
+                                        None,
+                                        r#"{"changeRequestId":"cr-1","latestRevisionNumber":1}"#
+                                            .to_string(),
+                                    )
+                                }
+                            }
+                            "POST" if path.ends_with("/check") => {
+                                match body
+                                    .lines()
+                                    .position(|l| l.trim_start().starts_with("bad:"))
+                                {
+                                    Some(i) => (
+                                        "400 Bad Request",
+                                        None,
+                                        serde_json::json!({ "diagnostics": [{
+                                            "range": { "environment": "app/dev",
+                                                "begin": { "line": i + 1, "column": 3, "byte": 0 },
+                                                "end": { "line": i + 1, "column": 6, "byte": 0 } },
+                                            "summary": "unknown property",
+                                            "path": "values.bad"
+                                        }] })
+                                        .to_string(),
+                                    ),
+                                    None => ("200 OK", None, "{}".to_string()),
+                                }
+                            }
+                            "PATCH" if header("if-match").as_deref() == Some(current.as_str()) => {
+                                env.0 = body;
+                                env.1 += 1;
+                                let etag = patch_etag.then(|| format!("\"{}\"", env.1));
+                                ("200 OK", etag, "{}".to_string())
+                            }
+                            "PATCH" => ("412 Precondition Failed", None, String::new()),
+                            _ => ("404 Not Found", None, String::new()),
+                        }
+                    };
+
+                    let etag = etag.map(|e| format!("ETag: {}\r\n", e)).unwrap_or_default();
+                    let response = format!(
+                        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        etag,
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn esc_check_reports_diagnostics_with_positions() {
+        let client = mock_client(spawn_mock_esc_server("values: {}\n").await);
+
+        let clean = client
+            .check_esc_environment("test-org", "app", "dev", "values:\n  region: us-east-1\n")
+            .await
+            .expect("check");
+        assert!(clean.is_empty());
+
+        let diagnostics = client
+            .check_esc_environment("test-org", "app", "dev", "values:\n  bad: true\n")
+            .await
+            .expect("check");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].summary, "unknown property");
+        assert_eq!(diagnostics[0].path.as_deref(), Some("values.bad"));
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert_eq!(diagnostics[0].column, Some(3));
+    }
+
+    #[tokio::test]
+    async fn esc_update_rejects_stale_if_match() {
+        let client = mock_client(spawn_mock_esc_server("values:\n  a: 1\n").await);
+
+        let details = client
+            .get_esc_environment("test-org", "app", "dev")
+            .await
+            .expect("get");
+        let loaded = details.if_match();
+        assert_eq!(loaded.as_deref(), Some("\"1\""));
+
+        // First save with the loaded ETag succeeds and hands back the new one
+        let next = client
+            .update_esc_environment(
+                "test-org",
+                "app",
+                "dev",
+                "values:\n  a: 2\n",
+                loaded.as_deref(),
+            )
+            .await
+            .expect("save");
+        assert_eq!(next.as_deref(), Some("\"2\""));
+
+        // Saving again with the stale ETag is a conflict, and the first save is kept
+        let stale = client
+            .update_esc_environment(
+                "test-org",
+                "app",
+                "dev",
+                "values:\n  a: 3\n",
+                loaded.as_deref(),
+            )
+            .await;
+        assert!(matches!(stale, Err(ApiError::EnvironmentConflict)));
+        let details = client
+            .get_esc_environment("test-org", "app", "dev")
+            .await
+            .expect("get");
+        assert_eq!(details.yaml.as_deref(), Some("values:\n  a: 2\n"));
+    }
+
+    #[tokio::test]
+    async fn esc_update_without_etag_header_fetches_the_new_tag() {
+        let client = mock_client(spawn_mock_esc_server_with("values:\n  a: 1\n", false).await);
+
+        let next = client
+            .update_esc_environment("test-org", "app", "dev", "values:\n  a: 2\n", Some("\"1\""))
+            .await
+            .expect("save");
+        assert_eq!(next.as_deref(), Some("\"2\""));
+
+        // The fetched tag protects the next save like a returned one would
+        client
+            .update_esc_environment(
+                "test-org",
+                "app",
+                "dev",
+                "values:\n  a: 3\n",
+                next.as_deref(),
+            )
+            .await
+            .expect("second save");
+    }
+
+    #[tokio::test]
+    async fn esc_draft_proposes_without_saving() {
+        let client = mock_client(spawn_mock_esc_server("values:\n  a: 1\n").await);
+
+        let draft = client
+            .create_esc_draft("test-org", "app", "dev", "values:\n  a: 2\n", Some("\"1\""))
+            .await
+            .expect("draft");
+        assert_eq!(
+            draft,
+            EscDraft {
+                change_request_id: "cr-1".to_string(),
+                revision: 1,
+            }
+        );
+
+        // The environment keeps its definition; the draft holds the proposal
+        let details = client
+            .get_esc_environment("test-org", "app", "dev")
+            .await
+            .expect("get");
+        assert_eq!(details.yaml.as_deref(), Some("values:\n  a: 1\n"));
+        let proposed = client
+            .get_esc_draft("test-org", "app", "dev", "cr-1")
+            .await
+            .expect("get draft");
+        assert_eq!(proposed, "values:\n  a: 2\n");
+
+        let stale = client
+            .create_esc_draft("test-org", "app", "dev", "values: {}\n", Some("\"0\""))
+            .await;
+        assert!(matches!(stale, Err(ApiError::EnvironmentConflict)));
+    }
+
     // ═════════════════════════════════════════════════════════════
     // Integration tests (require PULUMI_ACCESS_TOKEN in .env)
     // ═════════════════════════════════════════════════════════════
@@ -39,6 +39,152 @@ impl From<gen::OrgEnvironment> for domain::EscEnvironmentSummary {
     }
 }
 
+impl From<gen::EnvironmentRevision> for domain::EscRevision {
+    fn from(r: gen::EnvironmentRevision) -> Self {
+        Self {
+            number: r.number,
+            created: r.created.to_rfc3339(),
+            creator: r.creator_name.or(r.creator_login),
+            tags: r.tags,
+            retracted: r.retracted.map(|x| domain::EscRevisionRetraction {
+                replacement: x.replacement,
+                at: x.at.to_rfc3339(),
+                by: x.by_name.or(x.by_login),
+                reason: x.reason.filter(|r| !r.is_empty()),
+            }),
+        }
+    }
+}
+
+impl From<gen::EnvironmentRevisionTag> for domain::EscRevisionTag {
+    fn from(t: gen::EnvironmentRevisionTag) -> Self {
+        Self {
+            name: t.name,
+            revision: t.revision,
+            modified: t.modified.to_rfc3339(),
+            editor: t.editor_name.or(t.editor_login),
+        }
+    }
+}
+
+impl From<gen::SecretRotationEvent> for domain::EscRotationEvent {
+    fn from(e: gen::SecretRotationEvent) -> Self {
+        Self {
+            id: e.id,
+            created: e.created.to_rfc3339(),
+            completed: e.completed.map(|c| c.to_rfc3339()),
+            status: e.status,
+            pre_revision: e.pre_rotation_revision,
+            post_revision: e.post_rotation_revision,
+            error: e.error_message.filter(|m| !m.is_empty()),
+            scheduled: e.scheduled_action_id.is_some_and(|id| !id.is_empty()),
+            rotations: e
+                .rotations
+                .into_iter()
+                .map(|r| domain::EscSecretRotation {
+                    path: r.environment_path,
+                    status: r.status,
+                    error: r.error_message.filter(|m| !m.is_empty()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<gen::ScheduledAction> for domain::EscSchedule {
+    fn from(s: gen::ScheduledAction) -> Self {
+        // Unset times come back as empty strings
+        let time = |t: String| Some(t).filter(|t| !t.is_empty());
+        Self {
+            id: s.id,
+            kind: s.kind.to_string(),
+            cron: s.schedule_cron.filter(|c| !c.is_empty()),
+            once: s.schedule_once.filter(|o| !o.is_empty()),
+            next_execution: time(s.next_execution),
+            last_executed: time(s.last_executed),
+            paused: s.paused,
+        }
+    }
+}
+
+impl From<gen::ChangeRequest> for domain::EscChangeRequest {
+    fn from(c: gen::ChangeRequest) -> Self {
+        Self {
+            id: c.id,
+            status: c.status,
+            description: c.description,
+            created: c.created_at.to_rfc3339(),
+            created_by: c.created_by.name,
+            project: c.entity.project,
+            env: c.entity.name,
+            revision: c.latest_revision_number,
+            approval: None,
+        }
+    }
+}
+
+impl From<gen::GetChangeRequestResponse> for domain::EscChangeRequest {
+    fn from(c: gen::GetChangeRequestResponse) -> Self {
+        let gates = c
+            .gate_evaluation
+            .applicable_gates
+            .into_iter()
+            .map(|g| domain::EscChangeGate {
+                name: g.name,
+                satisfied: g.satisfied,
+                required_approvals: g.rule_details.required_approvals,
+                approvers: g
+                    .rule_details
+                    .approvers
+                    .into_iter()
+                    .map(|u| u.name)
+                    .collect(),
+            })
+            .collect();
+        Self {
+            id: c.id,
+            status: c.status,
+            description: c.description,
+            created: c.created_at.to_rfc3339(),
+            created_by: c.created_by.name,
+            project: c.entity.project,
+            env: c.entity.name,
+            revision: c.latest_revision_number,
+            approval: Some(domain::EscApproval {
+                satisfied: c.gate_evaluation.satisfied,
+                gates,
+            }),
+        }
+    }
+}
+
+impl TryFrom<gen::EnvironmentReferrer> for domain::EscReferrer {
+    /// The referrer is of a kind this client does not know
+    type Error = ();
+
+    fn try_from(r: gen::EnvironmentReferrer) -> Result<Self, ()> {
+        if let Some(env) = r.environment {
+            Ok(Self::Environment {
+                project: env.project,
+                name: env.name,
+                revision: env.revision,
+            })
+        } else if let Some(stack) = r.stack {
+            Ok(Self::Stack {
+                project: stack.project,
+                stack: stack.stack,
+                version: stack.version,
+            })
+        } else if let Some(account) = r.insights_account {
+            Ok(Self::InsightsAccount {
+                name: account.account_name,
+            })
+        } else {
+            Err(())
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────
 // Neo Task conversions
 // ─────────────────────────────────────────────────────────────
@@ -121,6 +267,13 @@ impl From<gen::ResourceResult> for domain::Resource {
             project: r.project,
             package: Some(r.package),
             modified: r.modified,
+            urn: r.urn,
+            created: r.created,
+            protected: r.protected.unwrap_or_default(),
+            parent_urn: r.parent_urn,
+            provider_urn: r.provider_urn,
+            dependencies: r.dependencies,
+            dependents: r.dependents,
         }
     }
 }
@@ -516,6 +669,135 @@ mod tests {
         assert_eq!(env.name, "", "None name should become empty string");
     }
 
+    #[test]
+    fn esc_revision_conversion_prefers_display_names() {
+        let at = "2024-06-20T14:30:00Z"
+            .parse::<chrono::DateTime<Utc>>()
+            .unwrap();
+        let gen_revision: gen::EnvironmentRevision = gen::EnvironmentRevision::builder()
+            .number(7)
+            .created(at)
+            .creator_login(Some("jdoe".to_string()))
+            .creator_name(Some("Jane Doe".to_string()))
+            .tags(vec!["stable".to_string()])
+            .retracted(Some(gen::EnvironmentRevisionRetracted {
+                at,
+                by_login: Some("ops".to_string()),
+                by_name: None,
+                reason: Some(String::new()),
+                replacement: 6,
+            }))
+            .try_into()
+            .expect("valid EnvironmentRevision");
+        let revision: domain::EscRevision = gen_revision.into();
+
+        assert_eq!(revision.number, 7);
+        assert_eq!(revision.created, "2024-06-20T14:30:00+00:00");
+        assert_eq!(revision.creator.as_deref(), Some("Jane Doe"));
+        assert_eq!(revision.tags, vec!["stable".to_string()]);
+        let retracted = revision.retracted.expect("retraction");
+        assert_eq!(retracted.replacement, 6);
+        assert_eq!(retracted.by.as_deref(), Some("ops"), "falls back to login");
+        assert_eq!(retracted.reason, None, "empty reason is dropped");
+    }
+
+    #[test]
+    fn esc_revision_tag_conversion_maps_all_fields() {
+        let at = "2024-06-20T14:30:00Z"
+            .parse::<chrono::DateTime<Utc>>()
+            .unwrap();
+        let gen_tag: gen::EnvironmentRevisionTag = gen::EnvironmentRevisionTag::builder()
+            .name("prod")
+            .revision(4)
+            .created(at)
+            .modified(at)
+            .editor_login(Some("jdoe".to_string()))
+            .try_into()
+            .expect("valid EnvironmentRevisionTag");
+        let tag: domain::EscRevisionTag = gen_tag.into();
+
+        assert_eq!(tag.name, "prod");
+        assert_eq!(tag.revision, 4);
+        assert_eq!(tag.editor.as_deref(), Some("jdoe"));
+    }
+
+    #[test]
+    fn esc_referrer_conversion_picks_the_set_kind() {
+        let stack = gen::EnvironmentReferrer {
+            environment: None,
+            insights_account: None,
+            stack: Some(gen::EnvironmentStackReferrer {
+                project: "web".to_string(),
+                stack: "prod".to_string(),
+                version: 42,
+            }),
+        };
+        assert_eq!(
+            domain::EscReferrer::try_from(stack),
+            Ok(domain::EscReferrer::Stack {
+                project: "web".to_string(),
+                stack: "prod".to_string(),
+                version: 42,
+            })
+        );
+        assert_eq!(
+            domain::EscReferrer::try_from(gen::EnvironmentReferrer::default()),
+            Err(())
+        );
+    }
+
+    #[test]
+    fn esc_change_request_conversion_reads_target_and_approvals() {
+        let gate = gen::ChangeGateEvaluation {
+            id: "gate-1".to_string(),
+            name: "Production approvals".to_string(),
+            rule_details: gen::ChangeGateRuleEvaluation {
+                approvers: vec![make_user_info("Alice")],
+                required_approvals: Some(2),
+                rule_type: "approval_required".to_string(),
+            },
+            satisfied: false,
+        };
+        let response: gen::GetChangeRequestResponse = gen::GetChangeRequestResponse::builder()
+            .id("cr-1")
+            .status("open")
+            .description("Raise the replica count")
+            .action(gen::GetChangeRequestResponseAction::Update)
+            .created_at(Utc::now())
+            .created_by(make_user_info("Bob"))
+            .entity(gen::TargetEntity {
+                entity_type: "environment".to_string(),
+                project: Some("app".to_string()),
+                name: Some("prod".to_string()),
+            })
+            .gate_evaluation(gen::ChangeRequestGateEvaluation {
+                applicable_gates: vec![gate],
+                satisfied: false,
+            })
+            .latest_revision_number(3)
+            .org_id("org-1")
+            .try_into()
+            .expect("valid GetChangeRequestResponse");
+
+        let request = domain::EscChangeRequest::from(response);
+        assert_eq!(request.env_ref(), Some(("app", "prod")));
+        assert_eq!(request.created_by, "Bob");
+        assert_eq!(request.revision, 3);
+        assert!(request.is_open());
+        assert_eq!(
+            request.approval,
+            Some(domain::EscApproval {
+                satisfied: false,
+                gates: vec![domain::EscChangeGate {
+                    name: "Production approvals".to_string(),
+                    satisfied: false,
+                    required_approvals: Some(2),
+                    approvers: vec!["Alice".to_string()],
+                }],
+            })
+        );
+    }
+
     // ═════════════════════════════════════════════════════════════
     // Neo Task conversion tests
     // ═════════════════════════════════════════════════════════════
@@ -725,6 +1007,13 @@ mod tests {
             .package("aws")
             .module("s3")
             .modified(Some("2024-01-15".to_string()))
+            .urn(Some(
+                "urn:pulumi:dev::my-project::aws:s3:Bucket::my-bucket".to_string(),
+            ))
+            .protected(Some(true))
+            .dependencies(vec![
+                "urn:pulumi:dev::my-project::aws:kms:Key::key".to_string()
+            ])
             .try_into()
             .expect("valid ResourceResult");
 
@@ -737,6 +1026,13 @@ mod tests {
         assert_eq!(resource.project, Some("my-project".to_string()));
         assert_eq!(resource.package, Some("aws".to_string()));
         assert_eq!(resource.modified, Some("2024-01-15".to_string()));
+        assert_eq!(
+            resource.urn.as_deref(),
+            Some("urn:pulumi:dev::my-project::aws:s3:Bucket::my-bucket")
+        );
+        assert!(resource.protected);
+        assert_eq!(resource.dependencies.len(), 1);
+        assert!(resource.dependents.is_empty());
     }
 
     #[test]
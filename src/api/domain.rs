@@ -42,7 +42,6 @@ pub struct Stack {
 }
 
 impl Stack {
-    #[allow(dead_code)]
     pub fn full_name(&self) -> String {
         format!(
             "{}/{}/{}",
@@ -79,6 +78,15 @@ pub struct StackUpdate {
     pub resource_changes: Option<ResourceChanges>,
 }
 
+impl StackUpdate {
+    pub fn start_time_formatted(&self) -> String {
+        self.start_time
+            .and_then(|t| DateTime::from_timestamp(t, 0))
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "Unknown".to_string())
+    }
+}
+
 /// Resource changes in an update
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceChanges {
@@ -92,6 +100,176 @@ pub struct ResourceChanges {
     pub same: Option<i32>,
 }
 
+/// Full details of a single stack update
+#[derive(Debug, Clone)]
+pub struct UpdateDetails {
+    pub update_id: String,
+    pub version: i32,
+    pub kind: String,
+    pub result: String,
+    pub message: String,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub resource_changes: Option<ResourceChanges>,
+    pub resource_count: Option<i32>,
+    pub requested_by: Option<UpdatePerson>,
+    pub commit: Option<UpdateCommit>,
+}
+
+impl UpdateDetails {
+    pub fn duration_formatted(&self) -> Option<String> {
+        let secs = self.end_time?.checked_sub(self.start_time?)?;
+        Some(if secs >= 60 {
+            format!("{}m {}s", secs / 60, secs % 60)
+        } else {
+            format!("{}s", secs)
+        })
+    }
+
+    /// Everyone involved in the update with their role, without repeats
+    pub fn people(&self) -> Vec<(&'static str, &UpdatePerson)> {
+        let commit = self.commit.as_ref();
+        let candidates = [
+            ("Requested by", self.requested_by.as_ref()),
+            ("Commit author", commit.and_then(|c| c.author.as_ref())),
+            ("Committer", commit.and_then(|c| c.committer.as_ref())),
+        ];
+
+        let mut people: Vec<(&'static str, &UpdatePerson)> = Vec::new();
+        for (role, person) in candidates {
+            if let Some(person) = person {
+                if !people.iter().any(|(_, p)| p.same_person(person)) {
+                    people.push((role, person));
+                }
+            }
+        }
+        people
+    }
+}
+
+/// Person involved in an update (requester, commit author, ...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePerson {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub github_login: String,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+impl UpdatePerson {
+    pub fn display_name(&self) -> String {
+        match (self.name.is_empty(), self.github_login.is_empty()) {
+            (false, false) if self.name != self.github_login => {
+                format!("{} (@{})", self.name, self.github_login)
+            }
+            (false, _) => self.name.clone(),
+            (true, false) => format!("@{}", self.github_login),
+            (true, true) => self.email.clone().unwrap_or_else(|| "Unknown".to_string()),
+        }
+    }
+
+    fn same_person(&self, other: &UpdatePerson) -> bool {
+        (!self.github_login.is_empty() && self.github_login == other.github_login)
+            || (!self.name.is_empty() && self.name == other.name)
+    }
+}
+
+/// Source commit an update was run from
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateCommit {
+    #[serde(default)]
+    pub sha: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub author: Option<UpdatePerson>,
+    #[serde(default)]
+    pub committer: Option<UpdatePerson>,
+}
+
+/// Kind of engine event shown in the update timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateEventKind {
+    /// A resource step is starting
+    ResourcePre,
+    /// A resource step finished
+    ResourceOutputs,
+    /// A resource step failed
+    ResourceFailed,
+    Diagnostic,
+    Stdout,
+    Summary,
+}
+
+/// Engine event from an update, flattened for display
+#[derive(Debug, Clone)]
+pub struct UpdateEvent {
+    /// Unix timestamp (seconds)
+    pub timestamp: i64,
+    pub kind: UpdateEventKind,
+    pub urn: Option<String>,
+    pub resource_type: Option<String>,
+    /// Step operation (create, update, delete, replace, same, ...)
+    pub op: Option<String>,
+    /// Properties that changed
+    pub diffs: Vec<String>,
+    pub severity: Option<String>,
+    pub message: String,
+}
+
+impl UpdateEvent {
+    /// Resource name (last segment of the URN)
+    pub fn resource_name(&self) -> Option<&str> {
+        self.urn.as_deref().and_then(|urn| urn.rsplit("::").next())
+    }
+}
+
+/// Resource in a stack's latest checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackResource {
+    pub urn: String,
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Provider reference (`<provider urn>::<provider id>`)
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub custom: bool,
+    #[serde(default)]
+    pub inputs: serde_json::Value,
+    #[serde(default)]
+    pub outputs: serde_json::Value,
+}
+
+impl StackResource {
+    /// Resource name (last segment of the URN)
+    pub fn name(&self) -> &str {
+        self.urn.rsplit("::").next().unwrap_or(&self.urn)
+    }
+
+    /// Short provider label, e.g. `aws` or `aws::eu-west` for explicit providers
+    pub fn provider_label(&self) -> Option<String> {
+        // urn:pulumi:<stack>::<project>::pulumi:providers:<pkg>::<name>::<id>
+        let reference = self.provider.as_deref()?;
+        let mut parts = reference.split("::").skip(2);
+        let package = parts.next()?.strip_prefix("pulumi:providers:")?;
+        match parts.next() {
+            Some(name) if !name.starts_with("default") => Some(format!("{}::{}", package, name)),
+            _ => Some(package.to_string()),
+        }
+    }
+}
+
 /// Organization-level stack update (includes stack info)
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -215,11 +393,195 @@ pub struct EscEnvironmentDetails {
     pub modified: Option<String>,
     #[serde(default)]
     pub revision: Option<i64>,
+    /// ETag of the loaded definition, sent back as If-Match when saving
+    #[serde(default)]
+    pub etag: Option<String>,
     // Catch any other fields
     #[serde(flatten)]
     pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
+impl EscEnvironmentDetails {
+    /// Version tag to send as If-Match: the ETag, or else the revision number
+    pub fn if_match(&self) -> Option<String> {
+        self.etag
+            .clone()
+            .or_else(|| self.revision.map(|r| format!("\"{}\"", r)))
+    }
+}
+
+/// Where to clone an ESC environment to, and what to carry over
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscCloneRequest {
+    pub project: String,
+    pub name: String,
+    /// Copy the full revision history instead of only the current definition
+    pub preserve_history: bool,
+    /// Copy environment tags and version tags
+    pub preserve_tags: bool,
+}
+
+/// A saved revision of an ESC environment definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscRevision {
+    pub number: i64,
+    pub created: String,
+    /// Display name (or login) of the user who saved the revision
+    pub creator: Option<String>,
+    /// Version tags pointing at this revision (e.g. `latest`, `stable`)
+    pub tags: Vec<String>,
+    pub retracted: Option<EscRevisionRetraction>,
+}
+
+/// Why and by whom a revision was retracted
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscRevisionRetraction {
+    /// Revision served in place of the retracted one
+    pub replacement: i64,
+    pub at: String,
+    pub by: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// A named version tag on an ESC environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscRevisionTag {
+    pub name: String,
+    pub revision: i64,
+    pub modified: String,
+    /// Display name (or login) of the user who last moved the tag
+    pub editor: Option<String>,
+}
+
+/// Something that refers to an ESC environment
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EscReferrer {
+    /// Another environment importing it
+    Environment {
+        project: String,
+        name: String,
+        revision: i64,
+    },
+    /// A stack using it in its config
+    Stack {
+        project: String,
+        stack: String,
+        version: i64,
+    },
+    /// An Insights account using it for credentials
+    InsightsAccount { name: String },
+}
+
+/// One run of secret rotation for an ESC environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscRotationEvent {
+    pub id: String,
+    pub created: String,
+    pub completed: Option<String>,
+    /// Overall status, e.g. `succeeded`, `failed`, `in_progress`
+    pub status: String,
+    /// Revision the rotation started from
+    pub pre_revision: i64,
+    /// Revision written with the rotated secrets
+    pub post_revision: Option<i64>,
+    pub error: Option<String>,
+    /// Started by a schedule rather than by a user
+    pub scheduled: bool,
+    pub rotations: Vec<EscSecretRotation>,
+}
+
+/// Result of rotating one rotator within a rotation run
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscSecretRotation {
+    /// Path of the rotator in the environment, e.g. `dbCreds`
+    pub path: String,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+/// A schedule attached to an ESC environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscSchedule {
+    pub id: String,
+    /// What runs, e.g. `environment_rotation`
+    pub kind: String,
+    /// Cron expression for repeating schedules
+    pub cron: Option<String>,
+    /// Time of a one-off schedule
+    pub once: Option<String>,
+    pub next_execution: Option<String>,
+    pub last_executed: Option<String>,
+    pub paused: bool,
+}
+
+/// A draft of an environment change, saved as a change request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscDraft {
+    pub change_request_id: String,
+    /// Change request revision the draft was saved as
+    pub revision: i64,
+}
+
+/// A change request proposing an update to an ESC environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscChangeRequest {
+    pub id: String,
+    /// e.g. `draft`, `open`, `applied`, `closed`
+    pub status: String,
+    pub description: String,
+    pub created: String,
+    pub created_by: String,
+    /// Targeted environment's project and name
+    pub project: Option<String>,
+    pub env: Option<String>,
+    /// Latest revision of the proposal
+    pub revision: i64,
+    /// Approval state; only loaded for a single change request
+    pub approval: Option<EscApproval>,
+}
+
+impl EscChangeRequest {
+    /// `project/name` of the targeted environment
+    pub fn env_ref(&self) -> Option<(&str, &str)> {
+        Some((self.project.as_deref()?, self.env.as_deref()?))
+    }
+
+    /// Still awaiting submission, approval or application
+    pub fn is_open(&self) -> bool {
+        !matches!(self.status.as_str(), "applied" | "closed")
+    }
+}
+
+/// How far a change request is through its approval gates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscApproval {
+    /// Every gate is satisfied, so the change request can be applied
+    pub satisfied: bool,
+    pub gates: Vec<EscChangeGate>,
+}
+
+/// One approval gate of a change request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscChangeGate {
+    pub name: String,
+    pub satisfied: bool,
+    pub required_approvals: Option<i64>,
+    /// Names of the users who approved
+    pub approvers: Vec<String>,
+}
+
+/// A problem found when checking an ESC environment definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscDiagnostic {
+    pub summary: String,
+    /// Property path the problem relates to (e.g. `values.aws.creds`)
+    pub path: Option<String>,
+    /// 1-based line in the YAML definition
+    pub line: Option<usize>,
+    /// 1-based column in the YAML definition
+    pub column: Option<usize>,
+}
+
 /// ESC Open session response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EscOpenResponse {
@@ -277,6 +639,14 @@ pub struct NeoTask {
     pub policies: Vec<NeoPolicy>,
 }
 
+/// One page of Neo tasks
+#[derive(Debug, Clone, Default)]
+pub struct NeoTaskPage {
+    pub tasks: Vec<NeoTask>,
+    /// Token for fetching the next page; `None` on the last page
+    pub continuation_token: Option<String>,
+}
+
 /// User who started a Neo task
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -307,32 +677,32 @@ pub struct NeoLinkedPR {
 
 /// Entity involved in a Neo task
 /// Supports types: "stack", "repository", "pull_request", "policy_issue"
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NeoEntity {
     /// Entity type: "stack", "repository", "pull_request", "policy_issue"
     #[serde(rename = "type")]
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub entity_type: Option<String>,
     /// Entity name (used by stack, repository)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// Project name (used by stack)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub project: Option<String>,
     /// Stack name (used by stack)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stack: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     /// Organization name (used by repository)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub org: Option<String>,
     /// Git forge type (used by repository, e.g., "github", "gitlab")
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub forge: Option<String>,
     /// Policy issue ID (used by policy_issue)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -385,6 +755,10 @@ pub struct NeoMessage {
     pub tool_calls: Vec<NeoToolCall>,
     /// Tool name (for tool responses)
     pub tool_name: Option<String>,
+    /// ID of the tool call this message runs or answers (for tool calls and responses)
+    pub tool_call_id: Option<String>,
+    /// ID the answer to an approval request refers to (for approval requests)
+    pub approval_request_id: Option<String>,
 }
 
 /// Neo Create Task API response
@@ -398,7 +772,6 @@ pub struct NeoCreateTaskResponse {
 /// Neo Update Task request (for PATCH endpoint)
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct NeoUpdateTaskRequest {
     /// Whether to share the task with other org members
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -416,6 +789,22 @@ pub struct NeoTaskResponse {
     pub requires_approval: bool,
 }
 
+/// Position in a Neo task's event stream, used to fetch only new events.
+/// `page_token` is the continuation token of the last (possibly partial) page,
+/// `seen_in_page` the number of raw events already consumed from it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NeoEventCursor {
+    pub page_token: Option<String>,
+    pub seen_in_page: usize,
+}
+
+/// New Neo messages plus the cursor to resume from
+#[derive(Debug, Clone)]
+pub struct NeoEventsPage {
+    pub messages: Vec<NeoMessage>,
+    pub cursor: NeoEventCursor,
+}
+
 // ─────────────────────────────────────────────────────────────
 // Neo Slash Commands Types
 // ─────────────────────────────────────────────────────────────
@@ -461,6 +850,16 @@ pub struct NeoCreateTaskMessage {
     /// Commands map - only present when using slash commands
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commands: Option<std::collections::HashMap<String, NeoSlashCommandPayload>>,
+    /// Entities to attach to the task - only present when asking about a selection
+    #[serde(rename = "entity_diff", skip_serializing_if = "Option::is_none")]
+    pub entity_diff: Option<NeoEntityDiff>,
+}
+
+/// Entities a user message adds to or removes from a task
+#[derive(Debug, Clone, Serialize)]
+pub struct NeoEntityDiff {
+    pub add: Vec<NeoEntity>,
+    pub remove: Vec<NeoEntity>,
 }
 
 /// Slash command data included in the payload
@@ -492,6 +891,43 @@ pub struct Resource {
     pub package: Option<String>,
     #[serde(default)]
     pub modified: Option<String>,
+    #[serde(default)]
+    pub urn: Option<String>,
+    #[serde(default)]
+    pub created: Option<String>,
+    #[serde(default)]
+    pub protected: bool,
+    #[serde(default)]
+    pub parent_urn: Option<String>,
+    #[serde(default)]
+    pub provider_urn: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub dependents: Vec<String>,
+}
+
+/// Sort order and page for a resource search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceSearchParams {
+    /// Pulumi search query (`type:`, `stack:`, `project:`, `package:`, `modified:`, free text)
+    pub query: String,
+    /// 1-based page number
+    pub page: i64,
+    pub page_size: i64,
+    /// Field to sort by (`name`, `type`, `stack`, ...); server default when `None`
+    pub sort: Option<String>,
+    pub ascending: bool,
+}
+
+/// One page of resource search results
+#[derive(Debug, Clone, Default)]
+pub struct ResourceSearchPage {
+    pub resources: Vec<Resource>,
+    /// Number of resources matching the query across all pages
+    pub total: Option<u64>,
+    /// Whether the server reported a next page
+    pub has_more: bool,
 }
 
 /// Policy violation
@@ -11,8 +11,12 @@ mod convert;
 mod domain;
 mod generated;
 
-pub use client::{ApiError, PulumiClient};
+pub use client::{parse_engine_event, ApiError, PulumiClient};
 pub use domain::{
-    EscEnvironmentSummary, NeoMessage, NeoMessageType, NeoSlashCommand, NeoTask, OrgStackUpdate,
-    RegistryPackage, RegistryTemplate, Resource, ResourceSummaryPoint, Service, Stack,
+    EscChangeRequest, EscCloneRequest, EscEnvironmentSummary, EscReferrer, EscRevision,
+    EscRevisionTag, EscRotationEvent, EscSchedule, NeoEntity, NeoEventCursor, NeoMessage,
+    NeoMessageType, NeoSlashCommand, NeoTask, NeoTaskPage, NeoTaskUser, NeoToolCall,
+    NeoUpdateTaskRequest, OrgStackUpdate, RegistryPackage, RegistryTemplate, Resource,
+    ResourceChanges, ResourceSearchPage, ResourceSearchParams, ResourceSummaryPoint, Service,
+    Stack, StackResource, StackUpdate, UpdateDetails, UpdateEvent, UpdateEventKind,
 };
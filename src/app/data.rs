@@ -73,6 +73,12 @@ impl App {
                 }
             }
 
+            // The signed-in user, for showing only their own Neo tasks
+            match client.get_current_user().await {
+                Ok(user) => self.state.user_login = user.github_login,
+                Err(e) => log::warn!("Failed to load the current user: {}", e),
+            }
+
             // Load data for current org (non-blocking)
             self.refresh_data();
             // Note: is_loading will be cleared when all spawned tasks complete
@@ -127,9 +133,9 @@ impl App {
             let org3 = org.clone();
             let tx3 = tx.clone();
             tokio::spawn(async move {
-                match client3.list_neo_tasks(org3.as_deref()).await {
-                    Ok(tasks) => {
-                        let _ = tx3.send(DataLoadResult::NeoTasks(tasks)).await;
+                match client3.list_neo_tasks(org3.as_deref(), None).await {
+                    Ok(page) => {
+                        let _ = tx3.send(DataLoadResult::NeoTasks(page)).await;
                     }
                     Err(e) => {
                         let _ = tx3.send(DataLoadResult::Error(format!("Neo: {}", e))).await;
@@ -141,9 +147,9 @@ impl App {
             let org4 = org.clone();
             let tx4 = tx.clone();
             tokio::spawn(async move {
-                match client4.search_resources(org4.as_deref(), "").await {
-                    Ok(resources) => {
-                        let _ = tx4.send(DataLoadResult::Resources(resources)).await;
+                match client4.count_resources(org4.as_deref()).await {
+                    Ok(count) => {
+                        let _ = tx4.send(DataLoadResult::ResourceCount(count)).await;
                     }
                     Err(e) => {
                         let _ = tx4
@@ -258,13 +264,21 @@ impl App {
                     let _ = tx10.send(DataLoadResult::NeoSlashCommands(vec![])).await;
                 }
             });
+
+            // Re-run the Resources view search (not counted in pending loads)
+            self.spawn_resource_search();
         }
     }
 
     /// Process async data loading results (non-blocking)
     pub(super) fn process_data_results(&mut self) {
         while let Ok(result) = self.data_result_rx.try_recv() {
-            self.pending_data_loads = self.pending_data_loads.saturating_sub(1);
+            if !matches!(
+                result,
+                DataLoadResult::ResourceSearch { .. } | DataLoadResult::NeoTaskPage { .. }
+            ) {
+                self.pending_data_loads = self.pending_data_loads.saturating_sub(1);
+            }
 
             match result {
                 DataLoadResult::Stacks(stacks) => {
@@ -276,16 +290,21 @@ impl App {
                     self.state.esc_environments = envs.clone();
                     self.esc_list.set_items(envs);
                 }
-                DataLoadResult::NeoTasks(tasks) => {
-                    self.state.neo_tasks = tasks.clone();
-                    self.neo_tasks_list.set_items(tasks);
+                DataLoadResult::NeoTasks(page) => {
+                    self.apply_neo_tasks(page);
+                }
+                DataLoadResult::NeoTaskPage { after, result } => {
+                    self.apply_neo_task_page(after, result);
                 }
                 DataLoadResult::NeoSlashCommands(commands) => {
                     log::info!("Received {} Neo slash commands", commands.len());
                     self.state.neo_slash_commands = commands;
                 }
-                DataLoadResult::Resources(resources) => {
-                    self.state.resources = resources;
+                DataLoadResult::ResourceCount(count) => {
+                    self.state.resource_count = count;
+                }
+                DataLoadResult::ResourceSearch { params, result } => {
+                    self.apply_resource_search(params, result);
                 }
                 DataLoadResult::Services(services) => {
                     self.state.services = services.clone();
@@ -0,0 +1,669 @@
+//! ESC view operations
+//!
+//! This module handles loading environment definitions, masking, revealing
+//! and copying resolved values, the edit → check → diff → save flow of the
+//! YAML editor, creating, cloning and deleting environments, and the
+//! revision history pane: viewing and diffing revisions, tagging, rolling
+//! back and retracting.
+
+use tui_scrollview::ScrollViewState;
+
+use crate::api::EscCloneRequest;
+use crate::components::{EditorDiagnostic, StatefulList, TextEditor};
+use crate::ui::{self, syntax::highlight_yaml};
+
+use super::types::EscEnvDialog;
+use super::App;
+
+/// An ESC environment as (org, project, name)
+pub(super) type EscEnvRef = (String, String, String);
+
+/// Definition written to newly created environments
+const STARTER_ENVIRONMENT_YAML: &str = "\
+# Import other environments, e.g.
+# imports:
+#   - shared/base
+values:
+  # Plain values and secrets, e.g.
+  # region: us-west-2
+  # apiKey:
+  #   fn::secret: change-me
+  environmentVariables: {}
+  pulumiConfig: {}
+";
+
+/// Check a project or environment name: letters, digits, `-`, `_` and `.`
+fn validate_env_name(kind: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(format!("{} is required", kind));
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!(
+            "{} may only contain letters, digits, '-', '_' and '.'",
+            kind
+        ));
+    }
+    Ok(())
+}
+
+impl App {
+    /// The environment selected in the ESC list
+    pub(super) fn selected_esc_env(&self) -> Option<EscEnvRef> {
+        self.esc_list.selected().map(|env| {
+            (
+                env.organization.clone(),
+                env.project.clone(),
+                env.name.clone(),
+            )
+        })
+    }
+
+    /// Load an environment's definition (and the ETag to save it with) into the Definition pane.
+    /// Returns false if loading failed.
+    pub(super) async fn load_env_definition(&mut self, env: &EscEnvRef) -> bool {
+        let Some(client) = self.client.clone() else {
+            return false;
+        };
+        let (org, project, name) = env;
+
+        self.is_loading = true;
+        self.spinner.set_message("Loading definition...");
+        log::debug!(
+            "Loading ESC environment definition: org={}, project={}, name={}",
+            org,
+            project,
+            name
+        );
+
+        let result = client.get_esc_environment(org, project, name).await;
+        self.is_loading = false;
+
+        match result {
+            Ok(details) => {
+                // Cache syntax-highlighted content when loading (not on every render)
+                self.state.selected_env_yaml_highlighted =
+                    details.yaml.as_ref().map(|y| highlight_yaml(y));
+                self.state.selected_env_etag = details.if_match();
+                self.state.selected_env_yaml = details.yaml;
+                self.esc_definition_scroll = ScrollViewState::default();
+                log::debug!("ESC environment definition loaded successfully");
+                true
+            }
+            Err(e) => {
+                log::error!("Failed to load ESC environment definition: {}", e);
+                self.error = Some(format!("Failed to load definition: {}", e));
+                false
+            }
+        }
+    }
+
+    /// Show resolved values from opening the environment; secrets start out masked
+    pub(super) fn set_esc_values(&mut self, values: Option<serde_json::Value>) {
+        let rows = values.as_ref().map(ui::flatten_values).unwrap_or_default();
+        self.esc_values_list = StatefulList::with_items(rows);
+        self.esc_revealed_values.clear();
+        self.esc_value_copied = None;
+        self.esc_exported = None;
+        self.state.selected_env_values = values;
+    }
+
+    /// Reveal or re-mask the selected value (and any secrets below it)
+    pub(super) fn toggle_selected_esc_value(&mut self) {
+        let Some(row) = self.esc_values_list.selected() else {
+            return;
+        };
+        if !self.esc_revealed_values.remove(&row.path) {
+            self.esc_revealed_values.insert(row.path.clone());
+        }
+    }
+
+    /// Copy the selected value, or its path, to the clipboard (OSC 52).
+    /// Copying a masked secret copies the real value.
+    pub(super) fn copy_selected_esc_value(&mut self, path: bool) {
+        let Some(row) = self.esc_values_list.selected() else {
+            return;
+        };
+        let (text, what) = if path {
+            (row.path.clone(), "path")
+        } else {
+            (row.clipboard_text(), "value")
+        };
+
+        match crate::clipboard::copy_to_clipboard(&text) {
+            Ok(()) => self.esc_value_copied = Some(what),
+            Err(e) => {
+                log::warn!("Failed to copy {} to clipboard: {}", what, e);
+                self.error = Some(format!("Failed to copy {}: {}", what, e));
+            }
+        }
+    }
+
+    /// Open the YAML editor on `content`, saving against the loaded definition's ETag
+    pub(super) fn open_esc_editor(&mut self, env: EscEnvRef, content: &str) {
+        self.esc_editor = TextEditor::with_content(content);
+        self.esc_editing_etag = self.state.selected_env_etag.clone();
+        self.esc_editing_env = Some(env);
+        self.show_esc_editor = true;
+    }
+
+    pub(super) fn close_esc_editor(&mut self) {
+        self.show_esc_editor = false;
+        self.esc_editing_env = None;
+        self.esc_editing_etag = None;
+        self.esc_save_diff = None;
+    }
+
+    /// Validate the editor content. Problems are marked in the editor; a clean
+    /// definition opens the diff against the loaded one for confirmation.
+    pub(super) async fn review_esc_edits(&mut self) {
+        let (Some((org, project, env_name)), Some(client)) =
+            (self.esc_editing_env.clone(), self.client.clone())
+        else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Checking environment...");
+        let content = self.esc_editor.content();
+        let result = client
+            .check_esc_environment(&org, &project, &env_name, &content)
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(diagnostics) if !diagnostics.is_empty() => {
+                log::info!(
+                    "ESC environment check reported {} problem(s)",
+                    diagnostics.len()
+                );
+                let diagnostics: Vec<EditorDiagnostic> = diagnostics
+                    .into_iter()
+                    .map(|d| EditorDiagnostic {
+                        line: d.line.map(|l| l.saturating_sub(1)),
+                        message: match d.path {
+                            Some(path) => format!("{}: {}", path, d.summary),
+                            None => d.summary,
+                        },
+                    })
+                    .collect();
+                if let Some(line) = diagnostics.iter().find_map(|d| d.line) {
+                    self.esc_editor.go_to_line(line);
+                }
+                self.esc_editor.set_diagnostics(diagnostics);
+            }
+            Ok(_) => {
+                self.esc_editor.set_diagnostics(Vec::new());
+                let original = self.state.selected_env_yaml.as_deref().unwrap_or_default();
+                let diff = ui::unified_diff(original, &content);
+                if diff.is_empty() {
+                    // Edits were undone by hand; nothing to save
+                    self.close_esc_editor();
+                } else {
+                    self.esc_save_diff = Some(diff);
+                    self.esc_save_diff_scroll = ScrollViewState::default();
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to check ESC environment: {}", e);
+                self.error = Some(format!("Failed to check environment: {}", e));
+            }
+        }
+    }
+
+    /// Save the editor content, guarded by the ETag the definition was loaded with.
+    /// On failure (including a conflicting change) the editor stays open with the edits.
+    pub(super) async fn save_esc_environment(&mut self) {
+        let (Some((org, project, env_name)), Some(client)) =
+            (self.esc_editing_env.clone(), self.client.clone())
+        else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Saving environment...");
+        let content = self.esc_editor.content();
+        let result = client
+            .update_esc_environment(
+                &org,
+                &project,
+                &env_name,
+                &content,
+                self.esc_editing_etag.as_deref(),
+            )
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(etag) => {
+                log::info!("ESC environment saved successfully");
+                // Update the cached YAML; the next save must match the new version
+                self.state.selected_env_yaml_highlighted = Some(highlight_yaml(&content));
+                self.state.selected_env_yaml = Some(content);
+                self.state.selected_env_etag = etag;
+                self.close_esc_editor();
+                if self.esc_revisions_env.is_some() {
+                    self.reload_esc_revisions().await;
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to save ESC environment: {}", e);
+                self.error = Some(format!("Failed to save: {}", e));
+            }
+        }
+    }
+
+    /// Show the revision history of `env` in place of the environment details
+    pub(super) async fn open_esc_revisions(&mut self, env: EscEnvRef) {
+        self.esc_revisions_list.clear();
+        self.esc_revision_tags.clear();
+        self.esc_revision_yaml.clear();
+        self.esc_revision_diff_base = None;
+        self.esc_revision_content = None;
+        self.esc_revisions_env = Some(env);
+        self.reload_esc_revisions().await;
+    }
+
+    pub(super) fn close_esc_revisions(&mut self) {
+        self.esc_revisions_env = None;
+        self.esc_revision_prompt = None;
+        self.esc_revision_content = None;
+    }
+
+    /// Fetch revisions and tags again, keeping the selected revision
+    pub(super) async fn reload_esc_revisions(&mut self) {
+        let (Some((org, project, name)), Some(client)) =
+            (self.esc_revisions_env.clone(), self.client.clone())
+        else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Loading revisions...");
+        let (revisions, tags) = tokio::join!(
+            client.list_esc_revisions(&org, &project, &name),
+            client.list_esc_revision_tags(&org, &project, &name)
+        );
+        self.is_loading = false;
+
+        match revisions {
+            Ok(revisions) => {
+                let selected = self.esc_revisions_list.selected().map(|r| r.number);
+                let index = selected
+                    .and_then(|n| revisions.iter().position(|r| r.number == n))
+                    .unwrap_or(0);
+                self.esc_revisions_list.set_items(revisions);
+                if !self.esc_revisions_list.is_empty() {
+                    self.esc_revisions_list.select(Some(index));
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to load ESC revisions: {}", e);
+                self.error = Some(format!("Failed to load revisions: {}", e));
+                self.close_esc_revisions();
+                return;
+            }
+        }
+        match tags {
+            Ok(tags) => self.esc_revision_tags = tags,
+            Err(e) => log::warn!("Failed to load ESC revision tags: {}", e),
+        }
+
+        self.update_esc_revision_content().await;
+    }
+
+    /// Show the selected revision's YAML, or its diff from the marked base revision
+    pub(super) async fn update_esc_revision_content(&mut self) {
+        self.esc_revision_scroll = ScrollViewState::default();
+        self.esc_revision_content = None;
+        let Some(number) = self.esc_revisions_list.selected().map(|r| r.number) else {
+            return;
+        };
+        let Some(yaml) = self.esc_revision_yaml(number).await else {
+            return;
+        };
+
+        let content = match self.esc_revision_diff_base.filter(|base| *base != number) {
+            Some(base) => {
+                let Some(base_yaml) = self.esc_revision_yaml(base).await else {
+                    return;
+                };
+                ui::highlight_diff(&self.theme, &ui::unified_diff(&base_yaml, &yaml))
+            }
+            None => highlight_yaml(&yaml),
+        };
+        self.esc_revision_content = Some(content);
+    }
+
+    /// YAML of a revision, fetched once and then served from the cache
+    async fn esc_revision_yaml(&mut self, number: i64) -> Option<String> {
+        if let Some(yaml) = self.esc_revision_yaml.get(&number) {
+            return Some(yaml.clone());
+        }
+        let (org, project, name) = self.esc_revisions_env.clone()?;
+        let client = self.client.clone()?;
+
+        self.is_loading = true;
+        self.spinner
+            .set_message(format!("Loading revision {}...", number));
+        let result = client
+            .get_esc_environment_revision(&org, &project, &name, number)
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(yaml) => {
+                self.esc_revision_yaml.insert(number, yaml.clone());
+                Some(yaml)
+            }
+            Err(e) => {
+                log::error!("Failed to load ESC revision {}: {}", number, e);
+                self.error = Some(format!("Failed to load revision {}: {}", number, e));
+                None
+            }
+        }
+    }
+
+    /// Create `tag` on the selected revision, or move it there if it already exists
+    pub(super) async fn tag_selected_revision(&mut self, tag: &str) {
+        let (Some((org, project, name)), Some(client), Some(number)) = (
+            self.esc_revisions_env.clone(),
+            self.client.clone(),
+            self.esc_revisions_list.selected().map(|r| r.number),
+        ) else {
+            return;
+        };
+
+        let exists = self.esc_revision_tags.iter().any(|t| t.name == tag);
+        let result = if exists {
+            client
+                .update_esc_revision_tag(&org, &project, &name, tag, number)
+                .await
+        } else {
+            client
+                .create_esc_revision_tag(&org, &project, &name, tag, number)
+                .await
+        };
+
+        match result {
+            Ok(()) => {
+                log::info!(
+                    "{} tag '{}' on revision {} of {}/{}",
+                    if exists { "Moved" } else { "Created" },
+                    tag,
+                    number,
+                    project,
+                    name
+                );
+                self.reload_esc_revisions().await;
+            }
+            Err(e) => {
+                log::error!("Failed to tag ESC revision: {}", e);
+                self.error = Some(format!("Failed to tag revision {}: {}", number, e));
+            }
+        }
+    }
+
+    /// Retract the selected revision; the environment falls back to the revision before it
+    pub(super) async fn retract_selected_revision(&mut self, reason: Option<&str>) {
+        let (Some(env), Some(client), Some(number)) = (
+            self.esc_revisions_env.clone(),
+            self.client.clone(),
+            self.esc_revisions_list.selected().map(|r| r.number),
+        ) else {
+            return;
+        };
+        let (org, project, name) = &env;
+
+        self.is_loading = true;
+        self.spinner
+            .set_message(format!("Retracting revision {}...", number));
+        let result = client
+            .retract_esc_revision(org, project, name, number, reason)
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(()) => {
+                log::info!("Retracted revision {} of {}/{}", number, project, name);
+                self.reload_esc_revisions().await;
+                // Retracting the latest revision changes the current definition
+                if self.state.selected_env_yaml.is_some() {
+                    self.load_env_definition(&env).await;
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to retract ESC revision: {}", e);
+                self.error = Some(format!("Failed to retract revision {}: {}", number, e));
+            }
+        }
+    }
+
+    /// Re-save the selected revision as a new revision. Goes through the editor's
+    /// check and diff confirmation, against the latest definition and ETag.
+    pub(super) async fn rollback_to_selected_revision(&mut self) {
+        let (Some(env), Some(number)) = (
+            self.esc_revisions_env.clone(),
+            self.esc_revisions_list.selected().map(|r| r.number),
+        ) else {
+            return;
+        };
+        if self.esc_revisions_list.items().first().map(|r| r.number) == Some(number) {
+            self.error = Some(format!(
+                "Revision {} is already the current definition",
+                number
+            ));
+            return;
+        }
+
+        let Some(yaml) = self.esc_revision_yaml(number).await else {
+            return;
+        };
+        if !self.load_env_definition(&env).await {
+            return;
+        }
+
+        self.open_esc_editor(env, &yaml);
+        self.esc_editor.mark_modified();
+        self.review_esc_edits().await;
+    }
+
+    /// Open the create / clone / delete dialog for the selected environment
+    pub(super) fn open_esc_env_dialog(&mut self, dialog: EscEnvDialog) {
+        let selected = self.selected_esc_env();
+        self.esc_env_project_input.clear();
+        self.esc_env_name_input.clear();
+        match (dialog, selected) {
+            (EscEnvDialog::Create, selected) => {
+                // Most new environments join the project being browsed
+                if let Some((_, project, _)) = selected {
+                    self.esc_env_project_input.set_value(project);
+                }
+            }
+            (EscEnvDialog::Clone, Some((_, project, name))) => {
+                self.esc_env_project_input.set_value(project);
+                self.esc_env_name_input.set_value(format!("{}-copy", name));
+            }
+            (_, None) => return,
+            (EscEnvDialog::Delete, Some(_)) => {}
+        }
+        // Start on the name field; the project is usually right already
+        self.esc_env_dialog_focus = match dialog {
+            EscEnvDialog::Delete => 0,
+            _ => 1,
+        };
+        self.esc_clone_keep_history = true;
+        self.esc_clone_keep_tags = true;
+        self.esc_env_dialog = Some(dialog);
+    }
+
+    /// Run the dialog's action, then reload the environment list
+    pub(super) async fn submit_esc_env_dialog(&mut self) {
+        let (Some(dialog), Some(client)) = (self.esc_env_dialog, self.client.clone()) else {
+            return;
+        };
+        let Some(org) = self.state.organization.clone() else {
+            return;
+        };
+        let project = self.esc_env_project_input.value().trim().to_string();
+        let name = self.esc_env_name_input.value().trim().to_string();
+
+        if dialog != EscEnvDialog::Delete {
+            if let Err(e) = validate_env_name("Project", &project)
+                .and_then(|_| validate_env_name("Name", &name))
+            {
+                self.error = Some(e);
+                return;
+            }
+        }
+
+        self.is_loading = true;
+        // Problem that did not stop the action, shown once the list is reloaded
+        let mut warning = None;
+        let result = match dialog {
+            EscEnvDialog::Create => {
+                self.spinner
+                    .set_message(format!("Creating {}/{}...", project, name));
+                match client.create_esc_environment(&org, &project, &name).await {
+                    Ok(()) => {
+                        // The environment exists now, so the starter definition is best effort
+                        if let Err(e) = client
+                            .update_esc_environment(
+                                &org,
+                                &project,
+                                &name,
+                                STARTER_ENVIRONMENT_YAML,
+                                None,
+                            )
+                            .await
+                        {
+                            log::warn!(
+                                "Created {}/{} but failed to write the starter definition: {}",
+                                project,
+                                name,
+                                e
+                            );
+                            warning = Some(format!(
+                                "Created {}/{}, but failed to write the starter definition: {}",
+                                project, name, e
+                            ));
+                        }
+                        Ok(Some((org.clone(), project, name)))
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            EscEnvDialog::Clone => {
+                let Some((_, source_project, source_name)) = self.selected_esc_env() else {
+                    self.is_loading = false;
+                    return;
+                };
+                self.spinner
+                    .set_message(format!("Cloning {}/{}...", source_project, source_name));
+                let request = EscCloneRequest {
+                    project,
+                    name,
+                    preserve_history: self.esc_clone_keep_history,
+                    preserve_tags: self.esc_clone_keep_tags,
+                };
+                client
+                    .clone_esc_environment(&org, &source_project, &source_name, &request)
+                    .await
+                    .map(|_| Some((org.clone(), request.project, request.name)))
+            }
+            EscEnvDialog::Delete => {
+                let Some((_, project, env_name)) = self.selected_esc_env() else {
+                    self.is_loading = false;
+                    return;
+                };
+                // Only delete once the environment's name has been typed back exactly
+                if name != env_name {
+                    self.is_loading = false;
+                    self.error = Some(format!(
+                        "The typed name does not match '{}'; type it exactly to delete",
+                        env_name
+                    ));
+                    return;
+                }
+                self.spinner
+                    .set_message(format!("Deleting {}/{}...", project, env_name));
+                client
+                    .delete_esc_environment(&org, &project, &env_name)
+                    .await
+                    .map(|_| None)
+            }
+        };
+        self.is_loading = false;
+
+        match result {
+            Ok(select) => {
+                self.esc_env_dialog = None;
+                self.reload_esc_environments(select.as_ref()).await;
+                if warning.is_some() {
+                    self.error = warning;
+                }
+            }
+            Err(e) => {
+                log::error!("ESC environment {:?} failed: {}", dialog, e);
+                self.error = Some(format!("Failed to {}: {}", dialog.verb(), e));
+            }
+        }
+    }
+
+    /// Fetch the environment list again, selecting `select` if given
+    pub(super) async fn reload_esc_environments(&mut self, select: Option<&EscEnvRef>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Loading environments...");
+        let result = client
+            .list_esc_environments(self.state.organization.as_deref())
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(envs) => {
+                let index = select.and_then(|(_, project, name)| {
+                    envs.iter()
+                        .position(|e| &e.project == project && &e.name == name)
+                });
+                self.state.esc_environments = envs.clone();
+                self.esc_list.set_items(envs);
+                if index.is_some() {
+                    self.esc_list.select(index);
+                }
+
+                // The selection may now point at a different environment
+                self.state.selected_env_yaml = None;
+                self.state.selected_env_etag = None;
+                self.state.selected_env_yaml_highlighted = None;
+                self.set_esc_values(None);
+                self.esc_definition_scroll = ScrollViewState::default();
+            }
+            Err(e) => {
+                log::error!("Failed to reload ESC environments: {}", e);
+                self.error = Some(format!("Failed to load environments: {}", e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_env_name_accepts_esc_names() {
+        assert!(validate_env_name("Name", "feature-123_v2.1").is_ok());
+        assert_eq!(
+            validate_env_name("Project", ""),
+            Err("Project is required".to_string())
+        );
+        assert!(validate_env_name("Name", "my env").is_err());
+        assert!(validate_env_name("Name", "team/dev").is_err());
+    }
+}
@@ -0,0 +1,284 @@
+//! ESC drafts and change requests
+//!
+//! This module saves editor content as a draft instead of updating the
+//! environment, and manages the change request pane: listing open change
+//! requests, their approval gates and proposed diff or resolved values,
+//! and submitting, approving, applying or closing them.
+
+use tui_scrollview::ScrollViewState;
+
+use crate::api::EscChangeRequest;
+use crate::components::StatefulList;
+use crate::ui;
+
+use super::types::{EscChangeAction, EscChangeView};
+use super::App;
+
+impl App {
+    /// Show the organization's open change requests in place of the environment details
+    pub(super) async fn open_esc_change_requests(&mut self, select: Option<String>) {
+        let org = self
+            .selected_esc_env()
+            .map(|(org, _, _)| org)
+            .or_else(|| self.state.organization.clone());
+        let Some(org) = org else {
+            return;
+        };
+        self.esc_change_requests_org = Some(org);
+        self.esc_change_requests = StatefulList::new();
+        self.esc_change_view = EscChangeView::default();
+        self.reload_esc_change_requests(select.as_deref()).await;
+    }
+
+    pub(super) fn close_esc_change_requests(&mut self) {
+        self.esc_change_requests_org = None;
+        self.esc_change_content = None;
+        self.esc_change_values = None;
+        self.esc_change_confirm = None;
+        self.esc_change_prompt = false;
+    }
+
+    /// Fetch open change requests again, selecting `select` (by ID) or keeping the selection
+    pub(super) async fn reload_esc_change_requests(&mut self, select: Option<&str>) {
+        let (Some(org), Some(client)) = (self.esc_change_requests_org.clone(), self.client.clone())
+        else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Loading change requests...");
+        let result = client.list_esc_change_requests(&org).await;
+        self.is_loading = false;
+
+        let requests: Vec<EscChangeRequest> = match result {
+            Ok(requests) => requests.into_iter().filter(|r| r.is_open()).collect(),
+            Err(e) => {
+                log::error!("Failed to load change requests: {}", e);
+                self.error = Some(format!("Failed to load change requests: {}", e));
+                self.close_esc_change_requests();
+                return;
+            }
+        };
+        let selected = select
+            .map(String::from)
+            .or_else(|| self.esc_change_requests.selected().map(|r| r.id.clone()));
+        let index = selected
+            .and_then(|id| requests.iter().position(|r| r.id == id))
+            .unwrap_or(0);
+        self.esc_change_requests.set_items(requests);
+        if !self.esc_change_requests.is_empty() {
+            self.esc_change_requests.select(Some(index));
+        }
+        self.update_esc_change_details().await;
+    }
+
+    /// Load the selected change request's approval gates, and its diff
+    /// against the current definition or its resolved values
+    pub(super) async fn update_esc_change_details(&mut self) {
+        self.esc_change_scroll = ScrollViewState::default();
+        self.esc_change_content = None;
+        self.esc_change_values = None;
+        let (Some(org), Some(client), Some(request)) = (
+            self.esc_change_requests_org.clone(),
+            self.client.clone(),
+            self.esc_change_requests.selected().cloned(),
+        ) else {
+            return;
+        };
+        let Some((project, env)) = request.env_ref() else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Loading change request...");
+        let details = client.get_esc_change_request(&org, &request.id).await;
+        match details {
+            Ok(details) => {
+                if let Some(item) = self
+                    .esc_change_requests
+                    .items_mut()
+                    .iter_mut()
+                    .find(|r| r.id == details.id)
+                {
+                    *item = details;
+                }
+            }
+            Err(e) => log::warn!("Failed to load change request {}: {}", request.id, e),
+        }
+
+        match self.esc_change_view {
+            EscChangeView::Diff => {
+                let (current, draft) = tokio::join!(
+                    client.get_esc_environment(&org, project, env),
+                    client.get_esc_draft(&org, project, env, &request.id)
+                );
+                match (current, draft) {
+                    (Ok(current), Ok(draft)) => {
+                        let current = current.yaml.unwrap_or_default();
+                        let diff = ui::unified_diff(&current, &draft);
+                        self.esc_change_content = Some(ui::highlight_diff(&self.theme, &diff));
+                    }
+                    (Err(e), _) | (_, Err(e)) => {
+                        log::error!("Failed to load draft {}: {}", request.id, e);
+                        self.error = Some(format!("Failed to load draft: {}", e));
+                    }
+                }
+            }
+            EscChangeView::Values => {
+                match client.open_esc_draft(&org, project, env, &request.id).await {
+                    Ok(response) => {
+                        self.esc_change_values = Some(
+                            response
+                                .values
+                                .as_ref()
+                                .map(ui::flatten_values)
+                                .unwrap_or_default(),
+                        );
+                    }
+                    Err(e) => {
+                        log::error!("Failed to open draft {}: {}", request.id, e);
+                        self.error = Some(format!("Failed to open draft: {}", e));
+                    }
+                }
+            }
+        }
+        self.is_loading = false;
+    }
+
+    /// Switch between the proposed diff and the values the draft resolves to
+    pub(super) async fn toggle_esc_change_view(&mut self) {
+        self.esc_change_view = match self.esc_change_view {
+            EscChangeView::Diff => EscChangeView::Values,
+            EscChangeView::Values => EscChangeView::Diff,
+        };
+        self.update_esc_change_details().await;
+    }
+
+    /// Save the editor content as a draft change request, leaving the
+    /// environment untouched, and show it among the change requests
+    pub(super) async fn save_esc_draft(&mut self) {
+        let (Some((org, project, env_name)), Some(client)) =
+            (self.esc_editing_env.clone(), self.client.clone())
+        else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Saving draft...");
+        let content = self.esc_editor.content();
+        let result = client
+            .create_esc_draft(
+                &org,
+                &project,
+                &env_name,
+                &content,
+                self.esc_editing_etag.as_deref(),
+            )
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(draft) => {
+                self.close_esc_editor();
+                self.close_esc_revisions();
+                self.close_esc_graph();
+                self.close_esc_rotation();
+                self.open_esc_change_requests(Some(draft.change_request_id))
+                    .await;
+            }
+            Err(e) => {
+                log::error!("Failed to save ESC draft: {}", e);
+                self.error = Some(format!("Failed to save draft: {}", e));
+            }
+        }
+    }
+
+    /// Submit the selected draft for approval
+    pub(super) async fn submit_selected_change_request(&mut self, description: Option<&str>) {
+        let (Some(org), Some(client), Some(id)) = (
+            self.esc_change_requests_org.clone(),
+            self.client.clone(),
+            self.esc_change_requests.selected().map(|r| r.id.clone()),
+        ) else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Submitting change request...");
+        let result = client
+            .submit_esc_change_request(&org, &id, description)
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(()) => self.reload_esc_change_requests(None).await,
+            Err(e) => {
+                log::error!("Failed to submit change request {}: {}", id, e);
+                self.error = Some(format!("Failed to submit change request: {}", e));
+            }
+        }
+    }
+
+    /// Approve, apply or close the selected change request, as confirmed
+    pub(super) async fn run_esc_change_action(&mut self) {
+        let (Some(action), Some(org), Some(client), Some(request)) = (
+            self.esc_change_confirm.take(),
+            self.esc_change_requests_org.clone(),
+            self.client.clone(),
+            self.esc_change_requests.selected().cloned(),
+        ) else {
+            return;
+        };
+
+        self.is_loading = true;
+        let result = match action {
+            EscChangeAction::Approve => {
+                // The latest revision is approved
+                self.spinner.set_message("Approving change request...");
+                client
+                    .approve_esc_change_request(&org, &request.id, request.revision)
+                    .await
+            }
+            EscChangeAction::Apply => {
+                self.spinner.set_message("Applying change request...");
+                client
+                    .apply_esc_change_request(&org, &request.id)
+                    .await
+                    .map(|message| {
+                        if let Some(message) = message {
+                            log::info!("Applied change request {}: {}", request.id, message);
+                        }
+                    })
+            }
+            EscChangeAction::Close => {
+                self.spinner.set_message("Closing change request...");
+                client.close_esc_change_request(&org, &request.id).await
+            }
+        };
+        self.is_loading = false;
+
+        if let Err(e) = result {
+            log::error!(
+                "Failed to {} change request {}: {}",
+                action.verb(),
+                request.id,
+                e
+            );
+            self.error = Some(format!("Failed to {} change request: {}", action.verb(), e));
+            return;
+        }
+
+        // Applying writes a new revision of the environment
+        let applied_to_selected = action == EscChangeAction::Apply
+            && self.selected_esc_env().is_some_and(|(_, project, name)| {
+                request.env_ref() == Some((project.as_str(), name.as_str()))
+            });
+        if applied_to_selected {
+            self.state.selected_env_yaml = None;
+            self.state.selected_env_etag = None;
+            self.state.selected_env_yaml_highlighted = None;
+            self.set_esc_values(None);
+        }
+        self.reload_esc_change_requests(None).await;
+    }
+}
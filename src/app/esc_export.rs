@@ -0,0 +1,285 @@
+//! ESC export and run
+//!
+//! This module writes an opened environment's `environmentVariables` to a
+//! dotenv, shell or JSON file, asking before any secret is written to disk,
+//! and opens `pulumi env run` in the Commands view to start a shell or a
+//! configured command with those variables.
+
+use std::io::Write;
+
+use crate::commands::{CommandCategory, PULUMI_COMMANDS};
+use crate::ui;
+
+use super::types::EscExportFormat;
+use super::App;
+
+/// A variable from the resolved `environmentVariables`
+#[derive(Debug, Clone, PartialEq)]
+struct ExportVariable {
+    name: String,
+    value: String,
+    secret: bool,
+}
+
+/// Collect the resolved `environmentVariables`, in the order they are listed
+fn environment_variables(rows: &[ui::EscValue]) -> Vec<ExportVariable> {
+    let mut variables: Vec<ExportVariable> = Vec::new();
+    let below = rows
+        .iter()
+        .skip_while(|row| !(row.depth == 0 && row.label == "environmentVariables"))
+        .skip(1)
+        .take_while(|row| row.depth > 0);
+    for row in below {
+        if row.depth == 1 {
+            variables.push(ExportVariable {
+                name: row.label.clone(),
+                value: row.clipboard_text(),
+                secret: row.secret,
+            });
+        } else if let Some(variable) = variables.last_mut() {
+            // A secret nested in an object or array variable
+            variable.secret |= row.secret;
+        }
+    }
+    variables
+}
+
+/// Render variables in an export format
+fn format_export(format: EscExportFormat, variables: &[ExportVariable]) -> String {
+    match format {
+        EscExportFormat::Dotenv => variables
+            .iter()
+            .map(|v| {
+                let value = v
+                    .value
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n");
+                format!("{}=\"{}\"\n", v.name, value)
+            })
+            .collect(),
+        EscExportFormat::Shell => variables
+            .iter()
+            .map(|v| format!("export {}='{}'\n", v.name, v.value.replace('\'', "'\\''")))
+            .collect(),
+        EscExportFormat::Json => {
+            let object: serde_json::Map<String, serde_json::Value> = variables
+                .iter()
+                .map(|v| (v.name.clone(), serde_json::Value::String(v.value.clone())))
+                .collect();
+            let mut json = serde_json::to_string_pretty(&object).unwrap_or_default();
+            json.push('\n');
+            json
+        }
+    }
+}
+
+/// Write an export readable by the current user only, since it may hold secrets;
+/// an existing file is made private before anything is written to it
+fn write_private(path: &str, content: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(content.as_bytes())
+}
+
+impl App {
+    /// Variables the export dialog writes, from the opened environment's values
+    fn esc_export_variables(&self) -> Vec<ExportVariable> {
+        environment_variables(self.esc_values_list.items())
+    }
+
+    /// Number of variables the export dialog writes, and how many of them are secrets
+    pub(super) fn esc_export_counts(&self) -> (usize, usize) {
+        let variables = self.esc_export_variables();
+        let secrets = variables.iter().filter(|v| v.secret).count();
+        (variables.len(), secrets)
+    }
+
+    /// Show the export dialog for the opened environment's variables
+    pub(super) fn open_esc_export(&mut self) {
+        let Some((_, _, name)) = self.selected_esc_env() else {
+            return;
+        };
+        if self.state.selected_env_values.is_none() {
+            self.error = Some("Open the environment with 'o' before exporting".to_string());
+            return;
+        }
+        if self.esc_export_variables().is_empty() {
+            self.error = Some("The environment has no environmentVariables to export".to_string());
+            return;
+        }
+
+        let format = EscExportFormat::default();
+        self.esc_export_input.set_value(format.default_path(&name));
+        self.esc_export_input.set_focused(true);
+        self.esc_export = Some(format);
+        self.esc_export_confirm = false;
+        self.esc_export_overwrite = false;
+    }
+
+    pub(super) fn close_esc_export(&mut self) {
+        self.esc_export = None;
+        self.esc_export_confirm = false;
+        self.esc_export_overwrite = false;
+    }
+
+    /// Switch the export format; an untouched default path follows the format
+    pub(super) fn set_esc_export_format(&mut self, format: EscExportFormat) {
+        let (Some(current), Some((_, _, name))) = (self.esc_export, self.selected_esc_env()) else {
+            return;
+        };
+        if self.esc_export_input.value() == current.default_path(&name) {
+            self.esc_export_input.set_value(format.default_path(&name));
+        }
+        self.esc_export = Some(format);
+    }
+
+    /// Write the export, first asking for confirmation if it contains secrets
+    /// or would overwrite an existing file
+    pub(super) fn submit_esc_export(&mut self) {
+        let Some(format) = self.esc_export else {
+            return;
+        };
+        let path = self.esc_export_input.value().trim().to_string();
+        if path.is_empty() {
+            self.error = Some("Enter a file to export to".to_string());
+            return;
+        }
+        let (_, secrets) = self.esc_export_counts();
+        let exists = std::path::Path::new(&path).exists();
+        if (secrets > 0 || exists) && !self.esc_export_confirm {
+            self.esc_export_overwrite = exists;
+            self.esc_export_confirm = true;
+            return;
+        }
+
+        let variables = self.esc_export_variables();
+        match write_private(&path, &format_export(format, &variables)) {
+            Ok(()) => {
+                log::info!(
+                    "Exported {} variables as {} to {}",
+                    variables.len(),
+                    format.label(),
+                    path
+                );
+                self.esc_exported = Some(path);
+                self.close_esc_export();
+            }
+            Err(e) => {
+                log::error!("Failed to export to {}: {}", path, e);
+                self.error = Some(format!("Failed to write {}: {}", path, e));
+                self.esc_export_confirm = false;
+            }
+        }
+    }
+
+    /// Open `pulumi env run` for the selected environment in the Commands
+    /// view, with the configured command (a shell by default) prefilled
+    pub(super) fn launch_esc_run(&mut self) {
+        let Some((org, project, name)) = self.selected_esc_env() else {
+            return;
+        };
+        let Some(cmd) = PULUMI_COMMANDS.iter().find(|c| c.name == "env run") else {
+            return;
+        };
+
+        let prefill = [
+            ("environment", format!("{}/{}/{}", org, project, name)),
+            ("command", self.config.esc_run_command()),
+        ];
+        self.show_command_dialog(cmd, CommandCategory::Utilities, &prefill);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables() -> Vec<ExportVariable> {
+        let values = serde_json::json!({
+            "properties": {
+                "environmentVariables": {
+                    "value": {
+                        "API_URL": {"value": "https://api.example.com"},
+                        "TOKEN": {"value": "it's \"secret\"", "secret": true},
+                    }
+                },
+                "region": {"value": "us-west-2"}
+            }
+        });
+        environment_variables(&ui::flatten_values(&values))
+    }
+
+    #[test]
+    fn environment_variables_keep_secret_markers() {
+        assert_eq!(
+            variables(),
+            vec![
+                ExportVariable {
+                    name: "API_URL".to_string(),
+                    value: "https://api.example.com".to_string(),
+                    secret: false,
+                },
+                ExportVariable {
+                    name: "TOKEN".to_string(),
+                    value: "it's \"secret\"".to_string(),
+                    secret: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn format_export_quotes_values_for_each_format() {
+        let variables = variables();
+
+        assert_eq!(
+            format_export(EscExportFormat::Dotenv, &variables),
+            "API_URL=\"https://api.example.com\"\nTOKEN=\"it's \\\"secret\\\"\"\n"
+        );
+        assert_eq!(
+            format_export(EscExportFormat::Shell, &variables),
+            "export API_URL='https://api.example.com'\nexport TOKEN='it'\\''s \"secret\"'\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&format_export(EscExportFormat::Json, &variables)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "API_URL": "https://api.example.com",
+                "TOKEN": "it's \"secret\"",
+            })
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_makes_an_existing_file_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("lazy-pulumi-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".env");
+        std::fs::write(&path, "OLD=1\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(path.to_str().unwrap(), "TOKEN=\"secret\"\n").unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(content, "TOKEN=\"secret\"\n");
+    }
+}
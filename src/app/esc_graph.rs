@@ -0,0 +1,619 @@
+//! ESC import graph
+//!
+//! This module parses `imports:` from environment definitions, walks them
+//! upstream from the selected environment and follows `/referrers`
+//! downstream, flattening both into indented rows for the graph pane.
+//! Import cycles are reported rather than followed.
+
+use std::collections::{HashMap, HashSet};
+
+use tokio::task::JoinSet;
+
+use crate::api::{EscReferrer, PulumiClient};
+use crate::components::StatefulList;
+
+use super::esc::EscEnvRef;
+use super::types::{EscGraphMark, EscGraphNode, EscGraphRow};
+use super::App;
+
+/// Environments fetched in each direction before the graph is cut off
+const MAX_GRAPH_ENVIRONMENTS: usize = 100;
+
+/// An entry of an environment's `imports:` list
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EscImport {
+    /// `project/name`
+    env: String,
+    /// Revision or tag after `@`
+    version: Option<String>,
+}
+
+/// Imports of each environment (`project/name`), or why they could not be loaded
+type ImportMap = HashMap<String, Result<Vec<EscImport>, String>>;
+
+/// Referrers of each environment (`project/name`), or why they could not be loaded
+type ReferrerMap = HashMap<String, Result<Vec<EscReferrer>, String>>;
+
+/// Read the top-level `imports:` list of an environment definition, in
+/// block (`- project/env`) or flow (`[a, b]`) style. Entries may be plain
+/// names or single-key maps with import options (`- project/env: {merge: false}`).
+fn parse_imports(yaml: &str) -> Vec<EscImport> {
+    let mut lines = yaml.lines();
+    let Some(rest) = lines.find_map(|line| line.strip_prefix("imports:")) else {
+        return Vec::new();
+    };
+
+    let rest = strip_comment(rest).trim();
+    if let Some(flow) = rest.strip_prefix('[') {
+        return flow
+            .trim_end_matches(']')
+            .split(',')
+            .filter_map(parse_import)
+            .collect();
+    }
+
+    let mut imports = Vec::new();
+    let mut item_indent = None;
+    for line in lines {
+        let content = strip_comment(line);
+        if content.trim().is_empty() {
+            continue;
+        }
+        let indent = content.len() - content.trim_start().len();
+        let Some(item) = content.trim_start().strip_prefix('-') else {
+            // A less indented line ends the list; deeper ones are import options
+            if indent == 0 {
+                break;
+            }
+            continue;
+        };
+        match item_indent {
+            None => item_indent = Some(indent),
+            Some(expected) if indent > expected => continue,
+            Some(expected) if indent < expected => break,
+            Some(_) => {}
+        }
+        // `- project/env: {...}` names the import as the map's only key
+        let entry = match split_key(item) {
+            Some(key) => key,
+            None => item,
+        };
+        imports.extend(parse_import(entry));
+    }
+    imports
+}
+
+/// Drop a trailing `# comment`
+pub(super) fn strip_comment(line: &str) -> &str {
+    match line.find(" #") {
+        Some(i) => &line[..i],
+        None if line.trim_start().starts_with('#') => "",
+        None => line,
+    }
+}
+
+/// Key of a `key: value` (or `key:`) entry, if it is one
+pub(super) fn split_key(item: &str) -> Option<&str> {
+    let item = item.trim();
+    match item.find(": ") {
+        Some(i) => Some(&item[..i]),
+        None => item.strip_suffix(':'),
+    }
+}
+
+/// Parse `project/env@version`; environments without a project are in `default`
+fn parse_import(entry: &str) -> Option<EscImport> {
+    let entry = entry.trim().trim_matches(|c| c == '"' || c == '\'');
+    if entry.is_empty() {
+        return None;
+    }
+    let (env, version) = match entry.split_once('@') {
+        Some((env, version)) => (env, Some(version.to_string())),
+        None => (entry, None),
+    };
+    let env = if env.contains('/') {
+        env.to_string()
+    } else {
+        format!("default/{}", env)
+    };
+    Some(EscImport { env, version })
+}
+
+/// Rows for the environments `root` imports, depth-first in import order.
+/// An environment imported more than once is expanded the first time only.
+fn import_rows(root: &str, imports: &ImportMap) -> Vec<EscGraphRow> {
+    fn walk(
+        rows: &mut Vec<EscGraphRow>,
+        cycles: &mut Vec<Vec<String>>,
+        expanded: &mut HashSet<String>,
+        path: &mut Vec<String>,
+        imports: &ImportMap,
+    ) {
+        let Some(env) = path.last().cloned() else {
+            return;
+        };
+        let depth = path.len();
+        match imports.get(&env) {
+            Some(Ok(children)) => {
+                for child in children {
+                    let mark = if let Some(start) = path.iter().position(|e| *e == child.env) {
+                        let mut cycle = path[start..].to_vec();
+                        cycle.push(child.env.clone());
+                        cycles.push(cycle);
+                        EscGraphMark::Cycle
+                    } else if expanded.contains(&child.env) {
+                        EscGraphMark::Repeated
+                    } else {
+                        EscGraphMark::None
+                    };
+                    rows.push(EscGraphRow {
+                        depth,
+                        node: EscGraphNode::Environment {
+                            env: child.env.clone(),
+                            version: child.version.clone(),
+                        },
+                        mark,
+                    });
+                    if mark == EscGraphMark::None {
+                        expanded.insert(child.env.clone());
+                        path.push(child.env.clone());
+                        walk(rows, cycles, expanded, path, imports);
+                        path.pop();
+                    }
+                }
+            }
+            Some(Err(e)) => rows.push(EscGraphRow {
+                depth,
+                node: EscGraphNode::Error(e.clone()),
+                mark: EscGraphMark::None,
+            }),
+            // Not fetched: the graph was cut off
+            None => rows.push(EscGraphRow {
+                depth,
+                node: EscGraphNode::Error("not loaded (graph too large)".to_string()),
+                mark: EscGraphMark::None,
+            }),
+        }
+    }
+
+    let mut rows = vec![heading("Imports (upstream)"), environment_row(root)];
+    let mut cycles = Vec::new();
+    let mut expanded = HashSet::from([root.to_string()]);
+    walk(
+        &mut rows,
+        &mut cycles,
+        &mut expanded,
+        &mut vec![root.to_string()],
+        imports,
+    );
+
+    if !cycles.is_empty() {
+        rows.push(heading("Import cycles"));
+        rows.extend(cycles.into_iter().map(|cycle| EscGraphRow {
+            depth: 0,
+            node: EscGraphNode::Cycle(cycle),
+            mark: EscGraphMark::Cycle,
+        }));
+    }
+    rows
+}
+
+/// Rows for everything that depends on `root`: importing environments
+/// (and what depends on them, in turn), stacks and Insights accounts
+fn referrer_rows(root: &str, referrers: &ReferrerMap) -> Vec<EscGraphRow> {
+    fn walk(
+        rows: &mut Vec<EscGraphRow>,
+        expanded: &mut HashSet<String>,
+        path: &mut Vec<String>,
+        referrers: &ReferrerMap,
+    ) {
+        let Some(env) = path.last().cloned() else {
+            return;
+        };
+        let depth = path.len();
+        let children = match referrers.get(&env) {
+            Some(Ok(children)) => children,
+            Some(Err(e)) => {
+                rows.push(EscGraphRow {
+                    depth,
+                    node: EscGraphNode::Error(e.clone()),
+                    mark: EscGraphMark::None,
+                });
+                return;
+            }
+            None => {
+                rows.push(EscGraphRow {
+                    depth,
+                    node: EscGraphNode::Error("not loaded (graph too large)".to_string()),
+                    mark: EscGraphMark::None,
+                });
+                return;
+            }
+        };
+
+        for child in children {
+            let (node, next) = match child {
+                EscReferrer::Environment { project, name, .. } => {
+                    let env = format!("{}/{}", project, name);
+                    (
+                        EscGraphNode::Environment {
+                            env: env.clone(),
+                            version: None,
+                        },
+                        Some(env),
+                    )
+                }
+                EscReferrer::Stack {
+                    project,
+                    stack,
+                    version,
+                } => (
+                    EscGraphNode::Stack {
+                        project: project.clone(),
+                        stack: stack.clone(),
+                        version: *version,
+                    },
+                    None,
+                ),
+                EscReferrer::InsightsAccount { name } => {
+                    (EscGraphNode::InsightsAccount(name.clone()), None)
+                }
+            };
+            let mark = match next {
+                Some(ref env) if path.contains(env) => EscGraphMark::Cycle,
+                Some(ref env) if expanded.contains(env) => EscGraphMark::Repeated,
+                _ => EscGraphMark::None,
+            };
+            rows.push(EscGraphRow { depth, node, mark });
+            if let (Some(env), EscGraphMark::None) = (next, mark) {
+                expanded.insert(env.clone());
+                path.push(env);
+                walk(rows, expanded, path, referrers);
+                path.pop();
+            }
+        }
+    }
+
+    let mut rows = vec![heading("Referrers (downstream)"), environment_row(root)];
+    let before = rows.len();
+    walk(
+        &mut rows,
+        &mut HashSet::from([root.to_string()]),
+        &mut vec![root.to_string()],
+        referrers,
+    );
+    if rows.len() == before {
+        rows.push(EscGraphRow {
+            depth: 1,
+            node: EscGraphNode::Error("nothing refers to this environment".to_string()),
+            mark: EscGraphMark::None,
+        });
+    }
+    rows
+}
+
+fn heading(title: &'static str) -> EscGraphRow {
+    EscGraphRow {
+        depth: 0,
+        node: EscGraphNode::Heading(title),
+        mark: EscGraphMark::None,
+    }
+}
+
+fn environment_row(env: &str) -> EscGraphRow {
+    EscGraphRow {
+        depth: 0,
+        node: EscGraphNode::Environment {
+            env: env.to_string(),
+            version: None,
+        },
+        mark: EscGraphMark::None,
+    }
+}
+
+/// Fetch definitions breadth-first from `root`, following imports.
+/// Pinned imports (`@version`) are followed at their latest revision.
+async fn fetch_imports(client: &PulumiClient, org: &str, root: &str) -> ImportMap {
+    let mut imports = ImportMap::new();
+    let mut frontier = vec![root.to_string()];
+
+    while !frontier.is_empty() && imports.len() < MAX_GRAPH_ENVIRONMENTS {
+        let mut requests = JoinSet::new();
+        for env in frontier.drain(..) {
+            let client = client.clone();
+            let org = org.to_string();
+            requests.spawn(async move {
+                let (project, name) = env.split_once('/').unwrap_or(("default", &env));
+                let result = client.get_esc_environment(&org, project, name).await;
+                (env.clone(), result)
+            });
+        }
+
+        while let Some(joined) = requests.join_next().await {
+            let Ok((env, result)) = joined else {
+                continue;
+            };
+            let parsed = result
+                .map(|details| parse_imports(details.yaml.as_deref().unwrap_or_default()))
+                .map_err(|e| e.to_string());
+            if let Ok(ref children) = parsed {
+                for child in children {
+                    if !imports.contains_key(&child.env) && !frontier.contains(&child.env) {
+                        frontier.push(child.env.clone());
+                    }
+                }
+            }
+            imports.insert(env, parsed);
+        }
+        frontier.retain(|env| !imports.contains_key(env));
+    }
+
+    imports
+}
+
+/// Fetch referrers breadth-first from `root`, following importing environments
+async fn fetch_referrers(client: &PulumiClient, org: &str, root: &str) -> ReferrerMap {
+    let mut referrers = ReferrerMap::new();
+    let mut frontier = vec![root.to_string()];
+
+    while !frontier.is_empty() && referrers.len() < MAX_GRAPH_ENVIRONMENTS {
+        let mut requests = JoinSet::new();
+        for env in frontier.drain(..) {
+            let client = client.clone();
+            let org = org.to_string();
+            requests.spawn(async move {
+                let (project, name) = env.split_once('/').unwrap_or(("default", &env));
+                let result = client.list_esc_referrers(&org, project, name).await;
+                (env.clone(), result)
+            });
+        }
+
+        while let Some(joined) = requests.join_next().await {
+            let Ok((env, result)) = joined else {
+                continue;
+            };
+            let result = result.map_err(|e| e.to_string());
+            if let Ok(ref children) = result {
+                for child in children {
+                    if let EscReferrer::Environment { project, name, .. } = child {
+                        let child = format!("{}/{}", project, name);
+                        if !referrers.contains_key(&child) && !frontier.contains(&child) {
+                            frontier.push(child);
+                        }
+                    }
+                }
+            }
+            referrers.insert(env, result);
+        }
+        frontier.retain(|env| !referrers.contains_key(env));
+    }
+
+    referrers
+}
+
+impl App {
+    /// Show the import graph pane for an environment
+    pub(super) async fn open_esc_graph(&mut self, env: EscEnvRef) {
+        self.esc_graph_env = Some(env);
+        self.esc_graph_list = StatefulList::new();
+        self.reload_esc_graph().await;
+    }
+
+    pub(super) fn close_esc_graph(&mut self) {
+        self.esc_graph_env = None;
+        self.esc_graph_list = StatefulList::new();
+    }
+
+    /// Walk imports and referrers of the graph's environment
+    pub(super) async fn reload_esc_graph(&mut self) {
+        let (Some((org, project, name)), Some(client)) =
+            (self.esc_graph_env.clone(), self.client.clone())
+        else {
+            return;
+        };
+        let root = format!("{}/{}", project, name);
+
+        self.is_loading = true;
+        self.spinner.set_message("Loading import graph...");
+        let (imports, referrers) = tokio::join!(
+            fetch_imports(&client, &org, &root),
+            fetch_referrers(&client, &org, &root)
+        );
+        self.is_loading = false;
+
+        let mut rows = import_rows(&root, &imports);
+        rows.extend(referrer_rows(&root, &referrers));
+        self.esc_graph_list.set_items(rows);
+    }
+
+    /// Re-root the graph on the selected environment row, selecting it in
+    /// the environment list too
+    pub(super) async fn open_selected_graph_environment(&mut self) {
+        let Some(EscGraphNode::Environment { env, .. }) =
+            self.esc_graph_list.selected().map(|row| row.node.clone())
+        else {
+            return;
+        };
+        let Some((org, _, _)) = self.esc_graph_env.clone() else {
+            return;
+        };
+        let Some((project, name)) = env.split_once('/') else {
+            return;
+        };
+
+        if let Some(index) = self
+            .esc_list
+            .items()
+            .iter()
+            .position(|e| e.project == project && e.name == name)
+        {
+            self.esc_list.select(Some(index));
+            self.state.selected_env_yaml = None;
+            self.state.selected_env_etag = None;
+            self.state.selected_env_yaml_highlighted = None;
+            self.set_esc_values(None);
+        }
+        self.open_esc_graph((org, project.to_string(), name.to_string()))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_rows(rows: &[EscGraphRow]) -> Vec<(usize, &str, EscGraphMark)> {
+        rows.iter()
+            .filter_map(|row| match &row.node {
+                EscGraphNode::Environment { env, .. } => Some((row.depth, env.as_str(), row.mark)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_imports_reads_block_flow_and_option_entries() {
+        let yaml = "\
+imports:
+  - shared/base   # org-wide defaults
+  - aws-creds@prod
+  - \"app/db\":
+      merge: false
+  # - old/disabled
+values:
+  imports:
+    - not/an-import
+";
+        assert_eq!(
+            parse_imports(yaml),
+            vec![
+                EscImport {
+                    env: "shared/base".to_string(),
+                    version: None,
+                },
+                EscImport {
+                    env: "default/aws-creds".to_string(),
+                    version: Some("prod".to_string()),
+                },
+                EscImport {
+                    env: "app/db".to_string(),
+                    version: None,
+                },
+            ]
+        );
+
+        let flow: Vec<String> = parse_imports("imports: [a/one, 'b/two@3']\nvalues: {}\n")
+            .into_iter()
+            .map(|i| i.env)
+            .collect();
+        assert_eq!(flow, vec!["a/one", "b/two"]);
+        assert!(parse_imports("values:\n  a: 1\n").is_empty());
+    }
+
+    #[test]
+    fn import_rows_marks_repeats_and_reports_cycles() {
+        let import = |env: &str| EscImport {
+            env: env.to_string(),
+            version: None,
+        };
+        let imports = ImportMap::from([
+            (
+                "app/dev".to_string(),
+                Ok(vec![import("shared/base"), import("shared/aws")]),
+            ),
+            ("shared/base".to_string(), Ok(vec![import("shared/loop")])),
+            ("shared/loop".to_string(), Ok(vec![import("shared/base")])),
+            ("shared/aws".to_string(), Ok(vec![import("shared/loop")])),
+        ]);
+
+        let rows = import_rows("app/dev", &imports);
+        assert_eq!(
+            env_rows(&rows),
+            vec![
+                (0, "app/dev", EscGraphMark::None),
+                (1, "shared/base", EscGraphMark::None),
+                (2, "shared/loop", EscGraphMark::None),
+                (3, "shared/base", EscGraphMark::Cycle),
+                (1, "shared/aws", EscGraphMark::None),
+                (2, "shared/loop", EscGraphMark::Repeated),
+            ]
+        );
+        assert_eq!(
+            rows.last().map(|row| &row.node),
+            Some(&EscGraphNode::Cycle(vec![
+                "shared/base".to_string(),
+                "shared/loop".to_string(),
+                "shared/base".to_string(),
+            ]))
+        );
+    }
+
+    #[test]
+    fn referrer_rows_follow_importing_environments_to_stacks() {
+        let referrers = ReferrerMap::from([
+            (
+                "shared/base".to_string(),
+                Ok(vec![
+                    EscReferrer::Environment {
+                        project: "app".to_string(),
+                        name: "dev".to_string(),
+                        revision: 7,
+                    },
+                    EscReferrer::Stack {
+                        project: "infra".to_string(),
+                        stack: "prod".to_string(),
+                        version: 12,
+                    },
+                ]),
+            ),
+            (
+                "app/dev".to_string(),
+                Ok(vec![EscReferrer::Stack {
+                    project: "web".to_string(),
+                    stack: "dev".to_string(),
+                    version: 3,
+                }]),
+            ),
+        ]);
+
+        let rows = referrer_rows("shared/base", &referrers);
+        let nodes: Vec<(usize, &EscGraphNode)> =
+            rows.iter().map(|row| (row.depth, &row.node)).collect();
+        assert_eq!(
+            nodes,
+            vec![
+                (0, &EscGraphNode::Heading("Referrers (downstream)")),
+                (
+                    0,
+                    &EscGraphNode::Environment {
+                        env: "shared/base".to_string(),
+                        version: None,
+                    }
+                ),
+                (
+                    1,
+                    &EscGraphNode::Environment {
+                        env: "app/dev".to_string(),
+                        version: None,
+                    }
+                ),
+                (
+                    2,
+                    &EscGraphNode::Stack {
+                        project: "web".to_string(),
+                        stack: "dev".to_string(),
+                        version: 3,
+                    }
+                ),
+                (
+                    1,
+                    &EscGraphNode::Stack {
+                        project: "infra".to_string(),
+                        stack: "prod".to_string(),
+                        version: 12,
+                    }
+                ),
+            ]
+        );
+    }
+}
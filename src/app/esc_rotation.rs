@@ -0,0 +1,242 @@
+//! ESC secret rotation
+//!
+//! This module finds the rotators (`fn::rotate`) declared in an environment
+//! definition and manages the rotation pane: rotation history, schedules,
+//! rotating secrets on demand and pausing or resuming schedules.
+
+use crate::components::StatefulList;
+
+use super::esc::EscEnvRef;
+use super::esc_graph::{split_key, strip_comment};
+use super::types::{EscRotationPane, EscRotator};
+use super::App;
+
+/// Find `fn::rotate` calls under `values:`, as `fn::rotate::<provider>: {...}`
+/// or the long form `fn::rotate: {provider: <provider>, ...}`
+fn parse_rotators(yaml: &str) -> Vec<EscRotator> {
+    let mut rotators: Vec<EscRotator> = Vec::new();
+    // Keys enclosing the current line, with their indentation
+    let mut keys: Vec<(usize, String)> = Vec::new();
+
+    for line in yaml.lines() {
+        let content = strip_comment(line);
+        let item = content.trim_start();
+        if item.is_empty() {
+            continue;
+        }
+        let indent = content.len() - item.len();
+        while keys.last().is_some_and(|(i, _)| *i >= indent) {
+            keys.pop();
+        }
+        let Some(key) = split_key(item) else {
+            continue;
+        };
+        let key = key.trim_matches(|c| c == '"' || c == '\'');
+        let in_values = keys.first().is_some_and(|(_, k)| k == "values");
+
+        if let Some(provider) = key.strip_prefix("fn::rotate") {
+            if in_values && keys.len() > 1 {
+                let path: Vec<&str> = keys[1..].iter().map(|(_, k)| k.as_str()).collect();
+                rotators.push(EscRotator {
+                    path: path.join("."),
+                    provider: provider.trim_start_matches("::").to_string(),
+                });
+            }
+        } else if key == "provider" && keys.last().is_some_and(|(_, k)| k == "fn::rotate") {
+            if let Some(rotator) = rotators.last_mut().filter(|r| r.provider.is_empty()) {
+                let value = item[key.len()..].trim_start_matches([':', ' ']);
+                rotator.provider = value.trim().trim_matches(['"', '\'']).to_string();
+            }
+        }
+        keys.push((indent, key.to_string()));
+    }
+    rotators
+}
+
+impl App {
+    /// Show the rotation pane for an environment
+    pub(super) async fn open_esc_rotation(&mut self, env: EscEnvRef) {
+        self.esc_rotation_env = Some(env);
+        self.esc_rotators = StatefulList::new();
+        self.esc_schedules = StatefulList::new();
+        self.esc_rotation_history.clear();
+        self.esc_rotation_pane = EscRotationPane::default();
+        self.reload_esc_rotation().await;
+    }
+
+    pub(super) fn close_esc_rotation(&mut self) {
+        self.esc_rotation_env = None;
+        self.esc_rotation_confirm = None;
+    }
+
+    /// Fetch the definition, rotation history and schedules of the pane's environment
+    pub(super) async fn reload_esc_rotation(&mut self) {
+        let (Some((org, project, name)), Some(client)) =
+            (self.esc_rotation_env.clone(), self.client.clone())
+        else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Loading rotation...");
+        let (details, history, schedules) = tokio::join!(
+            client.get_esc_environment(&org, &project, &name),
+            client.list_esc_rotation_history(&org, &project, &name),
+            client.list_esc_schedules(&org, &project, &name)
+        );
+        self.is_loading = false;
+
+        match details {
+            Ok(details) => self
+                .esc_rotators
+                .set_items(parse_rotators(details.yaml.as_deref().unwrap_or_default())),
+            Err(e) => {
+                log::error!("Failed to load ESC environment: {}", e);
+                self.error = Some(format!("Failed to load environment: {}", e));
+                self.close_esc_rotation();
+                return;
+            }
+        }
+        match history {
+            Ok(history) => self.esc_rotation_history = history,
+            Err(e) => log::warn!("Failed to load ESC rotation history: {}", e),
+        }
+        match schedules {
+            Ok(schedules) => self.esc_schedules.set_items(schedules),
+            Err(e) => log::warn!("Failed to load ESC schedules: {}", e),
+        }
+    }
+
+    /// Ask to rotate the selected rotator, or every rotator when `all` is set
+    pub(super) fn confirm_esc_rotation(&mut self, all: bool) {
+        if all {
+            if !self.esc_rotators.is_empty() {
+                self.esc_rotation_confirm = Some(Vec::new());
+            }
+        } else if let Some(rotator) = self.esc_rotators.selected() {
+            self.esc_rotation_confirm = Some(vec![rotator.path.clone()]);
+        }
+    }
+
+    /// Rotate the confirmed rotators. Rotation writes a new revision, so the
+    /// environment's loaded definition and values are dropped.
+    pub(super) async fn rotate_esc_secrets(&mut self) {
+        let Some(paths) = self.esc_rotation_confirm.take() else {
+            return;
+        };
+        let (Some(env), Some(client)) = (self.esc_rotation_env.clone(), self.client.clone()) else {
+            return;
+        };
+        let (org, project, name) = &env;
+
+        self.is_loading = true;
+        self.spinner.set_message("Rotating secrets...");
+        let result = client
+            .rotate_esc_environment(org, project, name, paths)
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(event) if event.status == "failed" => {
+                let errors: Vec<String> = event
+                    .rotations
+                    .iter()
+                    .filter_map(|r| r.error.as_ref().map(|e| format!("{}: {}", r.path, e)))
+                    .chain(event.error.clone())
+                    .collect();
+                self.error = Some(format!("Rotation failed: {}", errors.join("; ")));
+            }
+            Ok(event) => log::info!("Rotated secrets of {}/{}: {}", project, name, event.status),
+            Err(e) => {
+                log::error!("Failed to rotate ESC secrets: {}", e);
+                self.error = Some(format!("Failed to rotate: {}", e));
+            }
+        }
+
+        if self.selected_esc_env().as_ref() == Some(&env) {
+            self.state.selected_env_yaml = None;
+            self.state.selected_env_etag = None;
+            self.state.selected_env_yaml_highlighted = None;
+            self.set_esc_values(None);
+        }
+        self.reload_esc_rotation().await;
+    }
+
+    /// Pause the selected schedule, or resume it if it is paused
+    pub(super) async fn toggle_selected_esc_schedule(&mut self) {
+        let (Some((org, project, name)), Some(schedule), Some(client)) = (
+            self.esc_rotation_env.clone(),
+            self.esc_schedules.selected().cloned(),
+            self.client.clone(),
+        ) else {
+            return;
+        };
+        let pause = !schedule.paused;
+
+        self.is_loading = true;
+        self.spinner.set_message(if pause {
+            "Pausing schedule..."
+        } else {
+            "Resuming schedule..."
+        });
+        let result = client
+            .set_esc_schedule_paused(&org, &project, &name, &schedule.id, pause)
+            .await;
+        self.is_loading = false;
+
+        match result {
+            Ok(()) => self.reload_esc_rotation().await,
+            Err(e) => {
+                log::error!("Failed to update ESC schedule: {}", e);
+                self.error = Some(format!(
+                    "Failed to {} schedule: {}",
+                    if pause { "pause" } else { "resume" },
+                    e
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rotators_finds_short_and_long_forms_under_values() {
+        let yaml = "\
+imports:
+  - shared/aws
+values:
+  aws:
+    login:
+      fn::open::aws-login:
+        oidc: {roleArn: arn:aws:iam::123:role/esc}
+    creds:
+      fn::rotate::aws-iam:   # rotated weekly
+        inputs:
+          userArn: arn:aws:iam::123:user/app
+        state: {}
+  \"db-admin\":
+    fn::rotate:
+      provider: postgres
+      inputs: {}
+  # fn::rotate::disabled:
+fn::rotate::not-values: {}
+";
+        assert_eq!(
+            parse_rotators(yaml),
+            vec![
+                EscRotator {
+                    path: "aws.creds".to_string(),
+                    provider: "aws-iam".to_string(),
+                },
+                EscRotator {
+                    path: "db-admin".to_string(),
+                    provider: "postgres".to_string(),
+                },
+            ]
+        );
+        assert!(parse_rotators("values:\n  a: 1\n").is_empty());
+    }
+}
@@ -10,15 +10,17 @@ use tui_scrollview::ScrollViewState;
 
 use crate::event::keys;
 use crate::startup::{check_pulumi_cli, check_pulumi_token, CheckStatus};
-use crate::ui::syntax::highlight_yaml;
 
-use super::types::{FocusMode, PlatformView, SlashCommandsDialogView, Tab};
+use super::types::{
+    EscEnvDialog, EscRevisionPrompt, FocusMode, NeoExportFormat, PlatformView,
+    SlashCommandsDialogView, StacksPane, Tab,
+};
 use super::App;
 use crate::commands::{
-    can_run_command, commands_by_category, spawn_command, CommandExecution, CommandExecutionState,
-    ExecutionMode,
+    can_run_command, commands_by_category, encode_key, spawn_command, CommandCategory,
+    CommandExecution, CommandExecutionState, EngineProgress, PulumiCommand, TerminalScreen,
 };
-use crate::ui::{extract_values, json_to_yaml, CommandsViewState};
+use crate::ui::CommandsViewState;
 
 impl App {
     /// Handle key events
@@ -45,20 +47,48 @@ impl App {
             return;
         }
 
+        // Handle stack update details popup
+        if self.show_update_details {
+            self.handle_update_details_key(key);
+            return;
+        }
+
         // Handle Neo details popup
         if self.show_neo_details {
             if keys::is_escape(&key) || keys::is_char(&key, 'd') {
                 self.show_neo_details = false;
+            } else if keys::is_char(&key, 's') {
+                self.toggle_neo_task_sharing().await;
+            } else if keys::is_char(&key, 'y') {
+                self.copy_neo_task_link();
             }
             return;
         }
 
+        // Handle Neo approval dialog
+        if self.show_neo_approval {
+            self.handle_neo_approval_key(key);
+            return;
+        }
+
+        // Handle Neo cancel confirmation
+        if self.neo_cancel_target.is_some() {
+            self.handle_neo_cancel_key(key).await;
+            return;
+        }
+
         // Handle slash commands management dialog
         if self.show_slash_commands_dialog {
             self.handle_slash_commands_dialog_key(key).await;
             return;
         }
 
+        // Handle ESC save confirmation (diff) on top of the editor
+        if self.esc_save_diff.is_some() {
+            self.handle_esc_save_diff_key(key).await;
+            return;
+        }
+
         // Handle ESC YAML editor popup
         if self.show_esc_editor {
             self.handle_esc_editor_key(key).await;
@@ -130,10 +160,95 @@ impl App {
                     self.handle_commands_key(key).await;
                     return;
                 }
+                // A running interactive command receives every key, including Ctrl+C
+                CommandsViewState::OutputView if self.is_command_terminal_active() => {
+                    self.handle_commands_terminal_key(key);
+                    return;
+                }
                 _ => {}
             }
         }
 
+        // Typing in the create/clone/delete environment dialog must not trigger global keys
+        if self.tab == Tab::Esc && self.esc_env_dialog.is_some() {
+            self.handle_esc_env_dialog_key(key).await;
+            return;
+        }
+
+        // Typing the export file name must not trigger global keys
+        if self.tab == Tab::Esc && self.esc_export.is_some() {
+            self.handle_esc_export_key(key);
+            return;
+        }
+
+        // An existing Neo export is only overwritten after an explicit 'y'
+        if self.tab == Tab::Neo {
+            if let Some(format) = self.neo_export_confirm {
+                if keys::is_char(&key, 'y') {
+                    self.export_neo_conversation(format).await;
+                } else {
+                    self.neo_export_confirm = None;
+                }
+                return;
+            }
+        }
+
+        // Typing a revision tag or retraction reason must not trigger global keys
+        if self.tab == Tab::Esc && self.esc_revision_prompt.is_some() {
+            self.handle_esc_revision_prompt_key(key).await;
+            return;
+        }
+
+        // Typing a change request description must not trigger global keys
+        if self.tab == Tab::Esc && self.esc_change_prompt {
+            if keys::is_escape(&key) {
+                self.esc_change_prompt = false;
+            } else if keys::is_enter(&key) {
+                self.esc_change_prompt = false;
+                let description = self.esc_change_input.take();
+                let description = description.trim();
+                self.submit_selected_change_request(
+                    (!description.is_empty()).then_some(description),
+                )
+                .await;
+            } else {
+                self.esc_change_input.handle_key(&key);
+            }
+            return;
+        }
+
+        // Confirming a change request action must not trigger global keys
+        if self.tab == Tab::Esc && self.esc_change_confirm.is_some() {
+            if keys::is_char(&key, 'y') {
+                self.run_esc_change_action().await;
+            } else {
+                self.esc_change_confirm = None;
+            }
+            return;
+        }
+
+        // Confirming a secret rotation must not trigger global keys
+        if self.tab == Tab::Esc && self.esc_rotation_confirm.is_some() {
+            if keys::is_char(&key, 'y') {
+                self.rotate_esc_secrets().await;
+            } else {
+                self.esc_rotation_confirm = None;
+            }
+            return;
+        }
+
+        // Typing a resource query must not trigger global keys
+        if self.tab == Tab::Resources && self.resources_is_editing_query {
+            self.handle_resources_query_key(key);
+            return;
+        }
+
+        // Typing a Neo task filter must not trigger global keys
+        if self.tab == Tab::Neo && self.neo_tasks_is_editing_query {
+            self.handle_neo_tasks_query_key(key);
+            return;
+        }
+
         // Global keys
         if keys::is_quit(&key) {
             self.should_quit = true;
@@ -200,6 +315,9 @@ impl App {
             Tab::Stacks => {
                 self.handle_stacks_key(key).await;
             }
+            Tab::Resources => {
+                self.handle_resources_key(key);
+            }
             Tab::Esc => {
                 self.handle_esc_key(key).await;
             }
@@ -278,11 +396,22 @@ impl App {
                 self.spinner.set_message("Loading organization data...");
 
                 // Clear all view-specific state
-                self.state.selected_stack_updates.clear();
+                self.clear_selected_stack_details();
+                self.stacks_pane = StacksPane::Stacks;
+                self.resources_search.page = 1;
                 self.state.selected_env_yaml = None;
-                self.state.selected_env_values = None;
+                self.state.selected_env_etag = None;
+                self.set_esc_values(None);
+                self.close_esc_revisions();
+                self.close_esc_graph();
+                self.close_esc_rotation();
+                self.close_esc_change_requests();
                 self.state.neo_messages.clear();
                 self.state.current_task_id = None;
+                self.neo_exported = None;
+                self.neo_attached_entity = None;
+                self.reset_neo_tasks_search();
+                self.clear_neo_tool_calls();
                 self.neo_scroll_state = ScrollViewState::default();
                 self.neo_auto_scroll.store(true, Ordering::Relaxed);
 
@@ -293,61 +422,224 @@ impl App {
         }
     }
 
-    /// Handle stacks view keys
-    async fn handle_stacks_key(&mut self, key: KeyEvent) {
+    /// Handle Resources view keys
+    fn handle_resources_key(&mut self, key: KeyEvent) {
+        // [/] step through the server-side pages
+        let page_changed = if keys::is_char(&key, ']') || keys::is_page_down(&key) {
+            self.resources_search.next_page()
+        } else if keys::is_char(&key, '[') || keys::is_page_up(&key) {
+            self.resources_search.previous_page()
+        } else {
+            false
+        };
+        if page_changed {
+            self.spawn_resource_search();
+            return;
+        }
+
         if keys::is_up(&key) {
-            self.stacks_list.previous();
-            self.state.selected_stack_updates.clear();
+            self.resources_list.previous();
         } else if keys::is_down(&key) {
-            self.stacks_list.next();
-            self.state.selected_stack_updates.clear();
+            self.resources_list.next();
         } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
-            self.stacks_list.select_first();
+            self.resources_list.select_first();
         } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
-            self.stacks_list.select_last();
-        } else if keys::is_enter(&key) || keys::is_char(&key, 'u') {
-            // Load stack updates
-            if let Some(stack) = self.stacks_list.selected() {
-                if let Some(ref client) = self.client {
-                    self.is_loading = true;
-                    self.spinner.set_message("Loading updates...");
+            self.resources_list.select_last();
+        } else if keys::is_char(&key, '/') {
+            self.resources_is_editing_query = true;
+            self.resources_query_input
+                .set_value(self.resources_search.query.clone());
+            self.resources_query_input.set_focused(true);
+        } else if keys::is_char(&key, 's') {
+            let column = self.resources_search.sort.next();
+            self.resources_search.sort_by(column);
+            self.spawn_resource_search();
+        } else if keys::is_char(&key, 'S') {
+            self.resources_search.sort_by(self.resources_search.sort);
+            self.spawn_resource_search();
+        } else if keys::is_char(&key, 'N') {
+            self.ask_neo_about_selection();
+        }
+    }
 
-                    if let Ok(updates) = client
-                        .get_stack_updates(&stack.org_name, &stack.project_name, &stack.stack_name)
-                        .await
-                    {
-                        self.state.selected_stack_updates = updates
-                            .into_iter()
-                            .take(10)
-                            .map(|u| {
-                                let time = u
-                                    .start_time
-                                    .map(|t| {
-                                        chrono::DateTime::from_timestamp(t, 0)
-                                            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
-                                            .unwrap_or_else(|| "Unknown".to_string())
-                                    })
-                                    .unwrap_or_else(|| "Unknown".to_string());
-
-                                (
-                                    u.version,
-                                    u.result.unwrap_or_else(|| "Unknown".to_string()),
-                                    time,
-                                )
-                            })
-                            .collect();
-                    }
+    /// Handle keys while editing the resource search query
+    fn handle_resources_query_key(&mut self, key: KeyEvent) {
+        if keys::is_escape(&key) {
+            self.resources_is_editing_query = false;
+            self.resources_query_input.set_focused(false);
+        } else if keys::is_enter(&key) {
+            self.resources_is_editing_query = false;
+            self.resources_query_input.set_focused(false);
+            let query = self.resources_query_input.value().trim().to_string();
+            self.resources_search.set_query(query);
+            self.spawn_resource_search();
+        } else {
+            self.resources_query_input.handle_key(&key);
+        }
+    }
 
-                    self.is_loading = false;
+    /// Handle stacks view keys
+    async fn handle_stacks_key(&mut self, key: KeyEvent) {
+        // Left/Right arrows move focus between Stacks, Resources and Properties
+        if keys::is_left(&key) {
+            self.stacks_pane = self.stacks_pane.previous();
+            return;
+        }
+        if keys::is_right(&key) {
+            if !self.stack_updates_list.is_empty() || !self.stack_resources_list.is_empty() {
+                self.stacks_pane = self.stacks_pane.next();
+            }
+            return;
+        }
+        // Run a stack operation on the selected stack from any pane
+        let stack_command = match keys::get_char(&key) {
+            Some('p') => Some("preview"),
+            Some('U') => Some("up"),
+            Some('R') => Some("refresh"),
+            Some('D') => Some("destroy"),
+            _ => None,
+        };
+        if let Some(command) = stack_command {
+            self.launch_stack_command(command);
+            return;
+        }
+        // N asks Neo about the selected stack, update or resource
+        if keys::is_char(&key, 'N') {
+            self.ask_neo_about_selection();
+            return;
+        }
+        // J/K page the Properties pane from any pane
+        if keys::is_char(&key, 'J') || keys::is_page_down(&key) {
+            self.stack_resource_scroll.scroll_page_down();
+            return;
+        }
+        if keys::is_char(&key, 'K') || keys::is_page_up(&key) {
+            self.stack_resource_scroll.scroll_page_up();
+            return;
+        }
+
+        match self.stacks_pane {
+            StacksPane::Stacks => {
+                if keys::is_up(&key) {
+                    self.stacks_list.previous();
+                    self.clear_selected_stack_details();
+                } else if keys::is_down(&key) {
+                    self.stacks_list.next();
+                    self.clear_selected_stack_details();
+                } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
+                    self.stacks_list.select_first();
+                    self.clear_selected_stack_details();
+                } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
+                    self.stacks_list.select_last();
+                    self.clear_selected_stack_details();
+                } else if keys::is_enter(&key) {
+                    self.load_selected_stack_details().await;
+                } else if keys::is_char(&key, 'u') {
+                    // Load details and jump straight into the update history
+                    self.load_selected_stack_details().await;
+                    if !self.stack_updates_list.is_empty() {
+                        self.stacks_pane = StacksPane::Updates;
+                    }
+                }
+            }
+            StacksPane::Updates => {
+                if keys::is_up(&key) {
+                    self.stack_updates_list.previous();
+                } else if keys::is_down(&key) {
+                    self.stack_updates_list.next();
+                } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
+                    self.stack_updates_list.select_first();
+                } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
+                    self.stack_updates_list.select_last();
+                } else if keys::is_enter(&key) {
+                    self.open_selected_update().await;
+                } else if keys::is_escape(&key) {
+                    self.stacks_pane = StacksPane::Stacks;
+                }
+            }
+            StacksPane::Resources => {
+                if keys::is_up(&key) {
+                    self.stack_resources_list.previous();
+                    self.update_selected_stack_resource();
+                } else if keys::is_down(&key) {
+                    self.stack_resources_list.next();
+                    self.update_selected_stack_resource();
+                } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
+                    self.stack_resources_list.select_first();
+                    self.update_selected_stack_resource();
+                } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
+                    self.stack_resources_list.select_last();
+                    self.update_selected_stack_resource();
+                } else if keys::is_enter(&key) {
+                    self.stacks_pane = StacksPane::Properties;
+                } else if keys::is_escape(&key) {
+                    self.stacks_pane = StacksPane::Updates;
+                }
+            }
+            StacksPane::Properties => {
+                if keys::is_down(&key) {
+                    for _ in 0..3 {
+                        self.stack_resource_scroll.scroll_down();
+                    }
+                } else if keys::is_up(&key) {
+                    for _ in 0..3 {
+                        self.stack_resource_scroll.scroll_up();
+                    }
+                } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
+                    self.stack_resource_scroll.scroll_to_top();
+                } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
+                    self.stack_resource_scroll.scroll_to_bottom();
+                } else if keys::is_escape(&key) {
+                    self.stacks_pane = StacksPane::Resources;
                 }
             }
         }
     }
 
+    /// Handle stack update details popup keys
+    fn handle_update_details_key(&mut self, key: KeyEvent) {
+        if keys::is_escape(&key) || keys::is_enter(&key) {
+            self.show_update_details = false;
+        } else if keys::is_down(&key) {
+            for _ in 0..3 {
+                self.update_details_scroll.scroll_down();
+            }
+        } else if keys::is_up(&key) {
+            for _ in 0..3 {
+                self.update_details_scroll.scroll_up();
+            }
+        } else if keys::is_char(&key, 'J') || keys::is_page_down(&key) {
+            self.update_details_scroll.scroll_page_down();
+        } else if keys::is_char(&key, 'K') || keys::is_page_up(&key) {
+            self.update_details_scroll.scroll_page_up();
+        } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
+            self.update_details_scroll.scroll_to_top();
+        } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
+            self.update_details_scroll.scroll_to_bottom();
+        }
+    }
+
     /// Handle ESC view keys
     async fn handle_esc_key(&mut self, key: KeyEvent) {
         use super::types::EscPane;
 
+        if self.esc_change_requests_org.is_some() {
+            self.handle_esc_change_requests_key(key).await;
+            return;
+        }
+        if self.esc_rotation_env.is_some() {
+            self.handle_esc_rotation_key(key).await;
+            return;
+        }
+        if self.esc_graph_env.is_some() {
+            self.handle_esc_graph_key(key).await;
+            return;
+        }
+        if self.esc_revisions_env.is_some() {
+            self.handle_esc_revisions_key(key).await;
+            return;
+        }
+
         // Left/Right arrows switch between Definition and Resolved Values panes
         if keys::is_left(&key) || keys::is_char(&key, 'h') {
             self.esc_pane = EscPane::Definition;
@@ -363,9 +655,8 @@ impl App {
                     }
                 }
                 EscPane::ResolvedValues => {
-                    for _ in 0..3 {
-                        self.esc_values_scroll.scroll_down();
-                    }
+                    self.esc_values_list.next();
+                    self.esc_value_copied = None;
                 }
             }
         } else if keys::is_char(&key, 'k') {
@@ -376,9 +667,8 @@ impl App {
                     }
                 }
                 EscPane::ResolvedValues => {
-                    for _ in 0..3 {
-                        self.esc_values_scroll.scroll_up();
-                    }
+                    self.esc_values_list.previous();
+                    self.esc_value_copied = None;
                 }
             }
         }
@@ -386,76 +676,52 @@ impl App {
         else if keys::is_char(&key, 'J') || keys::is_page_down(&key) {
             match self.esc_pane {
                 EscPane::Definition => self.esc_definition_scroll.scroll_page_down(),
-                EscPane::ResolvedValues => self.esc_values_scroll.scroll_page_down(),
+                EscPane::ResolvedValues => self.esc_values_list.page(1, 10),
             }
         } else if keys::is_char(&key, 'K') || keys::is_page_up(&key) {
             match self.esc_pane {
                 EscPane::Definition => self.esc_definition_scroll.scroll_page_up(),
-                EscPane::ResolvedValues => self.esc_values_scroll.scroll_page_up(),
+                EscPane::ResolvedValues => self.esc_values_list.page(-1, 10),
             }
         }
+        // Reveal and copy the selected resolved value
+        else if self.esc_pane == EscPane::ResolvedValues && keys::is_char(&key, ' ') {
+            self.toggle_selected_esc_value();
+        } else if self.esc_pane == EscPane::ResolvedValues && keys::is_char(&key, 'y') {
+            self.copy_selected_esc_value(false);
+        } else if self.esc_pane == EscPane::ResolvedValues && keys::is_char(&key, 'Y') {
+            self.copy_selected_esc_value(true);
+        }
         // Up/Down arrows navigate environment list
         else if keys::is_up(&key) {
             self.esc_list.previous();
             self.state.selected_env_yaml = None;
+            self.state.selected_env_etag = None;
             self.state.selected_env_yaml_highlighted = None;
-            self.state.selected_env_values = None;
-            self.state.selected_env_values_highlighted = None;
+            self.set_esc_values(None);
             // Reset scroll when changing environments
             self.esc_definition_scroll = ScrollViewState::default();
-            self.esc_values_scroll = ScrollViewState::default();
         } else if keys::is_down(&key) {
             self.esc_list.next();
             self.state.selected_env_yaml = None;
+            self.state.selected_env_etag = None;
             self.state.selected_env_yaml_highlighted = None;
-            self.state.selected_env_values = None;
-            self.state.selected_env_values_highlighted = None;
+            self.set_esc_values(None);
             // Reset scroll when changing environments
             self.esc_definition_scroll = ScrollViewState::default();
-            self.esc_values_scroll = ScrollViewState::default();
         } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
             self.esc_list.select_first();
             self.esc_definition_scroll = ScrollViewState::default();
-            self.esc_values_scroll = ScrollViewState::default();
         } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
             self.esc_list.select_last();
             self.esc_definition_scroll = ScrollViewState::default();
-            self.esc_values_scroll = ScrollViewState::default();
         } else if keys::is_enter(&key) {
             // Load environment definition
-            if let Some(env) = self.esc_list.selected() {
-                if let Some(ref client) = self.client {
-                    self.is_loading = true;
-                    self.spinner.set_message("Loading definition...");
-
-                    log::debug!(
-                        "Loading ESC environment definition: org={}, project={}, name={}",
-                        env.organization,
-                        env.project,
-                        env.name
-                    );
-
-                    match client
-                        .get_esc_environment(&env.organization, &env.project, &env.name)
-                        .await
-                    {
-                        Ok(details) => {
-                            // Cache syntax-highlighted content when loading (not on every render)
-                            self.state.selected_env_yaml_highlighted =
-                                details.yaml.as_ref().map(|y| highlight_yaml(y));
-                            self.state.selected_env_yaml = details.yaml;
-                            self.esc_definition_scroll = ScrollViewState::default();
-                            log::debug!("ESC environment definition loaded successfully");
-                        }
-                        Err(e) => {
-                            log::error!("Failed to load ESC environment definition: {}", e);
-                            self.error = Some(format!("Failed to load definition: {}", e));
-                        }
-                    }
-
-                    self.is_loading = false;
-                }
+            if let Some(env) = self.selected_esc_env() {
+                self.load_env_definition(&env).await;
             }
+        } else if keys::is_char(&key, 'N') {
+            self.ask_neo_about_selection();
         } else if keys::is_char(&key, 'o') {
             // Open and resolve environment
             if let Some(env) = self.esc_list.selected() {
@@ -475,15 +741,7 @@ impl App {
                         .await
                     {
                         Ok(response) => {
-                            // Cache syntax-highlighted content when loading (not on every render)
-                            self.state.selected_env_values_highlighted =
-                                response.values.as_ref().map(|v| {
-                                    let filtered = extract_values(v);
-                                    let yaml_str = json_to_yaml(&filtered);
-                                    highlight_yaml(&yaml_str)
-                                });
-                            self.state.selected_env_values = response.values;
-                            self.esc_values_scroll = ScrollViewState::default();
+                            self.set_esc_values(response.values);
                             log::debug!("ESC environment opened and resolved successfully");
                         }
                         Err(e) => {
@@ -496,93 +754,276 @@ impl App {
                 }
             }
         } else if keys::is_char(&key, 'e') {
-            // Edit environment definition in YAML editor
-            // First, ensure we have the definition loaded
-            if let Some(env) = self.esc_list.selected() {
-                let yaml_content = if let Some(ref yaml) = self.state.selected_env_yaml {
-                    yaml.clone()
-                } else {
-                    // Need to load it first
-                    if let Some(ref client) = self.client {
-                        self.is_loading = true;
-                        self.spinner.set_message("Loading definition...");
-
-                        match client
-                            .get_esc_environment(&env.organization, &env.project, &env.name)
-                            .await
-                        {
-                            Ok(details) => {
-                                self.state.selected_env_yaml = details.yaml.clone();
-                                details.yaml.unwrap_or_default()
-                            }
-                            Err(e) => {
-                                log::error!("Failed to load ESC environment definition: {}", e);
-                                self.error = Some(format!("Failed to load definition: {}", e));
-                                self.is_loading = false;
-                                return;
-                            }
-                        }
-                    } else {
-                        return;
-                    }
-                };
+            // Edit environment definition in YAML editor, loading it first if needed
+            if let Some(env) = self.selected_esc_env() {
+                if self.state.selected_env_yaml.is_none() && !self.load_env_definition(&env).await {
+                    return;
+                }
+                let content = self.state.selected_env_yaml.clone().unwrap_or_default();
+                self.open_esc_editor(env, &content);
+            }
+        } else if keys::is_char(&key, 'v') {
+            // Revision history of the selected environment
+            if let Some(env) = self.selected_esc_env() {
+                self.open_esc_revisions(env).await;
+            }
+        } else if keys::is_char(&key, 'i') {
+            // Import graph and referrers of the selected environment
+            if let Some(env) = self.selected_esc_env() {
+                self.open_esc_graph(env).await;
+            }
+        } else if keys::is_char(&key, 'R') {
+            // Secret rotators, rotation history and schedules of the selected environment
+            if let Some(env) = self.selected_esc_env() {
+                self.open_esc_rotation(env).await;
+            }
+        } else if keys::is_char(&key, 'C') {
+            // Open change requests (drafts awaiting submission, approval or apply)
+            self.open_esc_change_requests(None).await;
+        } else if keys::is_char(&key, 'x') {
+            // Export the opened environment's variables to a file
+            self.open_esc_export();
+        } else if keys::is_char(&key, '!') {
+            // Run a shell or command with the environment's variables
+            self.launch_esc_run();
+        } else if keys::is_char(&key, 'n') {
+            self.open_esc_env_dialog(EscEnvDialog::Create);
+        } else if keys::is_char(&key, 'c') && self.esc_list.selected().is_some() {
+            self.open_esc_env_dialog(EscEnvDialog::Clone);
+        } else if keys::is_char(&key, 'D') && self.esc_list.selected().is_some() {
+            self.open_esc_env_dialog(EscEnvDialog::Delete);
+        }
+    }
 
-                self.is_loading = false;
+    /// Handle keys in the environment variables export dialog
+    fn handle_esc_export_key(&mut self, key: KeyEvent) {
+        if self.esc_export_confirm {
+            // Secrets and overwrites are only written after an explicit 'y'
+            if keys::is_char(&key, 'y') {
+                self.submit_esc_export();
+            } else {
+                self.esc_export_confirm = false;
+            }
+            return;
+        }
 
-                // Initialize editor with content
-                self.esc_editor = crate::components::TextEditor::with_content(&yaml_content);
-                self.esc_editing_env = Some((
-                    env.organization.clone(),
-                    env.project.clone(),
-                    env.name.clone(),
-                ));
-                self.show_esc_editor = true;
+        if keys::is_escape(&key) {
+            self.close_esc_export();
+        } else if keys::is_enter(&key) {
+            self.submit_esc_export();
+        } else if keys::is_tab(&key) {
+            if let Some(format) = self.esc_export {
+                self.set_esc_export_format(format.next());
             }
+        } else if keys::is_backtab(&key) {
+            if let Some(format) = self.esc_export {
+                self.set_esc_export_format(format.previous());
+            }
+        } else {
+            self.esc_export_input.handle_key(&key);
         }
     }
 
-    /// Handle ESC YAML editor keys
-    async fn handle_esc_editor_key(&mut self, key: KeyEvent) {
-        // Escape = Save and close
+    /// Handle keys in the create / clone / delete environment dialog
+    async fn handle_esc_env_dialog_key(&mut self, key: KeyEvent) {
+        let Some(dialog) = self.esc_env_dialog else {
+            return;
+        };
+        let fields = dialog.field_count();
+
         if keys::is_escape(&key) {
-            if self.esc_editor.is_modified() {
-                // Save the content
-                if let Some((ref org, ref project, ref env_name)) = self.esc_editing_env {
-                    if let Some(ref client) = self.client {
-                        self.is_loading = true;
-                        self.spinner.set_message("Saving environment...");
+            self.esc_env_dialog = None;
+        } else if keys::is_enter(&key) {
+            self.submit_esc_env_dialog().await;
+        } else if keys::is_tab(&key) || keys::is_down(&key) {
+            self.esc_env_dialog_focus = (self.esc_env_dialog_focus + 1) % fields;
+        } else if keys::is_backtab(&key) || keys::is_up(&key) {
+            self.esc_env_dialog_focus = (self.esc_env_dialog_focus + fields - 1) % fields;
+        } else {
+            match (dialog, self.esc_env_dialog_focus) {
+                (EscEnvDialog::Delete, _) | (_, 1) => {
+                    self.esc_env_name_input.handle_key(&key);
+                }
+                (_, 0) => {
+                    self.esc_env_project_input.handle_key(&key);
+                }
+                (_, 2) if keys::is_char(&key, ' ') => {
+                    self.esc_clone_keep_history = !self.esc_clone_keep_history;
+                }
+                (_, _) if keys::is_char(&key, ' ') => {
+                    self.esc_clone_keep_tags = !self.esc_clone_keep_tags;
+                }
+                _ => {}
+            }
+        }
+    }
 
-                        let content = self.esc_editor.content();
+    /// Handle keys in the ESC change requests pane
+    async fn handle_esc_change_requests_key(&mut self, key: KeyEvent) {
+        use super::types::EscChangeAction;
 
-                        match client
-                            .update_esc_environment(org, project, env_name, &content)
-                            .await
-                        {
-                            Ok(_) => {
-                                log::info!("ESC environment saved successfully");
-                                // Update the cached YAML
-                                self.state.selected_env_yaml = Some(content);
-                            }
-                            Err(e) => {
-                                log::error!("Failed to save ESC environment: {}", e);
-                                self.error = Some(format!("Failed to save: {}", e));
-                            }
-                        }
+        let selected = self.esc_change_requests.selected().is_some();
+        if keys::is_escape(&key) || keys::is_char(&key, 'C') {
+            self.close_esc_change_requests();
+        } else if keys::is_up(&key) {
+            self.esc_change_requests.previous();
+            self.update_esc_change_details().await;
+        } else if keys::is_down(&key) {
+            self.esc_change_requests.next();
+            self.update_esc_change_details().await;
+        } else if keys::is_char(&key, 'j') {
+            self.esc_change_scroll.scroll_down();
+        } else if keys::is_char(&key, 'k') {
+            self.esc_change_scroll.scroll_up();
+        } else if keys::is_char(&key, 'J') || keys::is_page_down(&key) {
+            self.esc_change_scroll.scroll_page_down();
+        } else if keys::is_char(&key, 'K') || keys::is_page_up(&key) {
+            self.esc_change_scroll.scroll_page_up();
+        } else if keys::is_char(&key, 'v') {
+            self.toggle_esc_change_view().await;
+        } else if keys::is_char(&key, 's') && selected {
+            self.esc_change_input.clear();
+            self.esc_change_prompt = true;
+        } else if keys::is_char(&key, 'A') && selected {
+            self.esc_change_confirm = Some(EscChangeAction::Approve);
+        } else if keys::is_char(&key, 'a') && selected {
+            self.esc_change_confirm = Some(EscChangeAction::Apply);
+        } else if keys::is_char(&key, 'X') && selected {
+            self.esc_change_confirm = Some(EscChangeAction::Close);
+        }
+    }
 
-                        self.is_loading = false;
-                    }
+    /// Handle keys in the ESC rotation pane
+    async fn handle_esc_rotation_key(&mut self, key: KeyEvent) {
+        use super::types::EscRotationPane;
+
+        if keys::is_escape(&key) || keys::is_char(&key, 'R') {
+            self.close_esc_rotation();
+        } else if keys::is_left(&key) {
+            self.esc_rotation_pane = EscRotationPane::Rotators;
+        } else if keys::is_right(&key) {
+            self.esc_rotation_pane = EscRotationPane::Schedules;
+        } else if keys::is_up(&key) || keys::is_char(&key, 'k') {
+            match self.esc_rotation_pane {
+                EscRotationPane::Rotators => self.esc_rotators.previous(),
+                EscRotationPane::Schedules => self.esc_schedules.previous(),
+            }
+        } else if keys::is_down(&key) || keys::is_char(&key, 'j') {
+            match self.esc_rotation_pane {
+                EscRotationPane::Rotators => self.esc_rotators.next(),
+                EscRotationPane::Schedules => self.esc_schedules.next(),
+            }
+        } else if keys::is_enter(&key) && self.esc_rotation_pane == EscRotationPane::Rotators {
+            self.confirm_esc_rotation(false);
+        } else if keys::is_char(&key, 'a') {
+            self.confirm_esc_rotation(true);
+        } else if keys::is_char(&key, 'p') && self.esc_rotation_pane == EscRotationPane::Schedules {
+            self.toggle_selected_esc_schedule().await;
+        }
+    }
+
+    /// Handle keys in the ESC import graph pane
+    async fn handle_esc_graph_key(&mut self, key: KeyEvent) {
+        if keys::is_escape(&key) || keys::is_char(&key, 'i') {
+            self.close_esc_graph();
+        } else if keys::is_up(&key) || keys::is_char(&key, 'k') {
+            self.esc_graph_list.previous();
+        } else if keys::is_down(&key) || keys::is_char(&key, 'j') {
+            self.esc_graph_list.next();
+        } else if keys::is_char(&key, 'K') || keys::is_page_up(&key) {
+            self.esc_graph_list.page(-1, 10);
+        } else if keys::is_char(&key, 'J') || keys::is_page_down(&key) {
+            self.esc_graph_list.page(1, 10);
+        } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
+            self.esc_graph_list.select_first();
+        } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
+            self.esc_graph_list.select_last();
+        } else if keys::is_enter(&key) {
+            self.open_selected_graph_environment().await;
+        }
+    }
+
+    /// Handle keys in the ESC revisions pane
+    async fn handle_esc_revisions_key(&mut self, key: KeyEvent) {
+        if keys::is_escape(&key) || keys::is_char(&key, 'v') {
+            self.close_esc_revisions();
+        } else if keys::is_up(&key) {
+            self.esc_revisions_list.previous();
+            self.update_esc_revision_content().await;
+        } else if keys::is_down(&key) {
+            self.esc_revisions_list.next();
+            self.update_esc_revision_content().await;
+        } else if keys::is_home(&key) || keys::is_char(&key, 'g') {
+            self.esc_revisions_list.select_first();
+            self.update_esc_revision_content().await;
+        } else if keys::is_end(&key) || keys::is_char(&key, 'G') {
+            self.esc_revisions_list.select_last();
+            self.update_esc_revision_content().await;
+        } else if keys::is_char(&key, 'j') {
+            self.esc_revision_scroll.scroll_down();
+        } else if keys::is_char(&key, 'k') {
+            self.esc_revision_scroll.scroll_up();
+        } else if keys::is_char(&key, 'J') || keys::is_page_down(&key) {
+            self.esc_revision_scroll.scroll_page_down();
+        } else if keys::is_char(&key, 'K') || keys::is_page_up(&key) {
+            self.esc_revision_scroll.scroll_page_up();
+        } else if keys::is_char(&key, 'd') {
+            // Mark the selected revision as the diff base, or clear the mark
+            let selected = self.esc_revisions_list.selected().map(|r| r.number);
+            self.esc_revision_diff_base = if self.esc_revision_diff_base == selected {
+                None
+            } else {
+                selected
+            };
+            self.update_esc_revision_content().await;
+        } else if keys::is_char(&key, 't') && self.esc_revisions_list.selected().is_some() {
+            self.esc_revision_input.clear();
+            self.esc_revision_prompt = Some(EscRevisionPrompt::Tag);
+        } else if keys::is_char(&key, 'X') && self.esc_revisions_list.selected().is_some() {
+            self.esc_revision_input.clear();
+            self.esc_revision_prompt = Some(EscRevisionPrompt::Retract);
+        } else if keys::is_char(&key, 'R') {
+            self.rollback_to_selected_revision().await;
+        }
+    }
+
+    /// Handle typing a tag name or retraction reason in the revisions pane
+    async fn handle_esc_revision_prompt_key(&mut self, key: KeyEvent) {
+        if keys::is_escape(&key) {
+            self.esc_revision_prompt = None;
+        } else if keys::is_enter(&key) {
+            let value = self.esc_revision_input.take();
+            let value = value.trim();
+            match self.esc_revision_prompt.take() {
+                Some(EscRevisionPrompt::Tag) if !value.is_empty() => {
+                    self.tag_selected_revision(value).await;
+                }
+                Some(EscRevisionPrompt::Retract) => {
+                    self.retract_selected_revision((!value.is_empty()).then_some(value))
+                        .await;
                 }
+                _ => {}
             }
+        } else {
+            self.esc_revision_input.handle_key(&key);
+        }
+    }
 
-            self.show_esc_editor = false;
-            self.esc_editing_env = None;
+    /// Handle ESC YAML editor keys
+    async fn handle_esc_editor_key(&mut self, key: KeyEvent) {
+        // Escape = Validate, then confirm the diff before saving
+        if keys::is_escape(&key) {
+            if self.esc_editor.is_modified() {
+                self.review_esc_edits().await;
+            } else {
+                self.close_esc_editor();
+            }
             return;
         }
 
         // Ctrl+C = Cancel without saving
         if keys::is_ctrl_char(&key, 'c') {
-            self.show_esc_editor = false;
-            self.esc_editing_env = None;
+            self.close_esc_editor();
             return;
         }
 
@@ -590,6 +1031,57 @@ impl App {
         self.esc_editor.handle_key(&key);
     }
 
+    /// Handle keys in the save confirmation dialog (diff of pending ESC edits)
+    async fn handle_esc_save_diff_key(&mut self, key: KeyEvent) {
+        if keys::is_char(&key, 'y') || keys::is_enter(&key) {
+            self.esc_save_diff = None;
+            self.save_esc_environment().await;
+        } else if keys::is_char(&key, 'd') {
+            // Propose the edits as a draft change request instead
+            self.esc_save_diff = None;
+            self.save_esc_draft().await;
+        } else if keys::is_char(&key, 'n') || keys::is_escape(&key) {
+            // Back to the editor with the edits intact
+            self.esc_save_diff = None;
+        } else if keys::is_char(&key, 'j') || keys::is_down(&key) {
+            for _ in 0..3 {
+                self.esc_save_diff_scroll.scroll_down();
+            }
+        } else if keys::is_char(&key, 'k') || keys::is_up(&key) {
+            for _ in 0..3 {
+                self.esc_save_diff_scroll.scroll_up();
+            }
+        } else if keys::is_char(&key, 'J') || keys::is_page_down(&key) {
+            self.esc_save_diff_scroll.scroll_page_down();
+        } else if keys::is_char(&key, 'K') || keys::is_page_up(&key) {
+            self.esc_save_diff_scroll.scroll_page_up();
+        } else if keys::is_char(&key, 'g') || keys::is_home(&key) {
+            self.esc_save_diff_scroll.scroll_to_top();
+        } else if keys::is_char(&key, 'G') || keys::is_end(&key) {
+            self.esc_save_diff_scroll.scroll_to_bottom();
+        }
+    }
+
+    /// Handle keys while editing the Neo task name filter, filtering as it is typed
+    fn handle_neo_tasks_query_key(&mut self, key: KeyEvent) {
+        if keys::is_escape(&key) {
+            // Esc clears the filter
+            self.neo_tasks_query_input.set_value(String::new());
+        } else if !keys::is_enter(&key) {
+            self.neo_tasks_query_input.handle_key(&key);
+        }
+        if keys::is_escape(&key) || keys::is_enter(&key) {
+            self.neo_tasks_is_editing_query = false;
+            self.neo_tasks_query_input.set_focused(false);
+        }
+
+        let query = self.neo_tasks_query_input.value().trim().to_string();
+        if query != self.neo_tasks_search.query {
+            self.neo_tasks_search.query = query;
+            self.refresh_neo_tasks_list();
+        }
+    }
+
     /// Handle Neo view keys
     async fn handle_neo_key(&mut self, key: KeyEvent) {
         // Esc shows the task list again (if hidden)
@@ -613,14 +1105,33 @@ impl App {
             self.neo_show_command_picker = !self.neo_filtered_commands.is_empty();
             self.neo_command_picker_index = 0;
         } else if keys::is_char(&key, 'n') {
-            // Start new task
-            self.state.neo_messages.clear();
-            self.state.current_task_id = None;
-            self.neo_scroll_state = ScrollViewState::default();
-            self.neo_auto_scroll.store(true, Ordering::Relaxed);
-            self.neo_hide_task_list = true; // Hide task list for new conversation
-            self.focus = FocusMode::Input;
-            self.neo_input.set_focused(true);
+            self.start_new_neo_task();
+        } else if !self.neo_hide_task_list && keys::is_char(&key, 'f') {
+            // Filter the task list by name
+            self.neo_tasks_is_editing_query = true;
+            self.neo_tasks_query_input
+                .set_value(self.neo_tasks_search.query.clone());
+            self.neo_tasks_query_input.set_focused(true);
+        } else if !self.neo_hide_task_list && keys::is_char(&key, 's') {
+            // Filter the task list by status
+            self.neo_tasks_search.status = self.neo_tasks_search.status.next();
+            self.refresh_neo_tasks_list();
+        } else if !self.neo_hide_task_list && keys::is_char(&key, 'm') {
+            // Show only my tasks, or everyone's; without the signed-in user
+            // nothing would match, so the filter is unavailable
+            if self.state.user_login.is_none() && !self.neo_tasks_search.mine {
+                self.error = Some(
+                    "Showing only your tasks is unavailable: the signed-in user could not be loaded"
+                        .to_string(),
+                );
+                return;
+            }
+            self.neo_tasks_search.mine = !self.neo_tasks_search.mine;
+            self.refresh_neo_tasks_list();
+        } else if !self.neo_hide_task_list && keys::is_char(&key, 'S') {
+            // Sort by update or creation time, newest or oldest first
+            self.neo_tasks_search.next_sort();
+            self.refresh_neo_tasks_list();
         } else if keys::is_up(&key) {
             if !self.neo_hide_task_list {
                 // Navigate task list when visible
@@ -634,8 +1145,9 @@ impl App {
             }
         } else if keys::is_down(&key) {
             if !self.neo_hide_task_list {
-                // Navigate task list when visible
+                // Navigate task list when visible, loading more tasks near its end
                 self.neo_tasks_list.next();
+                self.load_more_neo_tasks_if_needed();
             } else {
                 // Scroll chat down when in full-width mode
                 for _ in 0..3 {
@@ -679,11 +1191,67 @@ impl App {
             if self.neo_hide_task_list && self.state.current_task_id.is_some() {
                 // Refresh task details before showing dialog
                 self.refresh_current_task_details().await;
+                self.neo_share_link_copied = false;
                 self.show_neo_details = true;
             }
+        } else if keys::is_char(&key, 't') {
+            // Jump to the next tool call
+            self.select_neo_tool_call(true);
+        } else if keys::is_char(&key, 'T') {
+            // Jump to the previous tool call
+            self.select_neo_tool_call(false);
+        } else if keys::is_char(&key, ' ') {
+            // Expand or collapse the selected tool call
+            self.toggle_neo_tool_call();
+        } else if keys::is_char(&key, 'e') || keys::is_char(&key, 'E') {
+            // Export the loaded conversation, as Markdown (e) or JSON (E)
+            if !self.neo_hide_task_list {
+                self.error = Some("Open a task (Enter) to export its conversation".to_string());
+                return;
+            }
+            let format = if keys::is_char(&key, 'e') {
+                NeoExportFormat::Markdown
+            } else {
+                NeoExportFormat::Json
+            };
+            self.export_neo_conversation(format).await;
         } else if keys::is_char(&key, 'c') {
             // Show slash commands management dialog
             self.open_slash_commands_dialog().await;
+        } else if keys::is_char(&key, 'a') {
+            // Approve the pending approval request
+            self.open_approval_dialog(true);
+        } else if keys::is_char(&key, 'x') {
+            // Reject the pending approval request
+            self.open_approval_dialog(false);
+        } else if keys::is_char(&key, 'X') {
+            // Cancel the running task (asks for confirmation)
+            self.open_cancel_confirm();
+        }
+    }
+
+    /// Handle keys in the Neo cancel confirmation dialog
+    async fn handle_neo_cancel_key(&mut self, key: KeyEvent) {
+        if keys::is_char(&key, 'y') || keys::is_char(&key, 'Y') {
+            self.cancel_neo_task().await;
+        } else if keys::is_char(&key, 'n') || keys::is_char(&key, 'N') || keys::is_escape(&key) {
+            self.neo_cancel_target = None;
+        }
+    }
+
+    /// Handle keys in the Neo approval dialog
+    fn handle_neo_approval_key(&mut self, key: KeyEvent) {
+        if keys::is_escape(&key) {
+            // Cancel without responding - the prompt stays visible
+            self.show_neo_approval = false;
+            self.neo_approval_comment.set_focused(false);
+        } else if keys::is_enter(&key) {
+            self.submit_neo_approval();
+        } else if keys::is_tab(&key) || keys::is_backtab(&key) {
+            // Toggle between approve and reject
+            self.neo_approval_approve = !self.neo_approval_approve;
+        } else {
+            self.neo_approval_comment.handle_key(&key);
         }
     }
 
@@ -1313,6 +1881,8 @@ impl App {
                     self.platform_desc_scroll_state = ScrollViewState::default();
                 }
             },
+            // N - ask Neo about the selected service, component or template
+            KeyCode::Char('N') => self.ask_neo_about_selection(),
             // Number keys - jump to specific view
             KeyCode::Char('1') => {
                 self.platform_view = PlatformView::Services;
@@ -1392,29 +1962,16 @@ impl App {
 
         match key.code {
             // Space toggles the "don't show again" checkbox (only if checks passed)
-            KeyCode::Char(' ') => {
-                if checks_passed {
-                    self.splash_dont_show_again = !self.splash_dont_show_again;
-                }
+            KeyCode::Char(' ') if checks_passed => {
+                self.splash_dont_show_again = !self.splash_dont_show_again;
             }
-            // Enter dismisses the splash (only if checks passed)
-            KeyCode::Enter => {
-                if checks_complete && checks_passed {
-                    self.dismiss_splash();
-                }
-            }
-            // Escape also dismisses (only if checks passed)
-            KeyCode::Esc => {
-                if checks_complete && checks_passed {
-                    self.dismiss_splash();
-                }
+            // Enter or Escape dismisses the splash (only if checks passed)
+            KeyCode::Enter | KeyCode::Esc if checks_complete && checks_passed => {
+                self.dismiss_splash();
             }
             // q quits the application (always available, especially when checks fail)
-            KeyCode::Char('q') => {
-                // Always allow quitting, but especially important when checks fail
-                if checks_failed || checks_complete {
-                    self.should_quit = true;
-                }
+            KeyCode::Char('q') if checks_failed || checks_complete => {
+                self.should_quit = true;
             }
             _ => {}
         }
@@ -1578,8 +2135,90 @@ impl App {
         }
     }
 
+    /// Whether keystrokes should go to a running interactive command
+    pub(super) fn is_command_terminal_active(&self) -> bool {
+        self.commands_input.is_some()
+            && self.current_command_execution.as_ref().is_some_and(|exec| {
+                exec.terminal.is_some() && exec.state == CommandExecutionState::Running
+            })
+    }
+
+    /// Cancel the running command: interrupt first, kill if asked again
+    fn cancel_running_command(&mut self) {
+        let (Some(input), Some(exec)) = (
+            self.commands_input.as_ref(),
+            self.current_command_execution.as_mut(),
+        ) else {
+            return;
+        };
+        if exec.state != CommandExecutionState::Running {
+            return;
+        }
+        if exec.cancel_requested {
+            input.kill();
+        } else {
+            exec.cancel_requested = true;
+            input.interrupt();
+        }
+    }
+
+    /// Forward a key press to the running command's terminal
+    fn handle_commands_terminal_key(&mut self, key: KeyEvent) {
+        // Ctrl+C cancels through signals, since prompts read raw keys
+        if keys::is_ctrl_char(&key, 'c') {
+            self.cancel_running_command();
+            return;
+        }
+
+        let (Some(input), Some(exec)) = (
+            self.commands_input.as_ref(),
+            self.current_command_execution.as_mut(),
+        ) else {
+            return;
+        };
+        let Some(terminal) = exec.terminal.as_mut() else {
+            return;
+        };
+        if let Some(bytes) = encode_key(&key, terminal.screen().application_cursor()) {
+            // Typing jumps back to the live screen
+            terminal.scroll_to_bottom();
+            input.write(bytes);
+        }
+    }
+
     /// Handle keys in output view
     fn handle_commands_output_key(&mut self, key: KeyEvent) {
+        // Scroll the emulated terminal's history when it is shown
+        if self.commands_show_raw_output {
+            if let Some(terminal) = self
+                .current_command_execution
+                .as_mut()
+                .and_then(|exec| exec.terminal.as_mut())
+            {
+                if keys::is_char(&key, 'j') || keys::is_down(&key) {
+                    terminal.scroll_down(3);
+                    return;
+                } else if keys::is_char(&key, 'k') || keys::is_up(&key) {
+                    terminal.scroll_up(3);
+                    return;
+                } else if keys::is_page_down(&key) || keys::is_char(&key, 'J') {
+                    let (rows, _) = terminal.size();
+                    terminal.scroll_down(rows as usize);
+                    return;
+                } else if keys::is_page_up(&key) || keys::is_char(&key, 'K') {
+                    let (rows, _) = terminal.size();
+                    terminal.scroll_up(rows as usize);
+                    return;
+                } else if keys::is_char(&key, 'g') {
+                    terminal.scroll_to_top();
+                    return;
+                } else if keys::is_char(&key, 'G') {
+                    terminal.scroll_to_bottom();
+                    return;
+                }
+            }
+        }
+
         if keys::is_escape(&key) {
             // Close output view and go back to commands
             self.commands_view_state = CommandsViewState::BrowsingCommands;
@@ -1603,11 +2242,17 @@ impl App {
             self.commands_output_scroll.scroll_to_top();
         } else if keys::is_char(&key, 'G') {
             self.commands_output_scroll.scroll_to_bottom();
+        } else if keys::is_char(&key, 'X') {
+            self.cancel_running_command();
+        } else if keys::is_char(&key, 'v') {
+            // Toggle between the live resource table and raw text output
+            self.commands_show_raw_output = !self.commands_show_raw_output;
+            self.commands_output_scroll = ScrollViewState::default();
         }
     }
 
     /// Update commands list based on selected category
-    fn update_commands_for_selected_category(&mut self) {
+    pub(super) fn update_commands_for_selected_category(&mut self) {
         if let Some(category) = self.commands_category_list.selected() {
             let commands = commands_by_category(*category);
             let has_commands = !commands.is_empty();
@@ -1620,52 +2265,97 @@ impl App {
 
     /// Start execution of the selected command
     fn start_command_execution(&mut self) {
-        use crate::components::TextInput;
+        if let Some(cmd) = self.commands_command_list.selected().copied() {
+            self.begin_command_execution(cmd, &[]);
+        }
+    }
 
-        if let Some(cmd) = self.commands_command_list.selected() {
-            // Check if the command is interactive
-            if cmd.execution_mode == ExecutionMode::Interactive {
-                self.error = Some(format!(
-                    "Command '{}' requires interactive mode.\nPlease run it directly in your terminal.",
-                    cmd.name
-                ));
-                return;
-            }
+    /// Switch to the Commands view and open the parameter dialog for `cmd`,
+    /// selecting it in the sidebar behind the dialog. Refused while another
+    /// command is running.
+    pub(super) fn show_command_dialog(
+        &mut self,
+        cmd: &'static PulumiCommand,
+        category: CommandCategory,
+        prefill: &[(&str, String)],
+    ) {
+        if self
+            .current_command_execution
+            .as_ref()
+            .is_some_and(|exec| exec.state == CommandExecutionState::Running)
+        {
+            self.error = Some("Another command is still running".to_string());
+            return;
+        }
 
-            // Create execution instance
-            let execution = CommandExecution::new(cmd);
+        if let Some(idx) = self
+            .commands_category_list
+            .items()
+            .iter()
+            .position(|c| *c == category)
+        {
+            self.commands_category_list.select(Some(idx));
+            self.update_commands_for_selected_category();
+        }
+        if let Some(idx) = self
+            .commands_command_list
+            .items()
+            .iter()
+            .position(|c| c.name == cmd.name)
+        {
+            self.commands_command_list.select(Some(idx));
+        }
 
-            // Create input fields for parameters
-            self.commands_param_inputs = cmd
-                .params
-                .iter()
-                .map(|param| {
-                    let mut input = TextInput::new();
-                    if let Some(default) = param.default {
-                        input.set_value(default.to_string());
-                    }
-                    input
-                })
-                .collect();
+        self.tab = Tab::Commands;
+        self.begin_command_execution(cmd, prefill);
+    }
 
-            // Set focus to first parameter if any
-            self.commands_param_focus_index = 0;
-            if let Some(input) = self.commands_param_inputs.first_mut() {
-                input.set_focused(true);
-            }
+    /// Open the parameter dialog for a command, with `prefill` values taking
+    /// precedence over parameter defaults
+    pub(super) fn begin_command_execution(
+        &mut self,
+        cmd: &'static PulumiCommand,
+        prefill: &[(&str, String)],
+    ) {
+        use crate::components::TextInput;
 
-            self.current_command_execution = Some(execution);
+        // Create execution instance
+        let execution = CommandExecution::new(cmd);
 
-            // If no parameters, skip to confirmation or run
-            if cmd.params.is_empty() {
-                if cmd.needs_confirmation {
-                    self.commands_view_state = CommandsViewState::ConfirmDialog;
-                } else {
-                    self.run_current_command();
+        // Create input fields for parameters
+        self.commands_param_inputs = cmd
+            .params
+            .iter()
+            .map(|param| {
+                let mut input = TextInput::new();
+                let prefilled = prefill
+                    .iter()
+                    .find(|(name, _)| *name == param.name)
+                    .map(|(_, value)| value.clone());
+                if let Some(value) = prefilled.or(param.default.map(str::to_string)) {
+                    input.set_value(value);
                 }
+                input
+            })
+            .collect();
+
+        // Set focus to first parameter if any
+        self.commands_param_focus_index = 0;
+        if let Some(input) = self.commands_param_inputs.first_mut() {
+            input.set_focused(true);
+        }
+
+        self.current_command_execution = Some(execution);
+
+        // If no parameters, skip to confirmation or run
+        if cmd.params.is_empty() {
+            if cmd.needs_confirmation {
+                self.commands_view_state = CommandsViewState::ConfirmDialog;
             } else {
-                self.commands_view_state = CommandsViewState::InputDialog;
+                self.run_current_command();
             }
+        } else {
+            self.commands_view_state = CommandsViewState::InputDialog;
         }
     }
 
@@ -1694,12 +2384,19 @@ impl App {
 
             // Update state to running
             exec.state = CommandExecutionState::Running;
+            exec.engine = exec.uses_event_log().then(EngineProgress::default);
+            let (rows, cols) = self.commands_terminal_size;
+            exec.terminal = exec
+                .is_interactive()
+                .then(|| TerminalScreen::new(rows, cols));
+            // Prompts must be visible, so interactive commands open on the terminal
+            self.commands_show_raw_output = exec.terminal.is_some();
             self.commands_view_state = CommandsViewState::OutputView;
             self.commands_output_scroll = ScrollViewState::default();
 
             // Spawn the command
             let tx = self.command_result_tx.clone();
-            spawn_command(exec, tx);
+            self.commands_input = Some(spawn_command(exec, tx, (rows, cols)));
         }
     }
 }
@@ -7,16 +7,32 @@
 //! - View: render() method
 
 mod data;
+mod esc;
+mod esc_changes;
+mod esc_export;
+mod esc_graph;
+mod esc_rotation;
 mod handlers;
 mod neo;
+mod neo_context;
+mod neo_export;
+mod neo_tasks;
+mod resources;
+mod stacks;
 mod types;
 
 pub use types::{
-    AppState, DataLoadResult, EscPane, FocusMode, NeoAsyncResult, PlatformView,
-    SlashCommandsDialogView, Tab,
+    AppState, DataLoadResult, EscChangeAction, EscChangeView, EscEnvDialog, EscExportFormat,
+    EscGraphMark, EscGraphNode, EscGraphRow, EscPane, EscRevisionPrompt, EscRotationPane,
+    EscRotator, FocusMode, NeoApprovalState, NeoAsyncResult, NeoExportFormat, NeoTaskSearchState,
+    NeoTaskStatusFilter, PlatformView, PollBackoff, ResourceSearchState, ResourceSortColumn,
+    SlashCommandsDialogView, StackResourceRow, StacksPane, Tab,
 };
 
 use color_eyre::Result;
+use ratatui::text::Line;
+use ratatui::widgets::TableState;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
@@ -24,10 +40,13 @@ use tokio::sync::mpsc;
 use tui_scrollview::ScrollViewState;
 
 use crate::api::{
-    EscEnvironmentSummary, NeoTask, PulumiClient, RegistryPackage, RegistryTemplate, Service, Stack,
+    EscChangeRequest, EscEnvironmentSummary, EscRevision, EscRevisionTag, EscRotationEvent,
+    EscSchedule, NeoEntity, NeoEventCursor, NeoTask, PulumiClient, RegistryPackage,
+    RegistryTemplate, Resource, Service, Stack, StackUpdate,
 };
 use crate::commands::{
-    commands_by_category, CommandCategory, CommandExecution, CommandResult, PulumiCommand,
+    commands_by_category, CommandCategory, CommandExecution, CommandExecutionState, CommandInput,
+    CommandResult, PulumiCommand,
 };
 use crate::components::{Spinner, StatefulList, TextEditor, TextInput};
 use crate::config::Config;
@@ -107,21 +126,137 @@ pub struct App {
     pub(super) stacks_list: StatefulList<Stack>,
     pub(super) esc_list: StatefulList<EscEnvironmentSummary>,
     pub(super) neo_tasks_list: StatefulList<NeoTask>,
+    /// Filters, sort order and paging of the Neo task list
+    pub(super) neo_tasks_search: NeoTaskSearchState,
+    /// Task name filter input
+    pub(super) neo_tasks_query_input: TextInput,
+    /// Whether the task name filter input is focused
+    pub(super) neo_tasks_is_editing_query: bool,
     pub(super) neo_input: TextInput,
 
+    // Stacks detail state
+    /// Which pane is currently focused (Stacks, Updates, Resources or Properties)
+    pub(super) stacks_pane: types::StacksPane,
+    /// Update history of the selected stack
+    pub(super) stack_updates_list: StatefulList<StackUpdate>,
+    /// Show update details dialog
+    pub(super) show_update_details: bool,
+    /// Scroll state for the update details dialog
+    pub(super) update_details_scroll: ScrollViewState,
+    /// Resources of the selected stack, ordered as a parent/child tree
+    pub(super) stack_resources_list: StatefulList<StackResourceRow>,
+    /// Scroll state for the resource Properties pane
+    pub(super) stack_resource_scroll: ScrollViewState,
+
+    // Resources view state
+    /// Current page of resource search results
+    pub(super) resources_list: StatefulList<Resource>,
+    /// Table scroll state for the results
+    pub(super) resources_table_state: TableState,
+    /// Query, sort order and page of the search
+    pub(super) resources_search: ResourceSearchState,
+    /// Search query input
+    pub(super) resources_query_input: TextInput,
+    /// Whether the query input is focused
+    pub(super) resources_is_editing_query: bool,
+
     // ESC detail pane state
     /// Which pane is currently focused (Definition or Resolved Values)
     pub(super) esc_pane: types::EscPane,
     /// Scroll state for Definition pane
     pub(super) esc_definition_scroll: ScrollViewState,
-    /// Scroll state for Resolved Values pane
-    pub(super) esc_values_scroll: ScrollViewState,
+    /// Resolved values of the opened environment, one row per property
+    pub(super) esc_values_list: StatefulList<ui::EscValue>,
+    /// Paths of secrets revealed in the Resolved Values pane (masked by default)
+    pub(super) esc_revealed_values: HashSet<String>,
+    /// What was last copied from the selected value ("path" or "value")
+    pub(super) esc_value_copied: Option<&'static str>,
+    /// Export dialog for the opened environment's variables, with the chosen format
+    pub(super) esc_export: Option<EscExportFormat>,
+    /// File the export dialog writes to
+    pub(super) esc_export_input: TextInput,
+    /// Whether the export dialog is asking before writing secrets to disk
+    /// or overwriting a file
+    pub(super) esc_export_confirm: bool,
+    /// Whether the file the export dialog writes to already exists
+    pub(super) esc_export_overwrite: bool,
+    /// File the opened environment's variables were last exported to
+    pub(super) esc_exported: Option<String>,
     /// Show YAML editor dialog
     pub(super) show_esc_editor: bool,
     /// YAML editor component
     pub(super) esc_editor: TextEditor,
     /// Environment being edited (org, project, name)
     pub(super) esc_editing_env: Option<(String, String, String)>,
+    /// If-Match value the edited definition was loaded with
+    pub(super) esc_editing_etag: Option<String>,
+    /// Diff of the validated edits, shown for confirmation before saving
+    pub(super) esc_save_diff: Option<Vec<ui::DiffLine>>,
+    /// Scroll state for the save confirmation diff
+    pub(super) esc_save_diff_scroll: ScrollViewState,
+    /// Environment whose import graph is shown in place of the details
+    pub(super) esc_graph_env: Option<esc::EscEnvRef>,
+    /// Imports and referrers of that environment, as tree rows
+    pub(super) esc_graph_list: StatefulList<EscGraphRow>,
+    /// Organization whose change requests are shown in place of the details
+    pub(super) esc_change_requests_org: Option<String>,
+    /// Open change requests for ESC environments, newest first
+    pub(super) esc_change_requests: StatefulList<EscChangeRequest>,
+    /// Whether the selected change request shows its diff or resolved values
+    pub(super) esc_change_view: EscChangeView,
+    /// Highlighted diff of the selected change request against the current definition
+    pub(super) esc_change_content: Option<Vec<Line<'static>>>,
+    /// Values the selected change request's draft resolves to
+    pub(super) esc_change_values: Option<Vec<ui::EscValue>>,
+    /// Scroll state for the change request content
+    pub(super) esc_change_scroll: ScrollViewState,
+    /// Apply or close waiting for confirmation
+    pub(super) esc_change_confirm: Option<EscChangeAction>,
+    /// Whether the submit description prompt is shown
+    pub(super) esc_change_prompt: bool,
+    /// Description typed when submitting a draft
+    pub(super) esc_change_input: TextInput,
+    /// Environment whose secret rotation is shown in place of the details
+    pub(super) esc_rotation_env: Option<esc::EscEnvRef>,
+    /// Rotators declared in that environment's definition
+    pub(super) esc_rotators: StatefulList<EscRotator>,
+    /// Schedules attached to that environment
+    pub(super) esc_schedules: StatefulList<EscSchedule>,
+    /// Rotation runs of that environment, newest first
+    pub(super) esc_rotation_history: Vec<EscRotationEvent>,
+    /// Focused section of the rotation pane
+    pub(super) esc_rotation_pane: EscRotationPane,
+    /// Rotator paths waiting for confirmation; empty rotates them all
+    pub(super) esc_rotation_confirm: Option<Vec<String>>,
+    /// Environment whose revision history is shown in place of the details
+    pub(super) esc_revisions_env: Option<esc::EscEnvRef>,
+    /// Revisions of that environment, newest first
+    pub(super) esc_revisions_list: StatefulList<EscRevision>,
+    /// Version tags of that environment
+    pub(super) esc_revision_tags: Vec<EscRevisionTag>,
+    /// YAML of the revisions fetched so far, by revision number
+    pub(super) esc_revision_yaml: HashMap<i64, String>,
+    /// Revision marked as the left side of a diff
+    pub(super) esc_revision_diff_base: Option<i64>,
+    /// Highlighted YAML (or diff) of the selected revision
+    pub(super) esc_revision_content: Option<Vec<Line<'static>>>,
+    /// Scroll state for the revision content
+    pub(super) esc_revision_scroll: ScrollViewState,
+    /// Tag name / retraction reason prompt over the revisions pane
+    pub(super) esc_revision_prompt: Option<EscRevisionPrompt>,
+    pub(super) esc_revision_input: TextInput,
+    /// Create / clone / delete environment dialog
+    pub(super) esc_env_dialog: Option<EscEnvDialog>,
+    /// Project field of the create/clone dialog
+    pub(super) esc_env_project_input: TextInput,
+    /// Name field of the create/clone dialog (typed-back name when deleting)
+    pub(super) esc_env_name_input: TextInput,
+    /// Focused field of the dialog
+    pub(super) esc_env_dialog_focus: usize,
+    /// Clone option: copy the revision history
+    pub(super) esc_clone_keep_history: bool,
+    /// Clone option: copy environment and version tags
+    pub(super) esc_clone_keep_tags: bool,
 
     // Platform UI state
     pub(super) platform_view: PlatformView,
@@ -133,26 +268,53 @@ pub struct App {
 
     /// Neo polling state - tracks if we're waiting for agent response
     pub(super) neo_polling: bool,
-    /// Counter for polling interval (poll every N ticks)
-    pub(super) neo_poll_counter: u8,
+    /// Adaptive interval between polls while waiting for the agent
+    pub(super) neo_poll_backoff: PollBackoff,
+    /// A poll request is outstanding (avoid piling up requests on slow networks)
+    pub(super) neo_poll_in_flight: bool,
+    /// Where the next poll resumes in the task's event stream
+    pub(super) neo_event_cursor: NeoEventCursor,
+    /// Locally echoed user messages at the end of the chat not yet confirmed by the API
+    pub(super) neo_optimistic_messages: usize,
     /// Counter for stable polls (no new messages for N consecutive polls)
     pub(super) neo_stable_polls: u8,
-    /// Previous message count (to detect changes)
-    pub(super) neo_prev_message_count: usize,
-    /// Max polling attempts before giving up
-    pub(super) neo_max_polls: u8,
-    /// Current poll count
-    pub(super) neo_current_poll: u8,
     /// Background poll counter for when Neo tab is active
     pub(super) neo_bg_poll_counter: u8,
     /// Neo chat scroll view state
     pub(super) neo_scroll_state: ScrollViewState,
+    /// Rendered chat messages, so unchanged messages are not re-rendered each frame
+    pub(super) neo_render_cache: ui::NeoRenderCache,
     /// Auto-scroll to bottom when new messages arrive
     pub(super) neo_auto_scroll: Arc<AtomicBool>,
     /// Hide task list when a task is selected (full-width chat)
     pub(super) neo_hide_task_list: bool,
     /// Show Neo task details dialog
     pub(super) show_neo_details: bool,
+    /// Whether the share link was copied while the details dialog is open
+    pub(super) neo_share_link_copied: bool,
+    /// File the loaded Neo conversation was last exported to
+    pub(super) neo_exported: Option<String>,
+    /// Export waiting for confirmation before overwriting an existing file
+    pub(super) neo_export_confirm: Option<NeoExportFormat>,
+    /// Entity attached to the next new Neo task, from "Ask Neo about this"
+    pub(super) neo_attached_entity: Option<NeoEntity>,
+    /// IDs of the Neo tool calls shown expanded
+    pub(super) neo_expanded_tool_calls: HashSet<String>,
+    /// Neo tool call jumped to with t/T
+    pub(super) neo_selected_tool_call: Option<String>,
+    /// Scroll the selected tool call into view on the next frame
+    pub(super) neo_reveal_tool_call: bool,
+    /// Show Neo approval dialog (approve/reject a pending approval request)
+    pub(super) show_neo_approval: bool,
+    /// Decision in the approval dialog (true = approve, false = reject)
+    pub(super) neo_approval_approve: bool,
+    /// Optional comment sent along with the approval decision
+    pub(super) neo_approval_comment: TextInput,
+    /// Which approval request was answered, or is being answered
+    /// (hides the prompt until Neo emits new events)
+    pub(super) neo_approval: NeoApprovalState,
+    /// Task awaiting cancel confirmation (shows the confirm dialog when set)
+    pub(super) neo_cancel_target: Option<NeoTask>,
     /// Current task is running (from task status API)
     /// Used to keep thinking indicator visible until confirmed not running
     pub(super) neo_task_is_running: bool,
@@ -227,6 +389,12 @@ pub struct App {
     pub(super) commands_filter_input: TextInput,
     /// Whether the filter input is focused
     pub(super) commands_is_filtering: bool,
+    /// Show raw text output instead of the live resource table
+    pub(super) commands_show_raw_output: bool,
+    /// Keystroke and resize handle for the running command's PTY
+    pub(super) commands_input: Option<CommandInput>,
+    /// Output pane size (rows, cols) from the last render, used to size the PTY
+    pub(super) commands_terminal_size: (u16, u16),
     /// Channel for receiving command execution results
     pub(super) command_result_rx: mpsc::Receiver<CommandResult>,
     /// Channel sender for command execution results
@@ -306,29 +474,99 @@ impl App {
             stacks_list: StatefulList::new(),
             esc_list: StatefulList::new(),
             neo_tasks_list: StatefulList::new(),
+            neo_tasks_search: NeoTaskSearchState::default(),
+            neo_tasks_query_input: TextInput::new(),
+            neo_tasks_is_editing_query: false,
             neo_input: TextInput::new(),
+            stacks_pane: types::StacksPane::default(),
+            stack_updates_list: StatefulList::new(),
+            show_update_details: false,
+            update_details_scroll: ScrollViewState::default(),
+            stack_resources_list: StatefulList::new(),
+            stack_resource_scroll: ScrollViewState::default(),
+            resources_list: StatefulList::new(),
+            resources_table_state: TableState::default(),
+            resources_search: ResourceSearchState::default(),
+            resources_query_input: TextInput::new(),
+            resources_is_editing_query: false,
             esc_pane: types::EscPane::default(),
             esc_definition_scroll: ScrollViewState::default(),
-            esc_values_scroll: ScrollViewState::default(),
+            esc_values_list: StatefulList::new(),
+            esc_revealed_values: HashSet::new(),
+            esc_value_copied: None,
+            esc_export: None,
+            esc_export_input: TextInput::new(),
+            esc_export_confirm: false,
+            esc_export_overwrite: false,
+            esc_exported: None,
             show_esc_editor: false,
             esc_editor: TextEditor::new(),
             esc_editing_env: None,
+            esc_editing_etag: None,
+            esc_save_diff: None,
+            esc_save_diff_scroll: ScrollViewState::default(),
+            esc_graph_env: None,
+            esc_graph_list: StatefulList::new(),
+            esc_change_requests_org: None,
+            esc_change_requests: StatefulList::new(),
+            esc_change_view: EscChangeView::default(),
+            esc_change_content: None,
+            esc_change_values: None,
+            esc_change_scroll: ScrollViewState::default(),
+            esc_change_confirm: None,
+            esc_change_prompt: false,
+            esc_change_input: TextInput::new(),
+            esc_rotation_env: None,
+            esc_rotators: StatefulList::new(),
+            esc_schedules: StatefulList::new(),
+            esc_rotation_history: Vec::new(),
+            esc_rotation_pane: EscRotationPane::default(),
+            esc_rotation_confirm: None,
+            esc_revisions_env: None,
+            esc_revisions_list: StatefulList::new(),
+            esc_revision_tags: Vec::new(),
+            esc_revision_yaml: HashMap::new(),
+            esc_revision_diff_base: None,
+            esc_revision_content: None,
+            esc_revision_scroll: ScrollViewState::default(),
+            esc_revision_prompt: None,
+            esc_revision_input: TextInput::new(),
+            esc_env_dialog: None,
+            esc_env_project_input: TextInput::new(),
+            esc_env_name_input: TextInput::new(),
+            esc_env_dialog_focus: 0,
+            esc_clone_keep_history: true,
+            esc_clone_keep_tags: true,
             platform_view: PlatformView::Services,
             services_list: StatefulList::new(),
             packages_list: StatefulList::new(),
             templates_list: StatefulList::new(),
             platform_desc_scroll_state: ScrollViewState::default(),
             neo_polling: false,
-            neo_poll_counter: 0,
+            // Poll every 500ms while idle polls stretch it out to 5s (100ms ticks)
+            neo_poll_backoff: PollBackoff::new(5, 50),
+            neo_poll_in_flight: false,
+            neo_event_cursor: NeoEventCursor::default(),
+            neo_optimistic_messages: 0,
             neo_stable_polls: 0,
-            neo_prev_message_count: 0,
-            neo_max_polls: 60, // Max 60 polls (~60 seconds at 1 poll/second)
-            neo_current_poll: 0,
             neo_bg_poll_counter: 0,
             neo_scroll_state: ScrollViewState::default(),
+            neo_render_cache: ui::NeoRenderCache::new(),
             neo_auto_scroll: Arc::new(AtomicBool::new(true)),
             neo_hide_task_list: false,
             show_neo_details: false,
+            neo_share_link_copied: false,
+            neo_exported: None,
+            neo_export_confirm: None,
+            neo_attached_entity: None,
+            neo_expanded_tool_calls: HashSet::new(),
+            neo_selected_tool_call: None,
+            neo_reveal_tool_call: false,
+            show_neo_approval: false,
+            neo_approval_approve: true,
+            neo_approval_comment: TextInput::new(),
+            neo_approval: NeoApprovalState::default(),
+            neo_cancel_target: None,
             neo_task_is_running: false,
             neo_show_command_picker: false,
             neo_filtered_commands: Vec::new(),
@@ -364,6 +602,9 @@ impl App {
             commands_output_scroll: ScrollViewState::default(),
             commands_filter_input: TextInput::new(),
             commands_is_filtering: false,
+            commands_show_raw_output: false,
+            commands_input: None,
+            commands_terminal_size: (24, 80),
             command_result_rx,
             command_result_tx,
         };
@@ -394,6 +635,7 @@ impl App {
 
             // Render
             self.render()?;
+            self.sync_command_terminal_size();
 
             // Check for async startup check results (non-blocking)
             self.process_startup_results().await;
@@ -417,10 +659,8 @@ impl App {
 
                     // Poll for Neo updates if we're waiting for a response (fast polling)
                     if self.neo_polling {
-                        self.neo_poll_counter += 1;
-                        // Poll every 5 ticks (~500ms at 100ms tick rate)
-                        if self.neo_poll_counter >= 5 {
-                            self.neo_poll_counter = 0;
+                        // Interval adapts: fast while events arrive, slower while idle
+                        if self.neo_poll_backoff.tick() {
                             self.spawn_neo_poll();
                         }
                     }
@@ -468,11 +708,62 @@ impl App {
         let show_org_selector = self.show_org_selector;
         let show_logs = self.show_logs;
         let show_neo_details = self.show_neo_details;
+        // Get task for details dialog (cloned so the closure can borrow the task list mutably)
+        let selected_task_for_details: Option<NeoTask> = if show_neo_details {
+            self.details_task().cloned()
+        } else {
+            None
+        };
+        let neo_share_url = selected_task_for_details
+            .as_ref()
+            .and_then(|task| self.neo_task_share_url(task));
+        let neo_share_link_copied = self.neo_share_link_copied;
+        let neo_exported = self.neo_exported.as_deref();
+        let neo_export_overwrite = self
+            .neo_export_confirm
+            .and_then(|format| self.neo_export_path(format))
+            .map(|path| path.display().to_string());
+        let neo_attached = self.neo_attached_entity.as_ref().map(|entity| {
+            format!(
+                "{} {}",
+                entity.entity_type.as_deref().unwrap_or("entity"),
+                neo_context::entity_label(entity)
+            )
+        });
+        let show_neo_approval = self.show_neo_approval;
+        let neo_approval_approve = self.neo_approval_approve;
+        let neo_approval_comment = &self.neo_approval_comment;
+        let neo_pending_approval_index = self.pending_approval_index();
+        let neo_cancel_target = self.neo_cancel_target.as_ref();
         let show_esc_editor = self.show_esc_editor;
         let show_slash_commands_dialog = self.show_slash_commands_dialog;
         let slash_commands_dialog_view = self.slash_commands_dialog_view;
         let esc_editor = &self.esc_editor;
         let esc_editing_env = self.esc_editing_env.clone();
+        let esc_save_diff = self.esc_save_diff.as_deref();
+        let esc_env_dialog = self.esc_env_dialog;
+        let esc_env_dialog_selected = self
+            .esc_list
+            .selected()
+            .map(|env| format!("{}/{}", env.project, env.name));
+        let esc_env_project_input = &self.esc_env_project_input;
+        let esc_env_name_input = &self.esc_env_name_input;
+        let esc_env_dialog_focus = self.esc_env_dialog_focus;
+        let esc_clone_keep_history = self.esc_clone_keep_history;
+        let esc_clone_keep_tags = self.esc_clone_keep_tags;
+        let esc_export_counts = self.esc_export.is_some().then(|| self.esc_export_counts());
+        let esc_export =
+            self.esc_export
+                .zip(esc_export_counts)
+                .map(|(format, (variables, secrets))| ui::EscExportProps {
+                    env: esc_env_dialog_selected.clone().unwrap_or_default(),
+                    format,
+                    path_input: &self.esc_export_input,
+                    variables,
+                    secrets,
+                    confirm: self.esc_export_confirm,
+                    overwrite: self.esc_export_overwrite,
+                });
         let logger_state = &self.logger_state;
         let is_loading = self.is_loading;
         // For Neo tab, show spinner when polling (waiting for response)
@@ -490,9 +781,18 @@ impl App {
         let stacks_list = &mut self.stacks_list;
         let esc_list = &mut self.esc_list;
         let neo_tasks_list = &mut self.neo_tasks_list;
+        let neo_tasks_search = &self.neo_tasks_search;
+        let neo_tasks_query_input = &self.neo_tasks_query_input;
+        let neo_tasks_is_editing_query = self.neo_tasks_is_editing_query;
         let neo_input = &self.neo_input;
         let org_list = &mut self.org_list;
         let neo_scroll_state = &mut self.neo_scroll_state;
+        let neo_render_cache = &mut self.neo_render_cache;
+        let neo_tool_calls = ui::NeoToolCallView {
+            expanded: &self.neo_expanded_tool_calls,
+            selected: self.neo_selected_tool_call.as_deref(),
+        };
+        let neo_reveal_tool_call = &mut self.neo_reveal_tool_call;
         let neo_auto_scroll = self.neo_auto_scroll.clone();
         let neo_hide_task_list = self.neo_hide_task_list;
         let neo_show_command_picker = self.neo_show_command_picker;
@@ -512,10 +812,71 @@ impl App {
         let slash_cmd_edit_prompt = &self.slash_cmd_edit_prompt;
         let slash_cmd_edit_focus = self.slash_cmd_edit_focus;
 
+        // Stacks detail state
+        let stacks_pane = self.stacks_pane;
+        let stack_updates_list = &mut self.stack_updates_list;
+        let show_update_details = self.show_update_details;
+        let update_details_scroll = &mut self.update_details_scroll;
+        let esc_save_diff_scroll = &mut self.esc_save_diff_scroll;
+        let stack_resources_list = &mut self.stack_resources_list;
+        let stack_resource_scroll = &mut self.stack_resource_scroll;
+
+        // Resources view state
+        let resources_list = &mut self.resources_list;
+        let resources_table_state = &mut self.resources_table_state;
+        let resources_search = &self.resources_search;
+        let resources_query_input = &self.resources_query_input;
+        let resources_is_editing_query = self.resources_is_editing_query;
+
         // ESC detail pane state
         let esc_pane = self.esc_pane;
         let esc_definition_scroll = &mut self.esc_definition_scroll;
-        let esc_values_scroll = &mut self.esc_values_scroll;
+        let esc_values_list = &mut self.esc_values_list;
+        let esc_revealed_values = &self.esc_revealed_values;
+        let esc_value_copied = self.esc_value_copied;
+        let esc_exported = self.esc_exported.as_deref();
+        let esc_graph = self
+            .esc_graph_env
+            .as_ref()
+            .map(|(_, project, name)| ui::EscGraphProps {
+                root: format!("{}/{}", project, name),
+                rows: &mut self.esc_graph_list,
+            });
+        let esc_changes =
+            self.esc_change_requests_org
+                .is_some()
+                .then(|| ui::EscChangeRequestsProps {
+                    requests: &mut self.esc_change_requests,
+                    view: self.esc_change_view,
+                    content: self.esc_change_content.as_deref(),
+                    values: self.esc_change_values.as_deref(),
+                    scroll: &mut self.esc_change_scroll,
+                    confirm: self.esc_change_confirm,
+                    prompt: self.esc_change_prompt.then_some(&self.esc_change_input),
+                });
+        let esc_rotation =
+            self.esc_rotation_env
+                .as_ref()
+                .map(|(_, project, name)| ui::EscRotationProps {
+                    env: format!("{}/{}", project, name),
+                    rotators: &mut self.esc_rotators,
+                    schedules: &mut self.esc_schedules,
+                    history: &self.esc_rotation_history,
+                    focus: self.esc_rotation_pane,
+                    confirm: self.esc_rotation_confirm.as_deref(),
+                });
+        let esc_revisions = self
+            .esc_revisions_env
+            .is_some()
+            .then(|| ui::EscRevisionsProps {
+                revisions: &mut self.esc_revisions_list,
+                diff_base: self.esc_revision_diff_base,
+                content: self.esc_revision_content.as_deref(),
+                scroll: &mut self.esc_revision_scroll,
+                prompt: self
+                    .esc_revision_prompt
+                    .map(|prompt| (prompt, &self.esc_revision_input)),
+            });
 
         // Platform state
         let platform_view = self.platform_view;
@@ -534,21 +895,10 @@ impl App {
         let commands_output_scroll = &mut self.commands_output_scroll;
         let commands_filter_input = &self.commands_filter_input;
         let commands_is_filtering = self.commands_is_filtering;
+        let commands_show_raw_output = self.commands_show_raw_output;
+        let commands_terminal_size = &mut self.commands_terminal_size;
 
         self.terminal.draw(|frame| {
-            // Get selected task for details dialog (cloned inside closure)
-            let selected_task_for_details: Option<NeoTask> = if show_neo_details {
-                // First try to use the current task if one is loaded
-                if let Some(ref task_id) = state.current_task_id {
-                    state.neo_tasks.iter().find(|t| &t.id == task_id).cloned()
-                } else {
-                    // Fall back to selected task in list
-                    neo_tasks_list.selected().cloned()
-                }
-            } else {
-                None
-            };
-
             // Show splash screen with startup checklist
             if show_splash {
                 ui::render_splash(
@@ -563,6 +913,10 @@ impl App {
 
             let (header_area, content_area, footer_area) = ui::main_layout(frame.area());
 
+            // Approval request Neo is waiting on (if any)
+            let neo_pending_approval =
+                neo_pending_approval_index.and_then(|i| state.neo_messages.get(i));
+
             // Header with tabs
             ui::render_header(frame, theme, header_area, tab, org);
 
@@ -576,8 +930,30 @@ impl App {
                         frame,
                         theme,
                         content_area,
-                        stacks_list,
-                        &state.selected_stack_updates,
+                        ui::StacksViewProps {
+                            stacks: stacks_list,
+                            updates: stack_updates_list,
+                            resources: stack_resources_list,
+                            resource_highlighted: state
+                                .selected_stack_resource_highlighted
+                                .as_ref(),
+                            focused_pane: stacks_pane,
+                            properties_scroll: stack_resource_scroll,
+                        },
+                    );
+                }
+                Tab::Resources => {
+                    ui::render_resources_view(
+                        frame,
+                        theme,
+                        content_area,
+                        ui::ResourcesViewProps {
+                            resources: resources_list,
+                            table_state: resources_table_state,
+                            search: resources_search,
+                            query_input: resources_query_input,
+                            is_editing_query: resources_is_editing_query,
+                        },
                     );
                 }
                 Tab::Esc => {
@@ -592,12 +968,16 @@ impl App {
                                 .selected_env_yaml_highlighted
                                 .as_ref(),
                             selected_env_values: state.selected_env_values.as_ref(),
-                            selected_env_values_highlighted: state
-                                .selected_env_values_highlighted
-                                .as_ref(),
+                            values: esc_values_list,
+                            revealed_values: esc_revealed_values,
+                            value_copied: esc_value_copied,
+                            exported: esc_exported,
                             focused_pane: esc_pane,
                             definition_scroll: esc_definition_scroll,
-                            values_scroll: esc_values_scroll,
+                            revisions: esc_revisions,
+                            graph: esc_graph,
+                            rotation: esc_rotation,
+                            change_requests: esc_changes,
                         },
                     );
                 }
@@ -608,6 +988,10 @@ impl App {
                         content_area,
                         ui::NeoViewProps {
                             tasks: neo_tasks_list,
+                            task_search: neo_tasks_search,
+                            task_query_input: neo_tasks_query_input,
+                            is_editing_task_query: neo_tasks_is_editing_query,
+                            loaded_tasks: state.neo_tasks.len(),
                             messages: &state.neo_messages,
                             input: neo_input,
                             scroll_state: neo_scroll_state,
@@ -622,6 +1006,13 @@ impl App {
                                 all_commands: &state.neo_slash_commands,
                                 pending_commands: neo_pending_commands,
                             },
+                            pending_approval: neo_pending_approval,
+                            render_cache: neo_render_cache,
+                            exported: neo_exported,
+                            export_overwrite: neo_export_overwrite.as_deref(),
+                            attached: neo_attached.as_deref(),
+                            tool_calls: neo_tool_calls,
+                            reveal_tool_call: neo_reveal_tool_call,
                         },
                     );
                 }
@@ -654,6 +1045,8 @@ impl App {
                             output_scroll: commands_output_scroll,
                             filter_input: commands_filter_input,
                             is_filtering: commands_is_filtering,
+                            show_raw_output: commands_show_raw_output,
+                            terminal_size: commands_terminal_size,
                         },
                     );
                 }
@@ -677,13 +1070,73 @@ impl App {
                 ui::render_logs(frame, theme, logger_state);
             }
 
+            // Stack update details popup
+            if show_update_details {
+                if let Some(ref details) = state.selected_update_details {
+                    ui::render_update_details_dialog(
+                        frame,
+                        theme,
+                        details,
+                        &state.selected_update_events,
+                        state.selected_update_events_error.as_deref(),
+                        update_details_scroll,
+                    );
+                }
+            }
+
             // Neo task details popup
             if show_neo_details {
                 if let Some(ref task) = selected_task_for_details {
-                    ui::render_neo_details_dialog(frame, theme, task);
+                    ui::render_neo_details_dialog(
+                        frame,
+                        theme,
+                        task,
+                        neo_share_url.as_deref(),
+                        neo_share_link_copied,
+                    );
                 }
             }
 
+            // Neo approval dialog
+            if show_neo_approval {
+                if let Some(request) = neo_pending_approval {
+                    ui::render_neo_approval_dialog(
+                        frame,
+                        theme,
+                        request,
+                        neo_approval_approve,
+                        neo_approval_comment,
+                    );
+                }
+            }
+
+            // Neo cancel confirmation dialog
+            if let Some(task) = neo_cancel_target {
+                ui::render_neo_cancel_dialog(frame, theme, task);
+            }
+
+            // ESC create / clone / delete environment dialog
+            if let Some(dialog) = esc_env_dialog {
+                ui::render_esc_env_dialog(
+                    frame,
+                    theme,
+                    ui::EscEnvDialogProps {
+                        dialog,
+                        selected: esc_env_dialog_selected,
+                        project_input: esc_env_project_input,
+                        name_input: esc_env_name_input,
+                        focus: esc_env_dialog_focus,
+                        keep_history: esc_clone_keep_history,
+                        keep_tags: esc_clone_keep_tags,
+                    },
+                );
+            }
+
+            // ESC environment variables export dialog
+            if let Some(props) = esc_export {
+                ui::render_esc_export_dialog(frame, theme, props);
+            }
+
             // ESC YAML editor popup
             if show_esc_editor {
                 let env_name = esc_editing_env
@@ -691,6 +1144,10 @@ impl App {
                     .map(|(_, p, n)| format!("{}/{}", p, n))
                     .unwrap_or_else(|| "Unknown".to_string());
                 ui::render_esc_editor(frame, theme, esc_editor, &env_name);
+
+                if let Some(diff) = esc_save_diff {
+                    ui::render_esc_save_dialog(frame, theme, &env_name, diff, esc_save_diff_scroll);
+                }
             }
 
             // Slash commands management dialog
@@ -734,8 +1191,20 @@ impl App {
             return "Press ? or Esc to close help".to_string();
         }
 
+        if self.show_update_details {
+            return "j/k: scroll | J/K: page | g/G: top/bottom | Esc: close".to_string();
+        }
+
         if self.show_neo_details {
-            return "Press d or Esc to close details".to_string();
+            return "s: share/unshare | y: copy link | d/Esc: close details".to_string();
+        }
+
+        if self.neo_cancel_target.is_some() {
+            return "y: cancel task | n/Esc: keep running".to_string();
+        }
+
+        if self.show_neo_approval {
+            return "Tab: approve/reject | type: comment | Enter: send | Esc: cancel".to_string();
         }
 
         if self.show_slash_commands_dialog {
@@ -759,8 +1228,13 @@ impl App {
             };
         }
 
+        if self.esc_save_diff.is_some() {
+            return "y/Enter: save | d: save as draft | n/Esc: back to editor | j/k: scroll | g/G: top/bottom"
+                .to_string();
+        }
+
         if self.show_esc_editor {
-            return "Esc: Save & Close | Ctrl+C: Cancel | Tab: Indent | Ctrl+D: Delete line"
+            return "Esc: Check & Save | Ctrl+C: Cancel | Tab: Indent | Ctrl+D: Delete line"
                 .to_string();
         }
 
@@ -783,24 +1257,90 @@ impl App {
                 Tab::Dashboard => {
                     "Tab: switch | o: org | l: logs | ?: help | r: refresh | q: quit".to_string()
                 }
-                Tab::Stacks => {
-                    "↑↓: navigate | o: org | l: logs | Enter: details | r: refresh | q: quit"
+                Tab::Stacks => match self.stacks_pane {
+                    StacksPane::Stacks => {
+                        "↑↓: navigate | →: updates | Enter: details | p/U/R/D: preview/up/refresh/destroy | N: ask Neo | r: refresh | q: quit"
+                            .to_string()
+                    }
+                    StacksPane::Updates => {
+                        "↑↓: updates | Enter: open update | ←→: panes | N: ask Neo | r: refresh | q: quit"
+                            .to_string()
+                    }
+                    StacksPane::Resources => {
+                        "↑↓: resources | ←→: panes | J/K: scroll properties | N: ask Neo | r: refresh | q: quit"
+                            .to_string()
+                    }
+                    StacksPane::Properties => {
+                        "j/k: scroll | J/K: page | ←: resources | N: ask Neo | r: refresh | q: quit".to_string()
+                    }
+                },
+                Tab::Resources if self.resources_is_editing_query => {
+                    "Enter: search | Esc: cancel".to_string()
+                }
+                Tab::Resources => {
+                    "↑↓: navigate | /: query | s/S: sort column/direction | [/]: page | N: ask Neo | r: refresh | q: quit"
+                        .to_string()
+                }
+                Tab::Esc if self.esc_env_dialog.is_some() => {
+                    "Tab: next field | Space: toggle | Enter: confirm | Esc: cancel".to_string()
+                }
+                Tab::Esc if self.esc_export_confirm => {
+                    "y: write the file | any other key: back".to_string()
+                }
+                Tab::Esc if self.esc_export.is_some() => {
+                    "Tab: format | Enter: export | Esc: cancel".to_string()
+                }
+                Tab::Esc if self.esc_revision_prompt.is_some() => {
+                    "Enter: confirm | Esc: cancel".to_string()
+                }
+                Tab::Esc if self.esc_change_prompt => {
+                    "Enter: submit | Esc: cancel".to_string()
+                }
+                Tab::Esc if self.esc_rotation_confirm.is_some() => {
+                    "y: rotate | any other key: cancel".to_string()
+                }
+                Tab::Esc if self.esc_change_confirm.is_some() => format!(
+                    "y: {} | any other key: cancel",
+                    self.esc_change_confirm.map(|a| a.verb()).unwrap_or_default()
+                ),
+                Tab::Esc if self.esc_change_requests_org.is_some() => {
+                    "↑↓: requests | j/k: scroll | v: diff/values | s: submit | A: approve | a: apply | X: close request | Esc: close"
+                        .to_string()
+                }
+                Tab::Esc if self.esc_rotation_env.is_some() => {
+                    "↑↓: navigate | ←→: rotators/schedules | Enter: rotate | a: rotate all | p: pause/resume | Esc: close"
+                        .to_string()
+                }
+                Tab::Esc if self.esc_graph_env.is_some() => {
+                    "↑↓: navigate | Enter: graph of selected env | Esc: close".to_string()
+                }
+                Tab::Esc if self.esc_revisions_env.is_some() => {
+                    "↑↓: revisions | j/k: scroll | d: diff base | t: tag | R: roll back | X: retract | Esc: close"
                         .to_string()
                 }
                 Tab::Esc => {
-                    "↑↓: envs | ←→: panes | j/k: scroll | Enter: load | o: resolve | e: edit | q: quit"
+                    "↑↓: envs | ←→: panes | j/k: scroll | Enter: load | o: resolve | e: edit | v: revisions | i: imports | R: rotation | C: change requests | x: export | !: run | N: ask Neo | n/c/D: new/clone/delete | q: quit"
                         .to_string()
                 }
+                Tab::Neo if self.neo_tasks_is_editing_query => {
+                    "Enter: done | Esc: clear filter".to_string()
+                }
+                Tab::Neo if self.neo_export_confirm.is_some() => {
+                    "y: overwrite the file | any other key: back".to_string()
+                }
                 Tab::Neo => {
-                    if self.neo_hide_task_list {
-                        "j/k: scroll | /: commands | c: cmds | d: details | n: new | i: type | Esc: tasks | q: quit"
+                    if self.pending_approval_index().is_some() {
+                        "a: approve | x: reject | X: cancel | j/k: scroll | t/T: tool calls | d: details | Esc: tasks | q: quit"
+                            .to_string()
+                    } else if self.neo_hide_task_list {
+                        "j/k: scroll | t/T: tool calls | Space: expand | /: commands | c: cmds | d: details | e/E: export md/json | X: cancel | n: new | i: type | Esc: tasks | q: quit"
                             .to_string()
                     } else {
-                        "↑↓: tasks | Enter: select | /: commands | c: cmds | n: new | i: type | q: quit".to_string()
+                        "↑↓: tasks | Enter: select | f: filter | s: status | m: mine/all | S: sort | /: commands | c: cmds | X: cancel | n: new | i: type | q: quit".to_string()
                     }
                 }
                 Tab::Platform => {
-                    "↑↓: navigate | ←→: switch view | o: org | l: logs | r: refresh | q: quit"
+                    "↑↓: navigate | ←→: switch view | N: ask Neo | o: org | l: logs | r: refresh | q: quit"
                         .to_string()
                 }
                 Tab::Commands => {
@@ -817,8 +1357,20 @@ impl App {
                         ui::CommandsViewState::ConfirmDialog => {
                             "y: confirm | n/Esc: cancel".to_string()
                         }
+                        ui::CommandsViewState::OutputView if self.is_command_terminal_active() => {
+                            "Keys go to the running command | Ctrl+C: cancel".to_string()
+                        }
                         ui::CommandsViewState::OutputView => {
-                            "j/k: scroll | Esc: close | q: quit".to_string()
+                            let exec = self.current_command_execution.as_ref();
+                            let mut hint = String::from("j/k: scroll");
+                            if exec.is_some_and(|e| e.state == CommandExecutionState::Running) {
+                                hint.push_str(" | X: cancel");
+                            }
+                            if exec.is_some_and(|e| e.engine.is_some()) {
+                                hint.push_str(" | v: raw/resources");
+                            }
+                            hint.push_str(" | Esc: close | q: quit");
+                            hint
                         }
                     }
                 }
@@ -831,9 +1383,37 @@ impl App {
         use crate::commands::update_execution_state;
 
         while let Ok(result) = self.command_result_rx.try_recv() {
+            // Release the PTY once the command has finished
+            if matches!(
+                result,
+                CommandResult::Completed { .. } | CommandResult::Failed(_)
+            ) {
+                self.commands_input = None;
+            }
             if let Some(ref mut execution) = self.current_command_execution {
                 update_execution_state(execution, result);
             }
         }
     }
+
+    /// Resize the running command's PTY and terminal emulator to the rendered output pane
+    fn sync_command_terminal_size(&mut self) {
+        let Some(input) = self.commands_input.as_ref() else {
+            return;
+        };
+        let (rows, cols) = self.commands_terminal_size;
+        if rows == 0 || cols == 0 {
+            return;
+        }
+        input.resize(rows, cols);
+        if let Some(terminal) = self
+            .current_command_execution
+            .as_mut()
+            .and_then(|exec| exec.terminal.as_mut())
+        {
+            if terminal.size() != (rows, cols) {
+                terminal.resize(rows, cols);
+            }
+        }
+    }
 }
@@ -5,11 +5,45 @@
 
 use std::sync::atomic::Ordering;
 
-use crate::api::{NeoMessage, NeoMessageType, NeoTask};
+use crate::api::{
+    NeoEntity, NeoEventCursor, NeoMessage, NeoMessageType, NeoTask, NeoTaskUser,
+    NeoUpdateTaskRequest,
+};
 
-use super::types::NeoAsyncResult;
+use super::types::{NeoApprovalState, NeoAsyncResult};
 use super::App;
 
+/// Whether a Neo task status means the agent is still working
+fn is_running_status(status: &str) -> bool {
+    let s_lower = status.to_lowercase();
+    s_lower == "running" || s_lower == "in_progress" || s_lower == "pending"
+}
+
+/// Merge newly fetched messages into the conversation.
+///
+/// The last `optimistic` messages are user messages we echoed locally before the
+/// API confirmed them. Incoming messages are inserted ahead of those echoes, and
+/// an incoming user message replaces the oldest echo, so nothing shows up twice.
+/// Returns true if anything changed.
+fn merge_new_messages(
+    messages: &mut Vec<NeoMessage>,
+    optimistic: &mut usize,
+    incoming: Vec<NeoMessage>,
+) -> bool {
+    let changed = !incoming.is_empty();
+    for message in incoming {
+        *optimistic = (*optimistic).min(messages.len());
+        let tail_start = messages.len() - *optimistic;
+        if *optimistic > 0 && message.message_type == NeoMessageType::UserMessage {
+            messages[tail_start] = message;
+            *optimistic -= 1;
+        } else {
+            messages.insert(tail_start, message);
+        }
+    }
+    changed
+}
+
 impl App {
     /// Process any pending async Neo results
     pub(super) fn process_neo_results(&mut self) {
@@ -36,13 +70,17 @@ impl App {
                             .unwrap_or_else(|| "New task".to_string());
 
                         let new_task = NeoTask {
-                            id: task_id,
+                            id: task_id.clone(),
                             name: Some(msg_preview),
                             status: Some("running".to_string()),
                             created_at: Some(chrono::Utc::now().to_rfc3339()),
                             updated_at: None,
                             url: None,
-                            started_by: None,
+                            started_by: self.state.user_login.clone().map(|login| NeoTaskUser {
+                                name: None,
+                                login: Some(login),
+                                avatar_url: None,
+                            }),
                             is_shared: Some(false),
                             shared_at: None,
                             linked_prs: Vec::new(),
@@ -50,144 +88,186 @@ impl App {
                             policies: Vec::new(),
                         };
                         self.state.neo_tasks.insert(0, new_task);
-                        self.neo_tasks_list.set_items(self.state.neo_tasks.clone());
-                        self.neo_tasks_list.select(Some(0));
+                        self.refresh_neo_tasks_list();
+                        self.select_neo_task(&task_id);
                     }
                     // Start polling for updates
                     self.neo_polling = true;
-                    self.neo_poll_counter = 5; // Trigger immediate poll on next tick
+                    self.neo_poll_backoff.reset();
+                    self.neo_poll_backoff.trigger(); // Poll on the next tick
                 }
                 NeoAsyncResult::EventsReceived {
+                    task_id,
+                    since,
                     messages,
-                    has_more: _,
+                    cursor,
                     task_status,
                 } => {
-                    let current_count = messages.len();
-
-                    // Only update if we got messages from the API
-                    if !messages.is_empty() {
-                        // Check if this is actually new content
-                        let has_new_content = current_count != self.state.neo_messages.len()
-                            || messages.iter().any(|m| {
-                                !self.state.neo_messages.iter().any(|existing| {
-                                    existing.content == m.content
-                                        && existing.message_type == m.message_type
-                                })
-                            });
+                    self.neo_poll_in_flight = false;
 
-                        if has_new_content {
-                            self.state.neo_messages = messages;
-                            // Auto-scroll is handled by the render function
-                            // Reset stable counter since we got new content
-                            self.neo_stable_polls = 0;
-                        } else {
-                            self.neo_stable_polls += 1;
-                        }
+                    // Drop results for a task we've navigated away from, or that were
+                    // fetched from a position the conversation has already moved past
+                    if self.state.current_task_id.as_deref() != Some(task_id.as_str())
+                        || since != self.neo_event_cursor
+                    {
+                        continue;
+                    }
+                    self.neo_event_cursor = cursor;
+
+                    if merge_new_messages(
+                        &mut self.state.neo_messages,
+                        &mut self.neo_optimistic_messages,
+                        messages,
+                    ) {
+                        // New content: poll quickly again in case more is on the way
+                        self.neo_render_cache.invalidate();
+                        self.neo_stable_polls = 0;
+                        self.neo_poll_backoff.reset();
                     } else {
-                        self.neo_stable_polls += 1;
+                        self.neo_stable_polls = self.neo_stable_polls.saturating_add(1);
+                        self.neo_poll_backoff.back_off();
                     }
 
-                    // Increment poll count
-                    self.neo_current_poll += 1;
-                    self.neo_prev_message_count = current_count;
-
                     // Check task status - is NEO still working?
                     let task_is_running = task_status
-                        .as_ref()
-                        .map(|s| {
-                            let s_lower = s.to_lowercase();
-                            s_lower == "running" || s_lower == "in_progress" || s_lower == "pending"
-                        })
+                        .as_deref()
+                        .map(is_running_status)
                         .unwrap_or(false);
 
                     // Update the task running state - this keeps the thinking indicator visible
                     // until we confirm the task is no longer running
                     self.neo_task_is_running = task_is_running;
 
-                    // Check for assistant response
-                    let has_assistant_response = self.state.neo_messages.iter().any(|m| {
-                        m.message_type == NeoMessageType::AssistantMessage && !m.content.is_empty()
-                    });
+                    // Check for an assistant reply to the latest user message
+                    let has_assistant_response = self
+                        .state
+                        .neo_messages
+                        .iter()
+                        .rev()
+                        .take_while(|m| m.message_type != NeoMessageType::UserMessage)
+                        .any(|m| {
+                            m.message_type == NeoMessageType::AssistantMessage
+                                && !m.content.is_empty()
+                        });
 
                     // Stop polling if:
                     // 1. Task status is NOT running/in_progress (i.e., idle, completed, failed)
-                    //    AND we have at least one assistant message
-                    // 2. OR we've hit max polls (safety timeout)
-                    // 3. OR stable polls exceeded AND task is not running (fallback for API issues)
-                    let should_stop = (!task_is_running && has_assistant_response)
-                        || self.neo_current_poll >= self.neo_max_polls
-                        || (self.neo_stable_polls >= 20 && !task_is_running);
+                    //    AND Neo has replied to the latest user message
+                    // 2. OR stable polls exceeded AND task is not running (fallback for API issues)
+                    // While the task is running we keep polling, backing off to the max interval.
+                    let should_stop =
+                        !task_is_running && (has_assistant_response || self.neo_stable_polls >= 6);
 
                     log::debug!(
-                        "Neo poll: status={:?}, running={}, stable={}, poll={}/{}, stop={}",
+                        "Neo poll: status={:?}, running={}, stable={}, interval={}, stop={}",
                         task_status,
                         task_is_running,
                         self.neo_stable_polls,
-                        self.neo_current_poll,
-                        self.neo_max_polls,
+                        self.neo_poll_backoff.interval(),
                         should_stop
                     );
 
-                    if should_stop {
+                    if should_stop && self.neo_polling {
                         self.neo_polling = false;
                         self.is_loading = false;
-                        // Reset poll counters
                         self.neo_stable_polls = 0;
-                        self.neo_prev_message_count = 0;
-                        self.neo_current_poll = 0;
                         // Note: neo_task_is_running is already set above based on task_status
                         // so the thinking indicator will stay visible if task is still running
                     }
                 }
+                NeoAsyncResult::PollFailed => {
+                    // Transient failure: try again later rather than giving up
+                    self.neo_poll_in_flight = false;
+                    self.neo_poll_backoff.back_off();
+                }
+                NeoAsyncResult::ApprovalSent {
+                    task_id,
+                    index,
+                    result,
+                } => {
+                    if self.state.current_task_id.as_deref() != Some(task_id.as_str()) {
+                        continue;
+                    }
+                    self.neo_approval.finish_sending(index, result.is_ok());
+                    match result {
+                        // Resume polling for Neo's follow-up
+                        Ok(()) => self.start_neo_polling(),
+                        Err(e) => {
+                            log::error!("Failed to send Neo approval: {}", e);
+                            self.error = Some(format!("Failed to send approval: {}", e));
+                        }
+                    }
+                }
                 NeoAsyncResult::Error(e) => {
                     self.error = Some(format!("Neo error: {}", e));
                     self.neo_polling = false;
                     self.is_loading = false;
                     self.neo_task_is_running = false;
-                    // Reset poll counters
                     self.neo_stable_polls = 0;
-                    self.neo_prev_message_count = 0;
-                    self.neo_current_poll = 0;
                 }
             }
         }
     }
 
-    /// Spawn async task to poll Neo events and task status
-    pub(super) fn spawn_neo_poll(&self) {
+    /// Start fast polling for Neo's response to something we just sent
+    fn start_neo_polling(&mut self) {
+        self.neo_polling = true;
+        self.neo_poll_backoff.reset();
+        self.neo_stable_polls = 0;
+        // Mark task as running - will be updated by polling
+        self.neo_task_is_running = true;
+        // Enable auto-scroll - render function will handle positioning
+        self.neo_auto_scroll.store(true, Ordering::Relaxed);
+    }
+
+    /// Forget the event stream position, e.g. when switching to another task
+    pub(super) fn reset_neo_event_stream(&mut self) {
+        self.neo_event_cursor = NeoEventCursor::default();
+        self.neo_optimistic_messages = 0;
+        self.neo_stable_polls = 0;
+        self.neo_poll_backoff.reset();
+    }
+
+    /// Spawn async task to fetch new Neo events and the task status.
+    /// Only one poll is in flight at a time; it resumes from the stored cursor.
+    pub(super) fn spawn_neo_poll(&mut self) {
+        if self.neo_poll_in_flight {
+            return;
+        }
         if let (Some(task_id), Some(org)) = (&self.state.current_task_id, &self.state.organization)
         {
             if let Some(ref client) = self.client {
                 let client = client.clone();
                 let task_id = task_id.clone();
                 let org = org.clone();
+                let since = self.neo_event_cursor.clone();
                 let tx = self.neo_result_tx.clone();
+                self.neo_poll_in_flight = true;
 
                 tokio::spawn(async move {
-                    // Fetch both events and task status in parallel
+                    // Fetch new events and task status in parallel
                     let (events_result, task_result) = tokio::join!(
-                        client.get_neo_task_events(&org, &task_id),
+                        client.get_neo_task_events_since(&org, &task_id, &since),
                         client.get_neo_task(&org, &task_id)
                     );
 
                     // Extract task status (if available)
                     let task_status = task_result.ok().and_then(|task| task.status);
 
-                    match events_result {
-                        Ok(response) => {
-                            let _ = tx
-                                .send(NeoAsyncResult::EventsReceived {
-                                    messages: response.messages,
-                                    has_more: response.has_more,
-                                    task_status,
-                                })
-                                .await;
-                        }
+                    let result = match events_result {
+                        Ok(page) => NeoAsyncResult::EventsReceived {
+                            task_id,
+                            since,
+                            messages: page.messages,
+                            cursor: page.cursor,
+                            task_status,
+                        },
                         Err(e) => {
                             log::warn!("Failed to poll Neo task: {}", e);
-                            // Don't send error for transient poll failures
+                            NeoAsyncResult::PollFailed
                         }
-                    }
+                    };
+                    let _ = tx.send(result).await;
                 });
             }
         }
@@ -203,8 +283,14 @@ impl App {
 
         // Take pending commands (they'll be sent with this message)
         let pending_commands = std::mem::take(&mut self.neo_pending_commands);
+        // An entity from "Ask Neo about this" is attached to the task it starts
+        let entities: Vec<NeoEntity> = if self.state.current_task_id.is_none() {
+            self.neo_attached_entity.take().into_iter().collect()
+        } else {
+            Vec::new()
+        };
 
-        // Add user message to chat immediately
+        // Add user message to chat immediately (replaced once the API echoes it back)
         self.state.neo_messages.push(NeoMessage {
             role: "user".to_string(),
             content: message.clone(),
@@ -212,7 +298,10 @@ impl App {
             timestamp: None,
             tool_calls: vec![],
             tool_name: None,
+            tool_call_id: None,
+            approval_request_id: None,
         });
+        self.neo_optimistic_messages += 1;
 
         // Auto-scroll is handled by the render function
 
@@ -250,11 +339,16 @@ impl App {
                     } else if !pending_commands.is_empty() {
                         // Create new task with slash commands
                         client
-                            .create_neo_task_with_commands(&org, &message, &pending_commands)
+                            .create_neo_task_with_commands(
+                                &org,
+                                &message,
+                                &pending_commands,
+                                &entities,
+                            )
                             .await
                     } else {
                         // Create new task (plain message)
-                        client.create_neo_task(&org, &message).await
+                        client.create_neo_task(&org, &message, &entities).await
                     };
 
                     match result {
@@ -273,24 +367,175 @@ impl App {
                 });
 
                 // Start polling immediately (will pick up results)
-                self.neo_polling = true;
-                self.neo_poll_counter = 0;
-                self.neo_stable_polls = 0;
-                self.neo_prev_message_count = self.state.neo_messages.len();
-                self.neo_current_poll = 0;
-                // Mark task as running - will be updated by polling
-                self.neo_task_is_running = true;
-                // Enable auto-scroll - render function will handle positioning
-                self.neo_auto_scroll.store(true, Ordering::Relaxed);
+                self.start_neo_polling();
+            }
+        }
+    }
+
+    /// Index of the approval request Neo is currently waiting on, if any.
+    /// An approval is pending while it is the latest message and we haven't answered it yet.
+    pub(super) fn pending_approval_index(&self) -> Option<usize> {
+        self.neo_approval.pending(&self.state.neo_messages)
+    }
+
+    /// Open the approval dialog with the given initial decision
+    pub(super) fn open_approval_dialog(&mut self, approve: bool) {
+        if self.pending_approval_index().is_none() {
+            return;
+        }
+        self.neo_approval_approve = approve;
+        self.neo_approval_comment.clear();
+        self.neo_approval_comment.set_focused(true);
+        self.show_neo_approval = true;
+    }
+
+    /// Send the approval decision to Neo (non-blocking) and resume polling
+    pub(super) fn submit_neo_approval(&mut self) {
+        let Some(index) = self.pending_approval_index() else {
+            self.show_neo_approval = false;
+            return;
+        };
+
+        let approved = self.neo_approval_approve;
+        let comment = self.neo_approval_comment.take();
+        self.neo_approval_comment.set_focused(false);
+        self.show_neo_approval = false;
+
+        // The confirmation names the request it answers
+        let Some(approval_request_id) = self.state.neo_messages[index].approval_request_id.clone()
+        else {
+            log::warn!("Neo approval request has no ID");
+            self.error = Some("The approval request has no ID to answer".to_string());
+            return;
+        };
+
+        if let (Some(client), Some(org), Some(task_id)) = (
+            &self.client,
+            &self.state.organization,
+            &self.state.current_task_id,
+        ) {
+            let client = client.clone();
+            let org = org.clone();
+            let task_id = task_id.clone();
+            let tx = self.neo_result_tx.clone();
+
+            log::info!(
+                "Neo approval for task {}: {}",
+                task_id,
+                if approved { "approved" } else { "rejected" }
+            );
+
+            tokio::spawn(async move {
+                let comment = Some(comment.as_str()).filter(|c| !c.trim().is_empty());
+                let result = client
+                    .confirm_neo_task(&org, &task_id, &approval_request_id, approved, comment)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+                let _ = tx
+                    .send(NeoAsyncResult::ApprovalSent {
+                        task_id,
+                        index,
+                        result,
+                    })
+                    .await;
+            });
+
+            // Hide the prompt while the decision is sent; it is marked answered once accepted
+            self.neo_approval.start_sending(index);
+        }
+    }
+
+    /// Ask for confirmation before cancelling a running task.
+    /// In full-width chat this targets the current task, otherwise the selected task in the list.
+    pub(super) fn open_cancel_confirm(&mut self) {
+        let task = if self.neo_hide_task_list {
+            self.state
+                .current_task_id
+                .as_ref()
+                .and_then(|id| self.state.neo_tasks.iter().find(|t| &t.id == id).cloned())
+        } else {
+            self.neo_tasks_list.selected().cloned()
+        };
+
+        let Some(task) = task else {
+            return;
+        };
+
+        let is_current = self.state.current_task_id.as_deref() == Some(task.id.as_str());
+        let is_running = task
+            .status
+            .as_deref()
+            .map(is_running_status)
+            .unwrap_or(false)
+            || (is_current && (self.neo_polling || self.neo_task_is_running));
+
+        if is_running {
+            self.neo_cancel_target = Some(task);
+        } else {
+            self.error = Some("Task is not running".to_string());
+        }
+    }
+
+    /// Cancel the task awaiting confirmation, stop polling and refresh its status
+    pub(super) async fn cancel_neo_task(&mut self) {
+        let Some(task) = self.neo_cancel_target.take() else {
+            return;
+        };
+
+        let (Some(client), Some(org)) = (&self.client, &self.state.organization) else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Cancelling task...");
+
+        if let Err(e) = client.cancel_neo_task(org, &task.id).await {
+            log::error!("Failed to cancel Neo task: {}", e);
+            self.error = Some(format!("Failed to cancel task: {}", e));
+            self.is_loading = false;
+            return;
+        }
+        log::info!("Cancelled Neo task: {}", task.id);
+
+        // Stop polling if this is the task we're following
+        if self.state.current_task_id.as_deref() == Some(task.id.as_str()) {
+            self.neo_polling = false;
+            self.neo_task_is_running = false;
+            self.neo_stable_polls = 0;
+        }
+
+        // Refresh the task status (the API may still report running briefly)
+        let refreshed = client.get_neo_task(org, &task.id).await.ok();
+        if let Some(local_task) = self.state.neo_tasks.iter_mut().find(|t| t.id == task.id) {
+            match refreshed {
+                Some(updated)
+                    if !updated
+                        .status
+                        .as_deref()
+                        .map(is_running_status)
+                        .unwrap_or(false) =>
+                {
+                    *local_task = updated;
+                }
+                _ => local_task.status = Some("cancelled".to_string()),
             }
         }
+        self.refresh_neo_tasks_list();
+
+        self.is_loading = false;
     }
 
     /// Load selected Neo task
     pub(super) async fn load_selected_task(&mut self) {
-        if let Some(task) = self.neo_tasks_list.selected() {
+        if let Some(task) = self.neo_tasks_list.selected().cloned() {
             self.state.current_task_id = Some(task.id.clone());
             self.state.neo_messages.clear();
+            self.neo_approval = NeoApprovalState::default();
+            self.neo_exported = None;
+            self.neo_attached_entity = None;
+            self.clear_neo_tool_calls();
+            self.reset_neo_event_stream();
             self.neo_scroll_state = tui_scrollview::ScrollViewState::default();
             self.neo_auto_scroll.store(true, Ordering::Relaxed);
             // Reset background poll counter to start fresh polling cycle
@@ -303,9 +548,18 @@ impl App {
                 if let Some(org) = &self.state.organization {
                     self.is_loading = true;
 
-                    if let Ok(response) = client.continue_neo_task(org, &task.id, None).await {
-                        self.state.neo_messages = response.messages;
-                        // Auto-scroll is handled by the render function
+                    match client
+                        .get_neo_task_events_since(org, &task.id, &self.neo_event_cursor)
+                        .await
+                    {
+                        Ok(page) => {
+                            self.state.neo_messages = page.messages;
+                            self.neo_render_cache.invalidate();
+                            // Later polls only fetch what comes after this point
+                            self.neo_event_cursor = page.cursor;
+                            // Auto-scroll is handled by the render function
+                        }
+                        Err(e) => log::warn!("Failed to load Neo task events: {}", e),
                     }
 
                     self.is_loading = false;
@@ -393,6 +647,162 @@ impl App {
         }
     }
 
+    /// IDs of the tool calls in the loaded conversation, in order
+    fn neo_tool_call_ids(&self) -> Vec<String> {
+        self.state
+            .neo_messages
+            .iter()
+            .flat_map(|msg| &msg.tool_calls)
+            .filter(|call| !call.id.is_empty())
+            .map(|call| call.id.clone())
+            .collect()
+    }
+
+    /// Select the next (or previous) tool call and scroll it into view.
+    /// With none selected, `t` starts from the first call and `T` from the last.
+    pub(super) fn select_neo_tool_call(&mut self, forward: bool) {
+        let ids = self.neo_tool_call_ids();
+        if ids.is_empty() {
+            return;
+        }
+        let current = self
+            .neo_selected_tool_call
+            .as_ref()
+            .and_then(|id| ids.iter().position(|i| i == id));
+        let index = match (current, forward) {
+            (Some(i), true) => (i + 1).min(ids.len() - 1),
+            (Some(i), false) => i.saturating_sub(1),
+            (None, true) => 0,
+            (None, false) => ids.len() - 1,
+        };
+        self.neo_selected_tool_call = Some(ids[index].clone());
+        self.neo_render_cache.invalidate();
+        self.neo_reveal_tool_call = true;
+        self.neo_auto_scroll.store(false, Ordering::Relaxed);
+    }
+
+    /// Expand or collapse the selected tool call
+    pub(super) fn toggle_neo_tool_call(&mut self) {
+        let Some(id) = self.neo_selected_tool_call.clone() else {
+            return;
+        };
+        if !self.neo_expanded_tool_calls.remove(&id) {
+            self.neo_expanded_tool_calls.insert(id);
+        }
+        self.neo_render_cache.invalidate();
+    }
+
+    /// Clear the chat for a new conversation and focus the input
+    pub(super) fn start_new_neo_task(&mut self) {
+        self.state.neo_messages.clear();
+        self.state.current_task_id = None;
+        self.neo_approval = NeoApprovalState::default();
+        self.neo_exported = None;
+        self.neo_attached_entity = None;
+        self.clear_neo_tool_calls();
+        self.reset_neo_event_stream();
+        self.neo_scroll_state = tui_scrollview::ScrollViewState::default();
+        self.neo_auto_scroll.store(true, Ordering::Relaxed);
+        self.neo_hide_task_list = true; // Hide task list for new conversation
+        self.focus = super::types::FocusMode::Input;
+        self.neo_input.set_focused(true);
+    }
+
+    /// Forget expanded and selected tool calls, when another conversation is shown
+    pub(super) fn clear_neo_tool_calls(&mut self) {
+        self.neo_expanded_tool_calls.clear();
+        self.neo_selected_tool_call = None;
+        self.neo_reveal_tool_call = false;
+        self.neo_render_cache.invalidate();
+    }
+
+    /// Task shown in the details dialog: the loaded task, falling back to the list selection
+    pub(super) fn details_task(&self) -> Option<&NeoTask> {
+        if let Some(ref task_id) = self.state.current_task_id {
+            self.state.neo_tasks.iter().find(|t| &t.id == task_id)
+        } else {
+            self.neo_tasks_list.selected()
+        }
+    }
+
+    /// Console link for a task, only available once the task is shared
+    pub(super) fn neo_task_share_url(&self, task: &NeoTask) -> Option<String> {
+        if !task.is_shared.unwrap_or(false) {
+            return None;
+        }
+        task.url.clone().or_else(|| {
+            let client = self.client.as_ref()?;
+            let org = self.state.organization.as_deref()?;
+            Some(client.neo_task_url(org, &task.id))
+        })
+    }
+
+    /// Share or unshare the task shown in the details dialog.
+    /// Sharing also copies the link to the clipboard.
+    pub(super) async fn toggle_neo_task_sharing(&mut self) {
+        let Some(task) = self.details_task().cloned() else {
+            return;
+        };
+        let (Some(client), Some(org)) = (&self.client, &self.state.organization) else {
+            return;
+        };
+
+        let share = !task.is_shared.unwrap_or(false);
+        let request = NeoUpdateTaskRequest {
+            is_shared: Some(share),
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message(if share {
+            "Sharing task..."
+        } else {
+            "Unsharing task..."
+        });
+
+        match client.update_neo_task(org, &task.id, &request).await {
+            Ok(updated) => {
+                log::info!(
+                    "Neo task {} is now {}",
+                    task.id,
+                    if share { "shared" } else { "private" }
+                );
+                if let Some(local_task) = self.state.neo_tasks.iter_mut().find(|t| t.id == task.id)
+                {
+                    *local_task = updated;
+                }
+                self.refresh_neo_tasks_list();
+                self.neo_share_link_copied = false;
+                if share {
+                    self.copy_neo_task_link();
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to update Neo task sharing: {}", e);
+                self.error = Some(format!("Failed to update sharing: {}", e));
+            }
+        }
+
+        self.is_loading = false;
+    }
+
+    /// Copy the share link of the task shown in the details dialog (OSC 52)
+    pub(super) fn copy_neo_task_link(&mut self) {
+        let Some(url) = self
+            .details_task()
+            .and_then(|task| self.neo_task_share_url(task))
+        else {
+            return;
+        };
+
+        match crate::clipboard::copy_to_clipboard(&url) {
+            Ok(()) => self.neo_share_link_copied = true,
+            Err(e) => {
+                log::warn!("Failed to copy link to clipboard: {}", e);
+                self.error = Some(format!("Failed to copy link: {}", e));
+            }
+        }
+    }
+
     /// Refresh current task details from the API
     pub(super) async fn refresh_current_task_details(&mut self) {
         let task_id = match &self.state.current_task_id {
@@ -411,9 +821,82 @@ impl App {
                         *local_task = updated_task.clone();
                     }
                     // Also update the tasks list
-                    self.neo_tasks_list.set_items(self.state.neo_tasks.clone());
+                    self.refresh_neo_tasks_list();
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(message_type: NeoMessageType, content: &str) -> NeoMessage {
+        NeoMessage {
+            role: String::new(),
+            content: content.to_string(),
+            message_type,
+            timestamp: None,
+            tool_calls: vec![],
+            tool_name: None,
+            tool_call_id: None,
+            approval_request_id: None,
+        }
+    }
+
+    fn contents(messages: &[NeoMessage]) -> Vec<&str> {
+        messages.iter().map(|m| m.content.as_str()).collect()
+    }
+
+    #[test]
+    fn merge_new_messages_appends_and_reports_changes() {
+        let mut messages = vec![message(NeoMessageType::UserMessage, "hi")];
+        let mut optimistic = 0;
+
+        assert!(!merge_new_messages(&mut messages, &mut optimistic, vec![]));
+        assert!(merge_new_messages(
+            &mut messages,
+            &mut optimistic,
+            vec![message(NeoMessageType::AssistantMessage, "hello")]
+        ));
+        assert_eq!(contents(&messages), vec!["hi", "hello"]);
+    }
+
+    #[test]
+    fn merge_new_messages_replaces_local_echo() {
+        let mut messages = vec![
+            message(NeoMessageType::UserMessage, "first"),
+            message(NeoMessageType::AssistantMessage, "reply"),
+            message(NeoMessageType::UserMessage, "second"),
+        ];
+        let mut optimistic = 1;
+
+        // Agent output that raced ahead of the echo goes before it
+        merge_new_messages(
+            &mut messages,
+            &mut optimistic,
+            vec![message(NeoMessageType::ToolCall, "Executing: search")],
+        );
+        assert_eq!(
+            contents(&messages),
+            vec!["first", "reply", "Executing: search", "second"]
+        );
+        assert_eq!(optimistic, 1);
+
+        // The API's copy of the user message replaces the echo instead of duplicating it
+        merge_new_messages(
+            &mut messages,
+            &mut optimistic,
+            vec![
+                message(NeoMessageType::UserMessage, "second"),
+                message(NeoMessageType::AssistantMessage, "answer"),
+            ],
+        );
+        assert_eq!(
+            contents(&messages),
+            vec!["first", "reply", "Executing: search", "second", "answer"]
+        );
+        assert_eq!(optimistic, 0);
+    }
+}
@@ -0,0 +1,215 @@
+//! Ask Neo about a selection
+//!
+//! This module starts a Neo conversation from the Stacks, Resources, ESC and
+//! Platform views: the selected stack or repository is attached to the task
+//! and a prompt about the selection (e.g. why an update failed) is put in the
+//! input to edit and send.
+
+use crate::api::{NeoEntity, StackUpdate};
+
+use super::types::{PlatformView, StacksPane, Tab};
+use super::App;
+
+/// What an entity is called: `project/name` for stacks,
+/// `org/name` for repositories, else its name or ID
+pub(super) fn entity_label(entity: &NeoEntity) -> String {
+    let name = entity.name.as_ref().or(entity.stack.as_ref());
+    match (entity.project.as_ref().or(entity.org.as_ref()), name) {
+        (Some(scope), Some(name)) => format!("{}/{}", scope, name),
+        (None, Some(name)) => name.clone(),
+        _ => entity.id.clone().unwrap_or_else(|| "Unknown".to_string()),
+    }
+}
+
+fn stack_entity(project: &str, stack: &str) -> NeoEntity {
+    NeoEntity {
+        entity_type: Some("stack".to_string()),
+        name: Some(stack.to_string()),
+        project: Some(project.to_string()),
+        ..NeoEntity::default()
+    }
+}
+
+/// A repository entity for a GitHub or GitLab repository URL
+fn repository_entity(url: &str) -> Option<NeoEntity> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let mut parts = rest.trim_end_matches('/').split('/');
+    let forge = match parts.next()? {
+        "github.com" => "github",
+        "gitlab.com" => "gitlab",
+        _ => return None,
+    };
+    let org = parts.next().filter(|p| !p.is_empty())?;
+    let name = parts.next().filter(|p| !p.is_empty())?;
+    Some(NeoEntity {
+        entity_type: Some("repository".to_string()),
+        name: Some(name.trim_end_matches(".git").to_string()),
+        org: Some(org.to_string()),
+        forge: Some(forge.to_string()),
+        url: Some(url.to_string()),
+        ..NeoEntity::default()
+    })
+}
+
+/// Prompt about a stack, or about one of its updates
+fn stack_prompt(update: Option<&StackUpdate>) -> String {
+    match update {
+        Some(update) if update.result.as_deref() == Some("failed") => {
+            format!("Why did update {} of this stack fail?", update.version)
+        }
+        Some(update) => format!(
+            "Summarize what update {} of this stack changed.",
+            update.version
+        ),
+        None => {
+            "What is the current state of this stack, and does anything need attention?".to_string()
+        }
+    }
+}
+
+impl App {
+    /// The entity and prompt for the selection in the current view
+    fn neo_context(&self) -> Option<(Option<NeoEntity>, String)> {
+        match self.tab {
+            Tab::Stacks => {
+                let stack = self.stacks_list.selected()?;
+                let entity = stack_entity(&stack.project_name, &stack.stack_name);
+                let prompt = match self.stacks_pane {
+                    StacksPane::Stacks => stack_prompt(None),
+                    StacksPane::Updates => stack_prompt(self.stack_updates_list.selected()),
+                    StacksPane::Resources | StacksPane::Properties => {
+                        match self.stack_resources_list.selected() {
+                            Some(row) => format!(
+                                "Explain the {} resource {} in this stack, and whether anything about it needs attention.",
+                                row.resource.resource_type, row.resource.urn
+                            ),
+                            None => stack_prompt(None),
+                        }
+                    }
+                };
+                Some((Some(entity), prompt))
+            }
+            Tab::Resources => {
+                let resource = self.resources_list.selected()?;
+                let entity = resource
+                    .project
+                    .as_deref()
+                    .zip(resource.stack.as_deref())
+                    .map(|(project, stack)| stack_entity(project, stack));
+                let prompt = format!(
+                    "Explain the {} resource {}, and whether anything about it needs attention.",
+                    resource.resource_type,
+                    resource.urn.as_deref().unwrap_or(&resource.name)
+                );
+                Some((entity, prompt))
+            }
+            Tab::Esc => {
+                // Neo has no entity type for environments, so the prompt names it
+                let (_, project, name) = self.selected_esc_env()?;
+                let prompt = format!(
+                    "Explain what the {}/{} environment configures, what it imports and what uses it.",
+                    project, name
+                );
+                Some((None, prompt))
+            }
+            Tab::Platform => match self.platform_view {
+                PlatformView::Services => {
+                    let service = self.services_list.selected()?;
+                    let prompt = format!(
+                        "Tell me about the {} service: which stacks and environments belong to it, and are they healthy?",
+                        service.name
+                    );
+                    Some((None, prompt))
+                }
+                PlatformView::Components => {
+                    let package = self.packages_list.selected()?;
+                    let entity = package
+                        .repository_url
+                        .as_deref()
+                        .and_then(repository_entity);
+                    let prompt = format!(
+                        "How do I use the {} component ({}) in my Pulumi programs?",
+                        package.display_name(),
+                        package.key()
+                    );
+                    Some((entity, prompt))
+                }
+                PlatformView::Templates => {
+                    let template = self.templates_list.selected()?;
+                    let prompt = format!(
+                        "How do I start a new project from the {} template, and what does it create?",
+                        template.full_name()
+                    );
+                    Some((None, prompt))
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Start a new Neo conversation about the selection in the current view:
+    /// the selection's entity is attached to the task once the prompt is sent
+    pub(super) fn ask_neo_about_selection(&mut self) {
+        let Some((entity, prompt)) = self.neo_context() else {
+            return;
+        };
+        if let Some(ref entity) = entity {
+            log::info!(
+                "Asking Neo about {} {}",
+                entity.entity_type.as_deref().unwrap_or("entity"),
+                entity_label(entity)
+            );
+        }
+
+        self.start_new_neo_task();
+        self.neo_attached_entity = entity;
+        self.neo_input.set_value(prompt);
+        self.tab = Tab::Neo;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stack_prompt_asks_why_a_failed_update_failed() {
+        let update = |result: &str| StackUpdate {
+            version: 12,
+            start_time: None,
+            end_time: None,
+            result: Some(result.to_string()),
+            resource_changes: None,
+        };
+
+        assert_eq!(
+            stack_prompt(Some(&update("failed"))),
+            "Why did update 12 of this stack fail?"
+        );
+        assert_eq!(
+            stack_prompt(Some(&update("succeeded"))),
+            "Summarize what update 12 of this stack changed."
+        );
+    }
+
+    #[test]
+    fn repository_entity_parses_forge_urls() {
+        let entity = repository_entity("https://github.com/pulumi/pulumi-aws.git").unwrap();
+        assert_eq!(entity.forge.as_deref(), Some("github"));
+        assert_eq!(entity_label(&entity), "pulumi/pulumi-aws");
+
+        assert_eq!(
+            repository_entity("https://gitlab.com/acme/infra/").and_then(|e| e.forge),
+            Some("gitlab".to_string())
+        );
+        assert!(repository_entity("https://example.com/acme/infra").is_none());
+        assert!(repository_entity("https://github.com/acme").is_none());
+    }
+
+    #[test]
+    fn entity_label_scopes_names() {
+        assert_eq!(entity_label(&stack_entity("web", "prod")), "web/prod");
+    }
+}
@@ -0,0 +1,394 @@
+//! Neo conversation export
+//!
+//! This module writes the loaded Neo task's conversation, with the task's
+//! entities, linked pull requests and policies, to a Markdown file to paste
+//! into a postmortem or PR description, or to a JSON transcript.
+
+use std::path::PathBuf;
+
+use crate::api::{NeoMessage, NeoMessageType, NeoTask};
+
+use super::neo_context::entity_label;
+use super::types::NeoExportFormat;
+use super::App;
+
+/// Name of a message type in the JSON transcript
+fn message_type_name(message_type: &NeoMessageType) -> &'static str {
+    match message_type {
+        NeoMessageType::UserMessage => "user",
+        NeoMessageType::AssistantMessage => "assistant",
+        NeoMessageType::ToolCall => "tool_call",
+        NeoMessageType::ToolResponse => "tool_response",
+        NeoMessageType::ToolError => "tool_error",
+        NeoMessageType::ApprovalRequest => "approval_request",
+        NeoMessageType::TaskNameChange => "task_name",
+    }
+}
+
+/// A fenced code block, with a fence longer than any backtick run in `content`
+fn code_block(language: &str, content: &str) -> String {
+    let longest_run = content
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!(
+        "{}{}\n{}\n{}\n\n",
+        fence,
+        language,
+        content.trim_end(),
+        fence
+    )
+}
+
+/// `heading`, with the message time when there is one
+fn message_heading(heading: &str, msg: &NeoMessage) -> String {
+    match &msg.timestamp {
+        Some(timestamp) => format!("### {} ({})\n\n", heading, timestamp),
+        None => format!("### {}\n\n", heading),
+    }
+}
+
+/// Render the conversation of task `id` as Markdown
+fn format_markdown(id: &str, task: Option<&NeoTask>, messages: &[NeoMessage]) -> String {
+    let mut out = String::new();
+    let title = task
+        .and_then(|t| t.name.clone())
+        .unwrap_or_else(|| format!("Neo task {}", id));
+    out.push_str(&format!("# {}\n\n", title));
+
+    out.push_str(&format!("- **Task:** `{}`\n", id));
+    if let Some(task) = task {
+        let started_by = task
+            .started_by
+            .as_ref()
+            .and_then(|u| u.login.clone().or(u.name.clone()));
+        let fields = [
+            ("Status", task.status.clone()),
+            ("Started by", started_by),
+            ("Created", task.created_at.clone()),
+            ("Updated", task.updated_at.clone()),
+            ("Link", task.url.clone()),
+        ];
+        for (label, value) in fields {
+            if let Some(value) = value {
+                out.push_str(&format!("- **{}:** {}\n", label, value));
+            }
+        }
+    }
+    out.push('\n');
+
+    if let Some(task) = task {
+        out.push_str("## Entities\n\n");
+        if task.entities.is_empty() {
+            out.push_str("_None_\n");
+        }
+        for entity in &task.entities {
+            let label = entity_label(entity);
+            let label = match &entity.url {
+                Some(url) => format!("[{}]({})", label, url),
+                None => label,
+            };
+            out.push_str(&format!(
+                "- {}: {}\n",
+                entity.entity_type.as_deref().unwrap_or("unknown"),
+                label
+            ));
+        }
+
+        out.push_str("\n## Linked pull requests\n\n");
+        if task.linked_prs.is_empty() {
+            out.push_str("_None_\n");
+        }
+        for pr in &task.linked_prs {
+            let title = format!(
+                "#{} {}",
+                pr.number.unwrap_or(0),
+                pr.title.as_deref().unwrap_or("Untitled")
+            );
+            let title = match &pr.url {
+                Some(url) => format!("[{}]({})", title, url),
+                None => title,
+            };
+            let repository = pr
+                .repository
+                .as_ref()
+                .map(|r| format!(" in {}", r))
+                .unwrap_or_default();
+            let state = pr
+                .state
+                .as_ref()
+                .map(|s| format!(" ({})", s))
+                .unwrap_or_default();
+            out.push_str(&format!("- {}{}{}\n", title, repository, state));
+        }
+
+        out.push_str("\n## Policies\n\n");
+        if task.policies.is_empty() {
+            out.push_str("_None_\n");
+        }
+        for policy in &task.policies {
+            let pack = policy
+                .pack_name
+                .as_ref()
+                .map(|p| format!(" ({})", p))
+                .unwrap_or_default();
+            let enforcement = policy
+                .enforcement_level
+                .as_ref()
+                .map(|e| format!(": {}", e))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "- {}{}{}\n",
+                policy.name.as_deref().unwrap_or("Unknown"),
+                pack,
+                enforcement
+            ));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## Conversation\n\n");
+    for msg in messages {
+        let tool = msg.tool_name.as_deref().unwrap_or("tool");
+        match msg.message_type {
+            NeoMessageType::UserMessage => {
+                out.push_str(&message_heading("You", msg));
+                out.push_str(&format!("{}\n\n", msg.content.trim_end()));
+            }
+            NeoMessageType::AssistantMessage => {
+                out.push_str(&message_heading("Neo", msg));
+                if !msg.content.trim().is_empty() {
+                    out.push_str(&format!("{}\n\n", msg.content.trim_end()));
+                }
+                for call in &msg.tool_calls {
+                    out.push_str(&format!("**Tool call:** `{}`\n\n", call.name));
+                    if let Some(args) = &call.args {
+                        let args = serde_json::to_string_pretty(args).unwrap_or_default();
+                        out.push_str(&code_block("json", &args));
+                    }
+                }
+            }
+            NeoMessageType::ToolCall => {
+                out.push_str(&format!("_Executing `{}`_\n\n", tool));
+            }
+            NeoMessageType::ToolResponse => {
+                out.push_str(&format!("**Result of** `{}`:\n\n", tool));
+                out.push_str(&code_block("", &msg.content));
+            }
+            NeoMessageType::ToolError => {
+                out.push_str(&format!("**Error running** `{}`:\n\n", tool));
+                out.push_str(&code_block("", &msg.content));
+            }
+            NeoMessageType::ApprovalRequest => {
+                out.push_str("> **Approval requested**\n>\n");
+                for line in msg.content.lines() {
+                    if line.is_empty() {
+                        out.push_str(">\n");
+                    } else {
+                        out.push_str(&format!("> {}\n", line));
+                    }
+                }
+                out.push('\n');
+            }
+            NeoMessageType::TaskNameChange => {
+                out.push_str(&format!("_{}_\n\n", msg.content));
+            }
+        }
+    }
+    out
+}
+
+/// Render the conversation of task `id` as a JSON transcript
+fn format_json(id: &str, task: Option<&NeoTask>, messages: &[NeoMessage]) -> String {
+    let task = task
+        .and_then(|t| serde_json::to_value(t).ok())
+        .unwrap_or_else(|| serde_json::json!({ "id": id }));
+    let messages: Vec<serde_json::Value> = messages
+        .iter()
+        .map(|msg| {
+            let mut entry = serde_json::json!({
+                "type": message_type_name(&msg.message_type),
+                "content": msg.content,
+            });
+            if let Some(timestamp) = &msg.timestamp {
+                entry["timestamp"] = timestamp.clone().into();
+            }
+            if let Some(tool_name) = &msg.tool_name {
+                entry["toolName"] = tool_name.clone().into();
+            }
+            if let Some(tool_call_id) = &msg.tool_call_id {
+                entry["toolCallId"] = tool_call_id.clone().into();
+            }
+            if !msg.tool_calls.is_empty() {
+                entry["toolCalls"] = serde_json::to_value(&msg.tool_calls).unwrap_or_default();
+            }
+            entry
+        })
+        .collect();
+
+    let transcript = serde_json::json!({ "task": task, "messages": messages });
+    let mut json = serde_json::to_string_pretty(&transcript).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+impl App {
+    /// File the loaded task's conversation is exported to in `format`
+    pub(super) fn neo_export_path(&self, format: NeoExportFormat) -> Option<PathBuf> {
+        let id = self.state.current_task_id.as_ref()?;
+        Some(self.config.neo_export_dir().join(format.file_name(id)))
+    }
+
+    /// Write the loaded task's conversation and metadata to the export directory,
+    /// first asking for confirmation if that would overwrite an existing file
+    pub(super) async fn export_neo_conversation(&mut self, format: NeoExportFormat) {
+        let (Some(id), Some(path)) = (
+            self.state.current_task_id.clone(),
+            self.neo_export_path(format),
+        ) else {
+            return;
+        };
+        if self.state.neo_messages.is_empty() {
+            self.error = Some("The task has no messages to export yet".to_string());
+            return;
+        }
+        if path.exists() && self.neo_export_confirm.is_none() {
+            self.neo_export_confirm = Some(format);
+            return;
+        }
+        self.neo_export_confirm = None;
+
+        // Entities, PRs and policies change as the task runs
+        self.refresh_current_task_details().await;
+        let task = self.details_task();
+        let content = match format {
+            NeoExportFormat::Markdown => format_markdown(&id, task, &self.state.neo_messages),
+            NeoExportFormat::Json => format_json(&id, task, &self.state.neo_messages),
+        };
+
+        let dir = self.config.neo_export_dir();
+        match std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, content)) {
+            Ok(()) => {
+                log::info!(
+                    "Exported Neo task {} as {} to {}",
+                    id,
+                    format.label(),
+                    path.display()
+                );
+                self.neo_exported = Some(path.display().to_string());
+            }
+            Err(e) => {
+                log::error!("Failed to export Neo task to {}: {}", path.display(), e);
+                self.error = Some(format!("Failed to write {}: {}", path.display(), e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(message_type: NeoMessageType, content: &str) -> NeoMessage {
+        NeoMessage {
+            role: String::new(),
+            content: content.to_string(),
+            message_type,
+            timestamp: None,
+            tool_calls: vec![],
+            tool_name: None,
+            tool_call_id: None,
+            approval_request_id: None,
+        }
+    }
+
+    fn conversation() -> Vec<NeoMessage> {
+        let mut assistant = message(NeoMessageType::AssistantMessage, "Let me check the stack.");
+        assistant.tool_calls = serde_json::from_value(serde_json::json!([
+            {"id": "call-1", "name": "get_stack", "args": {"stack": "prod"}}
+        ]))
+        .unwrap();
+        let mut response = message(NeoMessageType::ToolResponse, "```\n3 resources\n```");
+        response.tool_name = Some("get_stack".to_string());
+        let mut error = message(NeoMessageType::ToolError, "permission denied");
+        error.tool_name = Some("update_stack".to_string());
+        vec![
+            message(NeoMessageType::TaskNameChange, "Task: Fix prod"),
+            message(NeoMessageType::UserMessage, "Why is prod failing?"),
+            assistant,
+            response,
+            error,
+            message(
+                NeoMessageType::ApprovalRequest,
+                "Run `pulumi up`?\n\nOn prod.",
+            ),
+        ]
+    }
+
+    fn task() -> NeoTask {
+        serde_json::from_value(serde_json::json!({
+            "id": "task-1",
+            "name": "Fix prod",
+            "status": "idle",
+            "entities": [{"type": "stack", "project": "web", "stack": "prod"}],
+            "linkedPrs": [{
+                "number": 42,
+                "title": "Raise memory",
+                "url": "https://github.com/acme/web/pull/42",
+                "repository": "acme/web",
+                "state": "open",
+            }],
+            "policies": [{
+                "name": "no-public-buckets",
+                "packName": "aws-guard",
+                "enforcementLevel": "mandatory",
+            }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn format_markdown_writes_metadata_and_every_message_type() {
+        let markdown = format_markdown("task-1", Some(&task()), &conversation());
+
+        for expected in [
+            "# Fix prod\n",
+            "- **Status:** idle\n",
+            "## Entities\n\n- stack: web/prod\n",
+            "- [#42 Raise memory](https://github.com/acme/web/pull/42) in acme/web (open)\n",
+            "- no-public-buckets (aws-guard): mandatory\n",
+            "_Task: Fix prod_\n",
+            "### You\n\nWhy is prod failing?\n",
+            "### Neo\n\nLet me check the stack.\n\n**Tool call:** `get_stack`\n\n```json\n{\n  \"stack\": \"prod\"\n}\n```\n",
+            // A fence longer than the backticks in the content
+            "**Result of** `get_stack`:\n\n````\n```\n3 resources\n```\n````\n",
+            "**Error running** `update_stack`:\n\n```\npermission denied\n```\n",
+            "> **Approval requested**\n>\n> Run `pulumi up`?\n>\n> On prod.\n",
+        ] {
+            assert!(markdown.contains(expected), "missing {:?} in\n{}", expected, markdown);
+        }
+    }
+
+    #[test]
+    fn format_json_keeps_task_metadata_and_tool_calls() {
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json("task-1", Some(&task()), &conversation())).unwrap();
+
+        assert_eq!(json["task"]["name"], "Fix prod");
+        assert_eq!(json["task"]["linkedPrs"][0]["number"], 42);
+        assert_eq!(json["task"]["policies"][0]["enforcementLevel"], "mandatory");
+        let messages = json["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 6);
+        assert_eq!(messages[2]["type"], "assistant");
+        assert_eq!(messages[2]["toolCalls"][0]["args"]["stack"], "prod");
+        assert_eq!(messages[4]["type"], "tool_error");
+        assert_eq!(messages[4]["toolName"], "update_stack");
+
+        // A task missing from the task list is exported with its ID
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json("task-2", None, &conversation())).unwrap();
+        assert_eq!(json["task"], serde_json::json!({"id": "task-2"}));
+    }
+}
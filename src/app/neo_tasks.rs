@@ -0,0 +1,121 @@
+//! Neo task list
+//!
+//! This module loads Neo tasks a page at a time, fetching the next page as
+//! the selection nears the end of the list, and shows the loaded tasks
+//! through the task list's status, owner and name filters and sort order.
+
+use crate::api::NeoTaskPage;
+
+use super::types::DataLoadResult;
+use super::App;
+
+/// Fetch the next page once the selection is this close to the end of the list
+const LOAD_AHEAD: usize = 10;
+
+impl App {
+    /// Show the loaded tasks that pass the filters, keeping the selected task selected
+    pub(super) fn refresh_neo_tasks_list(&mut self) {
+        let selected = self.neo_tasks_list.selected().map(|task| task.id.clone());
+        let tasks = self
+            .neo_tasks_search
+            .apply(&self.state.neo_tasks, self.state.user_login.as_deref());
+        self.neo_tasks_list.set_items(tasks);
+        if let Some(id) = selected {
+            self.select_neo_task(&id);
+        }
+        self.load_more_neo_tasks_if_needed();
+    }
+
+    /// Select a task in the list, if it passes the filters
+    pub(super) fn select_neo_task(&mut self, id: &str) {
+        if let Some(index) = self
+            .neo_tasks_list
+            .items()
+            .iter()
+            .position(|task| task.id == id)
+        {
+            self.neo_tasks_list.select(Some(index));
+        }
+    }
+
+    /// Show the first page of tasks, dropping any pages loaded before
+    pub(super) fn apply_neo_tasks(&mut self, page: NeoTaskPage) {
+        log::info!("Received {} Neo tasks", page.tasks.len());
+        self.state.neo_tasks = page.tasks;
+        self.neo_tasks_search.continuation_token = page.continuation_token;
+        self.neo_tasks_search.loading = false;
+        self.neo_tasks_search.error = None;
+        self.refresh_neo_tasks_list();
+    }
+
+    /// Fetch the next page in the background when the selection is near the end
+    /// of the list; an empty list (e.g. nothing loaded passes the filters) counts as the end
+    pub(super) fn load_more_neo_tasks_if_needed(&mut self) {
+        let search = &self.neo_tasks_search;
+        if search.loading || search.error.is_some() {
+            return;
+        }
+        let Some(after) = search.continuation_token.clone() else {
+            return;
+        };
+        let below = self.neo_tasks_list.len()
+            - self
+                .neo_tasks_list
+                .selected_index()
+                .map_or(0, |index| index + 1);
+        if below > LOAD_AHEAD {
+            return;
+        }
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        let org = self.state.organization.clone();
+        let tx = self.data_result_tx.clone();
+        self.neo_tasks_search.loading = true;
+        tokio::spawn(async move {
+            let result = client
+                .list_neo_tasks(org.as_deref(), Some(&after))
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(DataLoadResult::NeoTaskPage { after, result }).await;
+        });
+    }
+
+    /// Add a further page of tasks, unless the list was reloaded since it was requested
+    pub(super) fn apply_neo_task_page(
+        &mut self,
+        after: String,
+        result: Result<NeoTaskPage, String>,
+    ) {
+        if self.neo_tasks_search.continuation_token.as_deref() != Some(after.as_str()) {
+            log::debug!("Dropping stale page of Neo tasks");
+            return;
+        }
+
+        self.neo_tasks_search.loading = false;
+        match result {
+            Ok(page) => {
+                log::debug!("Received {} more Neo tasks", page.tasks.len());
+                for task in page.tasks {
+                    if !self.state.neo_tasks.iter().any(|t| t.id == task.id) {
+                        self.state.neo_tasks.push(task);
+                    }
+                }
+                self.neo_tasks_search.continuation_token = page.continuation_token;
+                self.refresh_neo_tasks_list();
+            }
+            Err(e) => {
+                log::warn!("Failed to load more Neo tasks: {}", e);
+                self.neo_tasks_search.error = Some(e);
+            }
+        }
+    }
+
+    /// Forget loaded pages and filters, when another organization is shown
+    pub(super) fn reset_neo_tasks_search(&mut self) {
+        self.neo_tasks_search = Default::default();
+        self.neo_tasks_query_input.set_value(String::new());
+        self.neo_tasks_is_editing_query = false;
+    }
+}
@@ -0,0 +1,63 @@
+//! Resources view operations
+//!
+//! This module runs resource searches against the Pulumi search API one
+//! page at a time and applies the results, dropping responses for a query,
+//! sort order or page the user has already moved away from.
+
+use crate::api::{ResourceSearchPage, ResourceSearchParams};
+
+use super::types::DataLoadResult;
+use super::App;
+
+impl App {
+    /// Fetch the current page of the Resources view in the background
+    pub(super) fn spawn_resource_search(&mut self) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let org = self.state.organization.clone();
+        let tx = self.data_result_tx.clone();
+        let params = self.resources_search.params();
+
+        self.resources_search.loading = true;
+        tokio::spawn(async move {
+            let result = client
+                .search_resources(org.as_deref(), &params)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx
+                .send(DataLoadResult::ResourceSearch { params, result })
+                .await;
+        });
+    }
+
+    /// Show a page of search results, unless the search parameters changed since it was requested
+    pub(super) fn apply_resource_search(
+        &mut self,
+        params: ResourceSearchParams,
+        result: Result<ResourceSearchPage, String>,
+    ) {
+        if params != self.resources_search.params() {
+            log::debug!("Dropping stale resource search for {:?}", params.query);
+            return;
+        }
+
+        self.resources_search.loading = false;
+        match result {
+            Ok(page) => {
+                self.resources_search.total = page.total;
+                self.resources_search.has_more = page.has_more;
+                self.resources_search.error = None;
+                self.resources_list.set_items(page.resources);
+                self.resources_list.select_first();
+            }
+            Err(e) => {
+                log::warn!("Resource search failed: {}", e);
+                self.resources_search.error = Some(e);
+                self.resources_search.total = None;
+                self.resources_search.has_more = false;
+                self.resources_list.set_items(Vec::new());
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Stacks view operations
+//!
+//! This module handles loading stack details (update history and the
+//! resource tree), opening individual updates, keeping the selected
+//! resource's properties in sync, and launching CLI commands on a stack.
+
+use std::collections::{HashMap, HashSet};
+
+use tui_scrollview::ScrollViewState;
+
+use crate::api::StackResource;
+use crate::commands::{CommandCategory, PULUMI_COMMANDS};
+use crate::ui::syntax::highlight_json;
+
+use super::types::StackResourceRow;
+use super::App;
+
+/// Order resources depth-first under their parent URN.
+/// Resources whose parent is missing from the checkpoint are shown at the top level.
+fn build_resource_tree(resources: Vec<StackResource>) -> Vec<StackResourceRow> {
+    let urns: HashSet<&str> = resources.iter().map(|r| r.urn.as_str()).collect();
+    let mut roots = Vec::new();
+    let mut children: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, resource) in resources.iter().enumerate() {
+        match resource.parent.as_deref() {
+            Some(parent) if parent != resource.urn && urns.contains(parent) => {
+                children.entry(parent).or_default().push(i)
+            }
+            _ => roots.push(i),
+        }
+    }
+
+    let mut order: Vec<(usize, usize)> = Vec::with_capacity(resources.len());
+    let mut visited = vec![false; resources.len()];
+    let mut stack: Vec<(usize, usize)> = roots.into_iter().rev().map(|i| (i, 0)).collect();
+    loop {
+        while let Some((i, depth)) = stack.pop() {
+            if std::mem::replace(&mut visited[i], true) {
+                continue;
+            }
+            order.push((i, depth));
+            if let Some(kids) = children.get(resources[i].urn.as_str()) {
+                stack.extend(kids.iter().rev().map(|&k| (k, depth + 1)));
+            }
+        }
+        // Parent cycles never reach a root; start a new top level there rather than dropping them
+        match visited.iter().position(|v| !v) {
+            Some(i) => stack.push((i, 0)),
+            None => break,
+        }
+    }
+
+    let mut resources: Vec<Option<StackResource>> = resources.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|(i, depth)| {
+            resources[i]
+                .take()
+                .map(|resource| StackResourceRow { depth, resource })
+        })
+        .collect()
+}
+
+impl App {
+    /// Forget the details loaded for the previously selected stack
+    pub(super) fn clear_selected_stack_details(&mut self) {
+        self.stack_updates_list.set_items(Vec::new());
+        self.state.selected_stack_resource_highlighted = None;
+        self.stack_resources_list.set_items(Vec::new());
+        self.stack_resource_scroll = ScrollViewState::default();
+    }
+
+    /// Load update history and resources for the selected stack
+    pub(super) async fn load_selected_stack_details(&mut self) {
+        let Some(stack) = self.stacks_list.selected().cloned() else {
+            return;
+        };
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner.set_message("Loading stack details...");
+
+        let (updates, resources) = tokio::join!(
+            client.get_stack_updates(&stack.org_name, &stack.project_name, &stack.stack_name),
+            client.get_stack_resources(&stack.org_name, &stack.project_name, &stack.stack_name)
+        );
+
+        match updates {
+            Ok(updates) => self.stack_updates_list.set_items(updates),
+            Err(e) => log::warn!("Failed to load stack updates: {}", e),
+        }
+
+        match resources {
+            Ok(resources) => {
+                self.stack_resources_list
+                    .set_items(build_resource_tree(resources));
+                self.update_selected_stack_resource();
+            }
+            Err(e) => log::warn!("Failed to load stack resources: {}", e),
+        }
+
+        self.is_loading = false;
+    }
+
+    /// Load the selected update's details and engine events, then open the details dialog
+    pub(super) async fn open_selected_update(&mut self) {
+        let (Some(stack), Some(update)) = (
+            self.stacks_list.selected().cloned(),
+            self.stack_updates_list.selected().cloned(),
+        ) else {
+            return;
+        };
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        self.is_loading = true;
+        self.spinner
+            .set_message(format!("Loading update v{}...", update.version));
+
+        let details = match client
+            .get_stack_update(
+                &stack.org_name,
+                &stack.project_name,
+                &stack.stack_name,
+                update.version,
+            )
+            .await
+        {
+            Ok(details) => details,
+            Err(e) => {
+                self.error = Some(format!("Failed to load update: {}", e));
+                self.is_loading = false;
+                return;
+            }
+        };
+
+        let events = client
+            .get_update_events(
+                &stack.org_name,
+                &stack.project_name,
+                &stack.stack_name,
+                &details.kind,
+                &details.update_id,
+            )
+            .await;
+        (
+            self.state.selected_update_events,
+            self.state.selected_update_events_error,
+        ) = match events {
+            Ok(events) => (events, None),
+            Err(e) => {
+                log::warn!("Failed to load update events: {}", e);
+                (Vec::new(), Some(e.to_string()))
+            }
+        };
+        self.state.selected_update_details = Some(details);
+        self.update_details_scroll = ScrollViewState::default();
+        self.show_update_details = true;
+        self.is_loading = false;
+    }
+
+    /// Open the Commands parameter dialog for `command` (preview, up, ...) on the
+    /// selected stack, with the stack and its mapped project directory prefilled
+    pub(super) fn launch_stack_command(&mut self, command: &str) {
+        let Some(stack) = self.stacks_list.selected().cloned() else {
+            return;
+        };
+        let Some(cmd) = PULUMI_COMMANDS.iter().find(|c| c.name == command) else {
+            return;
+        };
+        let mut prefill = vec![("stack", stack.full_name())];
+        match self
+            .config
+            .project_dir(&stack.org_name, &stack.project_name)
+        {
+            Some(dir) => prefill.push(("cwd", dir)),
+            None => log::info!(
+                "No project_dirs entry for {}/{}; using the current directory",
+                stack.org_name,
+                stack.project_name
+            ),
+        }
+
+        self.show_command_dialog(cmd, CommandCategory::StackOperations, &prefill);
+    }
+
+    /// Re-highlight the selected resource's inputs and outputs
+    pub(super) fn update_selected_stack_resource(&mut self) {
+        self.stack_resource_scroll = ScrollViewState::default();
+        self.state.selected_stack_resource_highlighted =
+            self.stack_resources_list.selected().map(|row| {
+                let properties = serde_json::json!({
+                    "inputs": row.resource.inputs,
+                    "outputs": row.resource.outputs,
+                });
+                let json = serde_json::to_string_pretty(&properties).unwrap_or_default();
+                highlight_json(&json)
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(name: &str, parent: Option<&str>) -> StackResource {
+        StackResource {
+            urn: format!("urn:pulumi:dev::app::test:index:Res::{}", name),
+            resource_type: "test:index:Res".to_string(),
+            id: None,
+            parent: parent.map(|p| format!("urn:pulumi:dev::app::test:index:Res::{}", p)),
+            provider: None,
+            custom: true,
+            inputs: serde_json::Value::Null,
+            outputs: serde_json::Value::Null,
+        }
+    }
+
+    fn names(rows: &[StackResourceRow]) -> Vec<(usize, &str)> {
+        rows.iter()
+            .map(|row| (row.depth, row.resource.name()))
+            .collect()
+    }
+
+    #[test]
+    fn build_resource_tree_nests_children_under_parents() {
+        let rows = build_resource_tree(vec![
+            resource("stack", None),
+            resource("bucket", Some("component")),
+            resource("component", Some("stack")),
+            resource("policy", Some("bucket")),
+            resource("role", Some("stack")),
+        ]);
+
+        assert_eq!(
+            names(&rows),
+            vec![
+                (0, "stack"),
+                (1, "component"),
+                (2, "bucket"),
+                (3, "policy"),
+                (1, "role"),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_resource_tree_keeps_orphans_and_cycles() {
+        let rows = build_resource_tree(vec![
+            resource("a", Some("b")),
+            resource("b", Some("a")),
+            resource("orphan", Some("missing")),
+        ]);
+
+        assert_eq!(names(&rows), vec![(0, "orphan"), (0, "a"), (1, "b")]);
+    }
+}
@@ -4,18 +4,35 @@
 //! including enums for tabs, focus modes, and the main application state struct.
 
 use crate::api::{
-    EscEnvironmentSummary, NeoMessage, NeoSlashCommand, NeoTask, OrgStackUpdate, RegistryPackage,
-    RegistryTemplate, Resource, ResourceSummaryPoint, Service, Stack,
+    EscEnvironmentSummary, NeoEventCursor, NeoMessage, NeoMessageType, NeoSlashCommand, NeoTask,
+    NeoTaskPage, OrgStackUpdate, RegistryPackage, RegistryTemplate, ResourceSearchPage,
+    ResourceSearchParams, ResourceSummaryPoint, Service, Stack, StackResource, UpdateDetails,
+    UpdateEvent,
 };
 
+/// Resources shown per page in the Resources view
+pub const RESOURCE_PAGE_SIZE: i64 = 50;
+
 /// Async data loading result
 #[derive(Debug)]
 pub enum DataLoadResult {
     Stacks(Vec<Stack>),
     EscEnvironments(Vec<EscEnvironmentSummary>),
-    NeoTasks(Vec<NeoTask>),
+    /// First page of Neo tasks
+    NeoTasks(NeoTaskPage),
+    /// A further page of Neo tasks, fetched with the given continuation token
+    NeoTaskPage {
+        after: String,
+        result: Result<NeoTaskPage, String>,
+    },
     NeoSlashCommands(Vec<NeoSlashCommand>),
-    Resources(Vec<Resource>),
+    /// Total number of resources in the organization (for dashboard)
+    ResourceCount(u64),
+    /// A page of resource search results for the given query
+    ResourceSearch {
+        params: ResourceSearchParams,
+        result: Result<ResourceSearchPage, String>,
+    },
     Services(Vec<Service>),
     RegistryPackages(Vec<RegistryPackage>),
     RegistryTemplates(Vec<RegistryTemplate>),
@@ -36,15 +53,28 @@ pub enum DataLoadResult {
 pub enum NeoAsyncResult {
     /// Task created successfully
     TaskCreated { task_id: String },
-    /// Task events/messages received
+    /// New task events/messages received since the last poll
     EventsReceived {
+        /// Task the poll was issued for (results for a task we've left are dropped)
+        task_id: String,
+        /// Cursor the poll started from
+        since: NeoEventCursor,
+        /// Only the messages after `since`
         messages: Vec<NeoMessage>,
-        #[allow(dead_code)]
-        has_more: bool,
+        /// Cursor to resume from on the next poll
+        cursor: NeoEventCursor,
         /// Task status from API (e.g., "running", "idle", "completed")
         /// Used to determine if we should keep polling/showing thinking indicator
         task_status: Option<String>,
     },
+    /// A poll request failed (transient, polling backs off and retries)
+    PollFailed,
+    /// The approval decision for the request at `index` was sent (or failed to)
+    ApprovalSent {
+        task_id: String,
+        index: usize,
+        result: Result<(), String>,
+    },
     /// Error occurred
     Error(String),
 }
@@ -67,6 +97,7 @@ pub enum Tab {
     Neo,
     Platform,
     Commands,
+    Resources,
 }
 
 impl Tab {
@@ -77,6 +108,7 @@ impl Tab {
             Tab::Commands,
             Tab::Neo,
             Tab::Stacks,
+            Tab::Resources,
             Tab::Esc,
             Tab::Platform,
         ]
@@ -86,6 +118,7 @@ impl Tab {
         match self {
             Tab::Dashboard => " Dashboard ",
             Tab::Stacks => " Stacks ",
+            Tab::Resources => " Resources ",
             Tab::Esc => " Environment ",
             Tab::Neo => " Neo ",
             Tab::Platform => " Platform ",
@@ -99,8 +132,9 @@ impl Tab {
             Tab::Commands => 1,
             Tab::Neo => 2,
             Tab::Stacks => 3,
-            Tab::Esc => 4,
-            Tab::Platform => 5,
+            Tab::Resources => 4,
+            Tab::Esc => 5,
+            Tab::Platform => 6,
         }
     }
 
@@ -110,8 +144,9 @@ impl Tab {
             1 => Tab::Commands,
             2 => Tab::Neo,
             3 => Tab::Stacks,
-            4 => Tab::Esc,
-            5 => Tab::Platform,
+            4 => Tab::Resources,
+            5 => Tab::Esc,
+            6 => Tab::Platform,
             _ => Tab::Dashboard,
         }
     }
@@ -151,6 +186,548 @@ impl EscPane {
     }
 }
 
+/// Create / clone / delete dialog in the ESC view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscEnvDialog {
+    /// New environment from a project and name, seeded with a starter definition
+    Create,
+    /// Copy of the selected environment under a new project and name
+    Clone,
+    /// Delete the selected environment once its name is typed back
+    Delete,
+}
+
+impl EscEnvDialog {
+    /// Action for error messages, e.g. "clone environment"
+    pub fn verb(&self) -> &'static str {
+        match self {
+            EscEnvDialog::Create => "create environment",
+            EscEnvDialog::Clone => "clone environment",
+            EscEnvDialog::Delete => "delete environment",
+        }
+    }
+
+    /// Number of focusable fields (inputs and checkboxes)
+    pub fn field_count(&self) -> usize {
+        match self {
+            EscEnvDialog::Create => 2,
+            EscEnvDialog::Clone => 4,
+            EscEnvDialog::Delete => 1,
+        }
+    }
+}
+
+/// Text prompt shown over the ESC revisions pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscRevisionPrompt {
+    /// Name of a tag to create, or move, on the selected revision
+    Tag,
+    /// Optional reason for retracting the selected revision
+    Retract,
+}
+
+/// Stacks view pane selection (which pane has focus)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StacksPane {
+    #[default]
+    Stacks,
+    Updates,
+    Resources,
+    Properties,
+}
+
+impl StacksPane {
+    pub fn next(&self) -> Self {
+        match self {
+            StacksPane::Stacks => StacksPane::Updates,
+            StacksPane::Updates => StacksPane::Resources,
+            StacksPane::Resources | StacksPane::Properties => StacksPane::Properties,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            StacksPane::Stacks | StacksPane::Updates => StacksPane::Stacks,
+            StacksPane::Resources => StacksPane::Updates,
+            StacksPane::Properties => StacksPane::Resources,
+        }
+    }
+}
+
+/// A resource in the stack resource tree, indented under its parent
+#[derive(Debug, Clone)]
+pub struct StackResourceRow {
+    pub depth: usize,
+    pub resource: StackResource,
+}
+
+/// What a row of the ESC import graph shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EscGraphNode {
+    /// Section title
+    Heading(&'static str),
+    /// An environment (`project/name`), with the version an import pins, if any
+    Environment {
+        env: String,
+        version: Option<String>,
+    },
+    /// A stack whose config uses the environment
+    Stack {
+        project: String,
+        stack: String,
+        version: i64,
+    },
+    /// An Insights account using the environment for credentials
+    InsightsAccount(String),
+    /// An import cycle, as the environments along it
+    Cycle(Vec<String>),
+    /// A branch that could not be loaded
+    Error(String),
+}
+
+/// How an environment row relates to the rest of the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscGraphMark {
+    #[default]
+    None,
+    /// Already expanded further up, so its children are not repeated
+    Repeated,
+    /// Imports an environment that is already on the path to it
+    Cycle,
+}
+
+/// A node in the ESC import graph, indented under its parent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscGraphRow {
+    pub depth: usize,
+    pub node: EscGraphNode,
+    pub mark: EscGraphMark,
+}
+
+/// A secret rotator (`fn::rotate`) found in an environment definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscRotator {
+    /// Path under `values`, e.g. `aws.creds`
+    pub path: String,
+    /// Rotation provider, e.g. `aws-iam`; empty if it could not be read
+    pub provider: String,
+}
+
+/// Section of the ESC rotation pane that has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscRotationPane {
+    #[default]
+    Rotators,
+    Schedules,
+}
+
+/// What the ESC change request pane shows for the selected change request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscChangeView {
+    /// Diff of the proposed definition against the current one
+    #[default]
+    Diff,
+    /// Values the proposed definition resolves to
+    Values,
+}
+
+/// Change request action waiting for a `y` confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscChangeAction {
+    /// Approve the latest revision of the change request
+    Approve,
+    /// Write the proposed definition to the environment
+    Apply,
+    /// Close the change request without applying it
+    Close,
+}
+
+impl EscChangeAction {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            EscChangeAction::Approve => "approve",
+            EscChangeAction::Apply => "apply",
+            EscChangeAction::Close => "close",
+        }
+    }
+}
+
+/// File format for exporting an ESC environment's `environmentVariables`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscExportFormat {
+    /// `KEY="value"` lines
+    #[default]
+    Dotenv,
+    /// `export KEY='value'` lines, to `source` from a shell
+    Shell,
+    /// A JSON object of names to values
+    Json,
+}
+
+impl EscExportFormat {
+    pub fn all() -> &'static [EscExportFormat] {
+        &[
+            EscExportFormat::Dotenv,
+            EscExportFormat::Shell,
+            EscExportFormat::Json,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EscExportFormat::Dotenv => "dotenv",
+            EscExportFormat::Shell => "shell",
+            EscExportFormat::Json => "JSON",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            EscExportFormat::Dotenv => EscExportFormat::Shell,
+            EscExportFormat::Shell => EscExportFormat::Json,
+            EscExportFormat::Json => EscExportFormat::Dotenv,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            EscExportFormat::Dotenv => EscExportFormat::Json,
+            EscExportFormat::Shell => EscExportFormat::Dotenv,
+            EscExportFormat::Json => EscExportFormat::Shell,
+        }
+    }
+
+    /// File written by default for the environment `name`
+    pub fn default_path(&self, name: &str) -> String {
+        match self {
+            EscExportFormat::Dotenv => ".env".to_string(),
+            EscExportFormat::Shell => format!("{}.env.sh", name),
+            EscExportFormat::Json => format!("{}.env.json", name),
+        }
+    }
+}
+
+/// File format for exporting a Neo conversation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeoExportFormat {
+    /// A readable transcript, e.g. for a postmortem or PR description
+    Markdown,
+    /// The task metadata and messages as structured data
+    Json,
+}
+
+impl NeoExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NeoExportFormat::Markdown => "Markdown",
+            NeoExportFormat::Json => "JSON",
+        }
+    }
+
+    /// File name the conversation of task `id` is exported to
+    pub fn file_name(&self, id: &str) -> String {
+        match self {
+            NeoExportFormat::Markdown => format!("neo-{}.md", id),
+            NeoExportFormat::Json => format!("neo-{}.json", id),
+        }
+    }
+}
+
+/// Column the Resources table is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResourceSortColumn {
+    #[default]
+    Name,
+    Type,
+    Stack,
+    Project,
+    Package,
+    Modified,
+}
+
+impl ResourceSortColumn {
+    pub fn all() -> &'static [ResourceSortColumn] {
+        &[
+            ResourceSortColumn::Name,
+            ResourceSortColumn::Type,
+            ResourceSortColumn::Stack,
+            ResourceSortColumn::Project,
+            ResourceSortColumn::Package,
+            ResourceSortColumn::Modified,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ResourceSortColumn::Name => "Name",
+            ResourceSortColumn::Type => "Type",
+            ResourceSortColumn::Stack => "Stack",
+            ResourceSortColumn::Project => "Project",
+            ResourceSortColumn::Package => "Package",
+            ResourceSortColumn::Modified => "Modified",
+        }
+    }
+
+    /// Field name understood by the search API's `sort` parameter
+    pub fn field(&self) -> &'static str {
+        match self {
+            ResourceSortColumn::Name => "name",
+            ResourceSortColumn::Type => "type",
+            ResourceSortColumn::Stack => "stack",
+            ResourceSortColumn::Project => "project",
+            ResourceSortColumn::Package => "package",
+            ResourceSortColumn::Modified => "modified",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let all = ResourceSortColumn::all();
+        let index = all.iter().position(|c| c == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+}
+
+/// Query, sort order and page of the Resources view
+#[derive(Debug, Clone)]
+pub struct ResourceSearchState {
+    /// Query the current results were searched with
+    pub query: String,
+    pub sort: ResourceSortColumn,
+    pub ascending: bool,
+    /// 1-based page number
+    pub page: i64,
+    /// Matches across all pages, as reported by the server
+    pub total: Option<u64>,
+    pub has_more: bool,
+    /// A search for the current parameters is outstanding
+    pub loading: bool,
+    /// Error from the last search (e.g. an invalid query)
+    pub error: Option<String>,
+}
+
+impl Default for ResourceSearchState {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            sort: ResourceSortColumn::default(),
+            ascending: true,
+            page: 1,
+            total: None,
+            has_more: false,
+            loading: false,
+            error: None,
+        }
+    }
+}
+
+impl ResourceSearchState {
+    /// Parameters for fetching the current page
+    pub fn params(&self) -> ResourceSearchParams {
+        ResourceSearchParams {
+            query: self.query.clone(),
+            page: self.page,
+            page_size: RESOURCE_PAGE_SIZE,
+            sort: Some(self.sort.field().to_string()),
+            ascending: self.ascending,
+        }
+    }
+
+    /// Number of pages, once the total is known
+    pub fn page_count(&self) -> Option<i64> {
+        self.total
+            .map(|total| (total as i64 + RESOURCE_PAGE_SIZE - 1) / RESOURCE_PAGE_SIZE)
+            .map(|pages| pages.max(1))
+    }
+
+    /// Start over on the first page with a new query
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.page = 1;
+    }
+
+    /// Sort by the given column; choosing the current column again reverses the order
+    pub fn sort_by(&mut self, column: ResourceSortColumn) {
+        if self.sort == column {
+            self.ascending = !self.ascending;
+        } else {
+            self.sort = column;
+            self.ascending = true;
+        }
+        self.page = 1;
+    }
+
+    /// Move to the next page; returns false on the last page
+    pub fn next_page(&mut self) -> bool {
+        let has_next = match self.page_count() {
+            Some(pages) => self.page < pages,
+            None => self.has_more,
+        };
+        if has_next {
+            self.page += 1;
+        }
+        has_next
+    }
+
+    /// Move to the previous page; returns false on the first page
+    pub fn previous_page(&mut self) -> bool {
+        if self.page > 1 {
+            self.page -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Neo tasks the task list is filtered to, by status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NeoTaskStatusFilter {
+    #[default]
+    All,
+    Running,
+    Idle,
+    Completed,
+    Cancelled,
+}
+
+impl NeoTaskStatusFilter {
+    pub fn all() -> &'static [NeoTaskStatusFilter] {
+        &[
+            NeoTaskStatusFilter::All,
+            NeoTaskStatusFilter::Running,
+            NeoTaskStatusFilter::Idle,
+            NeoTaskStatusFilter::Completed,
+            NeoTaskStatusFilter::Cancelled,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            NeoTaskStatusFilter::All => "all",
+            NeoTaskStatusFilter::Running => "running",
+            NeoTaskStatusFilter::Idle => "idle",
+            NeoTaskStatusFilter::Completed => "completed",
+            NeoTaskStatusFilter::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether a task with `status` is shown; pending and in-progress tasks count as running
+    pub fn matches(&self, status: Option<&str>) -> bool {
+        let status = status.unwrap_or_default().to_lowercase();
+        match self {
+            NeoTaskStatusFilter::All => true,
+            NeoTaskStatusFilter::Running => {
+                matches!(status.as_str(), "running" | "in_progress" | "pending")
+            }
+            NeoTaskStatusFilter::Cancelled => status == "cancelled" || status == "canceled",
+            other => status == other.title(),
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let all = NeoTaskStatusFilter::all();
+        let index = all.iter().position(|f| f == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+}
+
+/// Time the Neo task list is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NeoTaskSort {
+    #[default]
+    Updated,
+    Created,
+}
+
+impl NeoTaskSort {
+    pub fn title(&self) -> &'static str {
+        match self {
+            NeoTaskSort::Updated => "updated",
+            NeoTaskSort::Created => "created",
+        }
+    }
+
+    /// The time a task is sorted by; tasks never updated sort by creation
+    fn time<'a>(&self, task: &'a NeoTask) -> Option<&'a str> {
+        match self {
+            NeoTaskSort::Updated => task.updated_at.as_deref().or(task.created_at.as_deref()),
+            NeoTaskSort::Created => task.created_at.as_deref(),
+        }
+    }
+}
+
+/// Filters, sort order and paging of the Neo task list
+///
+/// The tasks API only pages through tasks, so filtering and sorting apply
+/// to the pages loaded so far; more pages load as the selection nears the
+/// end of the list.
+#[derive(Debug, Clone, Default)]
+pub struct NeoTaskSearchState {
+    /// Text task names must contain (case-insensitive)
+    pub query: String,
+    pub status: NeoTaskStatusFilter,
+    /// Only tasks started by the current user
+    pub mine: bool,
+    pub sort: NeoTaskSort,
+    /// Oldest first instead of newest first
+    pub ascending: bool,
+    /// Token for the next page of tasks; `None` once every page is loaded
+    pub continuation_token: Option<String>,
+    /// A page of tasks is being fetched
+    pub loading: bool,
+    /// Error from fetching the last page
+    pub error: Option<String>,
+}
+
+impl NeoTaskSearchState {
+    /// Whether any filter hides tasks
+    pub fn is_filtered(&self) -> bool {
+        !self.query.is_empty() || self.status != NeoTaskStatusFilter::All || self.mine
+    }
+
+    /// Whether `task` passes the filters; `user` is the current user's login
+    fn matches(&self, task: &NeoTask, user: Option<&str>) -> bool {
+        let name_matches = self.query.is_empty()
+            || task
+                .name
+                .as_deref()
+                .is_some_and(|name| name.to_lowercase().contains(&self.query.to_lowercase()));
+        let owner_matches = !self.mine
+            || task
+                .started_by
+                .as_ref()
+                .and_then(|u| u.login.as_deref())
+                .is_some_and(|login| Some(login) == user);
+        name_matches && owner_matches && self.status.matches(task.status.as_deref())
+    }
+
+    /// The tasks passing the filters, in sort order; tasks without a time come last
+    pub fn apply(&self, tasks: &[NeoTask], user: Option<&str>) -> Vec<NeoTask> {
+        let mut shown: Vec<NeoTask> = tasks
+            .iter()
+            .filter(|task| self.matches(task, user))
+            .cloned()
+            .collect();
+        shown.sort_by(|a, b| match (self.sort.time(a), self.sort.time(b)) {
+            (Some(a), Some(b)) if self.ascending => a.cmp(b),
+            (Some(a), Some(b)) => b.cmp(a),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+        shown
+    }
+
+    /// Cycle the sort order: updated, newest first, then oldest first, then the same by creation
+    pub fn next_sort(&mut self) {
+        if self.ascending {
+            self.sort = match self.sort {
+                NeoTaskSort::Updated => NeoTaskSort::Created,
+                NeoTaskSort::Created => NeoTaskSort::Updated,
+            };
+        }
+        self.ascending = !self.ascending;
+    }
+}
+
 /// Platform sub-view selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlatformView {
@@ -219,6 +796,97 @@ impl PlatformView {
     }
 }
 
+/// Progress of answering the approval request Neo is waiting on.
+/// A request counts as answered only once the server accepted the decision,
+/// so a failed send shows the prompt again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NeoApprovalState {
+    /// Index of the approval request message the server accepted a decision for
+    answered: Option<usize>,
+    /// Index of the approval request a decision is being sent for
+    sending: Option<usize>,
+}
+
+impl NeoApprovalState {
+    /// Index of the approval request waiting on the user: the latest message,
+    /// unless it was answered or a decision for it is being sent
+    pub fn pending(&self, messages: &[NeoMessage]) -> Option<usize> {
+        let last = messages.len().checked_sub(1)?;
+        let waiting = messages[last].message_type == NeoMessageType::ApprovalRequest
+            && self.answered != Some(last)
+            && self.sending != Some(last);
+        waiting.then_some(last)
+    }
+
+    /// A decision for the request at `index` was sent
+    pub fn start_sending(&mut self, index: usize) {
+        self.sending = Some(index);
+    }
+
+    /// The server accepted (`ok`) or rejected the decision for the request at `index`
+    pub fn finish_sending(&mut self, index: usize, ok: bool) {
+        if self.sending == Some(index) {
+            self.sending = None;
+        }
+        if ok {
+            self.answered = Some(index);
+        }
+    }
+}
+
+/// Adaptive polling interval, measured in event loop ticks.
+/// Starts fast, doubles on every poll that brings nothing new, and drops back
+/// to the minimum as soon as new events arrive.
+#[derive(Debug, Clone)]
+pub struct PollBackoff {
+    min_ticks: u16,
+    max_ticks: u16,
+    interval: u16,
+    elapsed: u16,
+}
+
+impl PollBackoff {
+    pub fn new(min_ticks: u16, max_ticks: u16) -> Self {
+        Self {
+            min_ticks,
+            max_ticks,
+            interval: min_ticks,
+            elapsed: 0,
+        }
+    }
+
+    /// Advance one tick; returns true when a poll is due
+    pub fn tick(&mut self) -> bool {
+        self.elapsed = self.elapsed.saturating_add(1);
+        if self.elapsed >= self.interval {
+            self.elapsed = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Go back to the fastest interval
+    pub fn reset(&mut self) {
+        self.interval = self.min_ticks;
+        self.elapsed = 0;
+    }
+
+    /// Double the interval, up to the maximum
+    pub fn back_off(&mut self) {
+        self.interval = self.interval.saturating_mul(2).min(self.max_ticks);
+    }
+
+    /// Make the next tick poll immediately
+    pub fn trigger(&mut self) {
+        self.elapsed = self.interval;
+    }
+
+    pub fn interval(&self) -> u16 {
+        self.interval
+    }
+}
+
 /// Application state - holds all data fetched from APIs
 #[derive(Default)]
 pub struct AppState {
@@ -226,22 +894,31 @@ pub struct AppState {
     pub stacks: Vec<Stack>,
     pub esc_environments: Vec<EscEnvironmentSummary>,
     pub neo_tasks: Vec<NeoTask>,
-    pub resources: Vec<Resource>,
+    /// Total number of resources in the organization (for dashboard)
+    pub resource_count: u64,
     /// Recent stack updates across the organization (for dashboard)
     pub recent_updates: Vec<OrgStackUpdate>,
     /// Resource count over time (for dashboard chart)
     pub resource_summary: Vec<ResourceSummaryPoint>,
 
     // Selected stack details
-    pub selected_stack_updates: Vec<(i32, String, String)>,
+    /// Update opened from the update history (summary, changes, people)
+    pub selected_update_details: Option<UpdateDetails>,
+    /// Engine event log of the opened update
+    pub selected_update_events: Vec<UpdateEvent>,
+    /// Why the engine event log of the opened update could not be loaded
+    pub selected_update_events_error: Option<String>,
+    /// Cached syntax-highlighted inputs/outputs of the selected stack resource
+    pub selected_stack_resource_highlighted: Option<Vec<ratatui::text::Line<'static>>>,
 
     // Selected ESC env details
     pub selected_env_yaml: Option<String>,
+    /// If-Match value for saving the loaded definition (ETag or revision)
+    pub selected_env_etag: Option<String>,
     /// Cached syntax-highlighted lines for YAML definition (computed once when yaml changes)
     pub selected_env_yaml_highlighted: Option<Vec<ratatui::text::Line<'static>>>,
+    /// Response of opening the environment (resolved values with secret markers)
     pub selected_env_values: Option<serde_json::Value>,
-    /// Cached syntax-highlighted lines for resolved values (computed once when values change)
-    pub selected_env_values_highlighted: Option<Vec<ratatui::text::Line<'static>>>,
 
     // Neo conversation
     pub neo_messages: Vec<NeoMessage>,
@@ -257,4 +934,184 @@ pub struct AppState {
     // Organization
     pub organization: Option<String>,
     pub organizations: Vec<String>,
+    /// Login of the signed-in user
+    pub user_login: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticks_until_poll(backoff: &mut PollBackoff) -> u16 {
+        let mut ticks = 1;
+        while !backoff.tick() {
+            ticks += 1;
+        }
+        ticks
+    }
+
+    #[test]
+    fn poll_backoff_doubles_up_to_max_and_resets() {
+        let mut backoff = PollBackoff::new(5, 30);
+        assert_eq!(ticks_until_poll(&mut backoff), 5);
+
+        backoff.back_off();
+        assert_eq!(ticks_until_poll(&mut backoff), 10);
+        backoff.back_off();
+        backoff.back_off();
+        assert_eq!(backoff.interval(), 30);
+        backoff.back_off();
+        assert_eq!(ticks_until_poll(&mut backoff), 30);
+
+        backoff.reset();
+        assert_eq!(ticks_until_poll(&mut backoff), 5);
+    }
+
+    #[test]
+    fn resource_search_sort_by_toggles_direction_and_resets_page() {
+        let mut search = ResourceSearchState {
+            page: 3,
+            ..ResourceSearchState::default()
+        };
+
+        search.sort_by(ResourceSortColumn::Name);
+        assert!(!search.ascending);
+        assert_eq!(search.page, 1);
+
+        search.page = 2;
+        search.sort_by(ResourceSortColumn::Modified);
+        assert_eq!(search.sort, ResourceSortColumn::Modified);
+        assert!(search.ascending);
+        assert_eq!(search.page, 1);
+        assert_eq!(search.params().sort.as_deref(), Some("modified"));
+    }
+
+    #[test]
+    fn resource_search_paging_stops_at_last_page() {
+        let mut search = ResourceSearchState {
+            total: Some(RESOURCE_PAGE_SIZE as u64 + 1),
+            ..ResourceSearchState::default()
+        };
+        assert_eq!(search.page_count(), Some(2));
+
+        assert!(!search.previous_page());
+        assert!(search.next_page());
+        assert!(!search.next_page());
+        assert_eq!(search.page, 2);
+
+        // Without a total, follow the server's next-page hint
+        search.total = None;
+        search.has_more = true;
+        assert!(search.next_page());
+        assert_eq!(search.page, 3);
+    }
+
+    fn neo_tasks() -> Vec<NeoTask> {
+        serde_json::from_value(serde_json::json!([
+            {"id": "1", "name": "Fix prod stack", "status": "running",
+             "createdAt": "2024-05-01T10:00:00Z", "updatedAt": "2024-05-03T10:00:00Z",
+             "createdBy": {"login": "alice"}},
+            {"id": "2", "name": "Upgrade providers", "status": "idle",
+             "createdAt": "2024-05-02T10:00:00Z", "updatedAt": "2024-05-02T11:00:00Z",
+             "createdBy": {"login": "bob"}},
+            {"id": "3", "name": "Audit prod policies", "status": "cancelled",
+             "createdAt": "2024-04-30T10:00:00Z",
+             "createdBy": {"login": "alice"}},
+        ]))
+        .unwrap()
+    }
+
+    fn ids(tasks: Vec<NeoTask>) -> Vec<String> {
+        tasks.into_iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn neo_task_search_filters_by_name_status_and_owner() {
+        let tasks = neo_tasks();
+        let mut search = NeoTaskSearchState {
+            query: "PROD".to_string(),
+            ..NeoTaskSearchState::default()
+        };
+        assert_eq!(ids(search.apply(&tasks, Some("alice"))), vec!["1", "3"]);
+
+        search.status = NeoTaskStatusFilter::Cancelled;
+        assert_eq!(ids(search.apply(&tasks, Some("alice"))), vec!["3"]);
+
+        search.query.clear();
+        search.status = NeoTaskStatusFilter::All;
+        search.mine = true;
+        assert_eq!(ids(search.apply(&tasks, Some("bob"))), vec!["2"]);
+        // Without knowing who the user is, no task is theirs
+        assert!(search.apply(&tasks, None).is_empty());
+    }
+
+    #[test]
+    fn neo_task_search_sorts_by_time() {
+        let tasks = neo_tasks();
+        let mut search = NeoTaskSearchState::default();
+        // Updated, newest first; a task never updated sorts by creation
+        assert_eq!(ids(search.apply(&tasks, None)), vec!["1", "2", "3"]);
+
+        search.next_sort();
+        assert!(search.ascending);
+        assert_eq!(ids(search.apply(&tasks, None)), vec!["3", "2", "1"]);
+
+        search.next_sort();
+        assert_eq!(search.sort, NeoTaskSort::Created);
+        assert!(!search.ascending);
+        assert_eq!(ids(search.apply(&tasks, None)), vec!["2", "1", "3"]);
+
+        search.next_sort();
+        search.next_sort();
+        assert_eq!(search.sort, NeoTaskSort::Updated);
+        assert!(!search.ascending);
+    }
+
+    fn neo_message(message_type: NeoMessageType) -> NeoMessage {
+        NeoMessage {
+            role: String::new(),
+            content: String::new(),
+            message_type,
+            timestamp: None,
+            tool_calls: vec![],
+            tool_name: None,
+            tool_call_id: None,
+            approval_request_id: None,
+        }
+    }
+
+    #[test]
+    fn neo_approval_is_answered_only_once_accepted() {
+        let mut messages = vec![
+            neo_message(NeoMessageType::UserMessage),
+            neo_message(NeoMessageType::ApprovalRequest),
+        ];
+        let mut approval = NeoApprovalState::default();
+        assert_eq!(approval.pending(&messages), Some(1));
+
+        // Hidden while the decision is sent, and shown again when sending fails
+        approval.start_sending(1);
+        assert_eq!(approval.pending(&messages), None);
+        approval.finish_sending(1, false);
+        assert_eq!(approval.pending(&messages), Some(1));
+
+        approval.start_sending(1);
+        approval.finish_sending(1, true);
+        assert_eq!(approval.pending(&messages), None);
+
+        // A new request from Neo is pending again
+        messages.push(neo_message(NeoMessageType::AssistantMessage));
+        assert_eq!(approval.pending(&messages), None);
+        messages.push(neo_message(NeoMessageType::ApprovalRequest));
+        assert_eq!(approval.pending(&messages), Some(3));
+    }
+
+    #[test]
+    fn poll_backoff_trigger_polls_on_next_tick() {
+        let mut backoff = PollBackoff::new(5, 30);
+        backoff.back_off();
+        backoff.trigger();
+        assert!(backoff.tick());
+        assert_eq!(backoff.interval(), 10);
+    }
 }
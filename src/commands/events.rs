@@ -0,0 +1,228 @@
+//! Live resource progress from the Pulumi engine event log
+//!
+//! Streaming stack operations write their engine events to a JSON-lines
+//! file (`--event-log`). The executor tails that file and this module folds
+//! the parsed events into a per-resource status table for the output view.
+
+use std::collections::HashMap;
+
+use crate::api::{UpdateEvent, UpdateEventKind};
+
+/// Status of a single resource step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceStatus {
+    /// Step started, outputs not received yet
+    Running,
+    /// Step finished (or was planned, for previews)
+    Done,
+    /// Step failed
+    Failed,
+}
+
+/// A resource touched by the running operation
+#[derive(Debug, Clone)]
+pub struct ResourceProgress {
+    pub urn: String,
+    pub resource_type: String,
+    /// Latest step operation (create, update, delete, replace, same, ...)
+    pub op: String,
+    pub status: ResourceStatus,
+    /// Properties that changed
+    pub diffs: Vec<String>,
+}
+
+impl ResourceProgress {
+    /// Resource name (last segment of the URN)
+    pub fn name(&self) -> &str {
+        self.urn.rsplit("::").next().unwrap_or(&self.urn)
+    }
+
+    /// Whether the step leaves the resource untouched
+    pub fn is_unchanged(&self) -> bool {
+        self.op == "same"
+    }
+}
+
+/// Engine progress accumulated from event log entries
+#[derive(Debug, Clone, Default)]
+pub struct EngineProgress {
+    /// Resources in the order the engine first reported them
+    pub resources: Vec<ResourceProgress>,
+    /// Warning and error diagnostics
+    pub diagnostics: Vec<UpdateEvent>,
+    /// Summary line (duration and operation counts), once the engine finishes
+    pub summary: Option<String>,
+    index: HashMap<String, usize>,
+}
+
+impl EngineProgress {
+    /// Fold one engine event into the table
+    pub fn apply(&mut self, event: UpdateEvent) {
+        match event.kind {
+            UpdateEventKind::ResourcePre => self.record_step(event, ResourceStatus::Running),
+            UpdateEventKind::ResourceOutputs => self.record_step(event, ResourceStatus::Done),
+            UpdateEventKind::ResourceFailed => self.record_step(event, ResourceStatus::Failed),
+            UpdateEventKind::Diagnostic => {
+                let severity = event.severity.as_deref().unwrap_or_default();
+                if (severity.starts_with("warning") || severity.starts_with("error"))
+                    && !event.message.is_empty()
+                {
+                    self.diagnostics.push(event);
+                }
+            }
+            UpdateEventKind::Summary => {
+                self.summary = Some(event.message);
+            }
+            // Program output is already in the raw text view
+            UpdateEventKind::Stdout => {}
+        }
+    }
+
+    fn record_step(&mut self, event: UpdateEvent, status: ResourceStatus) {
+        let Some(urn) = event.urn else {
+            return;
+        };
+        let op = event.op.unwrap_or_default();
+        match self.index.get(&urn) {
+            Some(&i) => {
+                let resource = &mut self.resources[i];
+                // A replacement runs several steps; keep a failure visible until the end
+                if resource.status != ResourceStatus::Failed {
+                    resource.status = status;
+                }
+                if status == ResourceStatus::Running {
+                    resource.op = op;
+                }
+                if !event.diffs.is_empty() {
+                    resource.diffs = event.diffs;
+                }
+            }
+            None => {
+                self.index.insert(urn.clone(), self.resources.len());
+                self.resources.push(ResourceProgress {
+                    urn,
+                    resource_type: event.resource_type.unwrap_or_default(),
+                    op,
+                    status,
+                    diffs: event.diffs,
+                });
+            }
+        }
+    }
+
+    /// Resources with a pending or applied change
+    pub fn changed(&self) -> impl Iterator<Item = &ResourceProgress> {
+        self.resources.iter().filter(|r| !r.is_unchanged())
+    }
+
+    /// Number of resources the operation leaves untouched
+    pub fn unchanged_count(&self) -> usize {
+        self.resources.iter().filter(|r| r.is_unchanged()).count()
+    }
+
+    /// Count resources in the given status
+    pub fn count(&self, status: ResourceStatus) -> usize {
+        self.resources.iter().filter(|r| r.status == status).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::parse_engine_event;
+
+    /// `pulumi up --event-log` output for a stack creating a bucket and updating a role
+    const UP_EVENT_LOG: &str = r#"{"sequence":0,"timestamp":1718000000,"preludeEvent":{"config":{"aws:region":"eu-central-1"}}}
+{"sequence":1,"timestamp":1718000001,"resourcePreEvent":{"metadata":{"op":"same","urn":"urn:pulumi:dev::app::pulumi:pulumi:Stack::app-dev","type":"pulumi:pulumi:Stack","old":null,"new":null,"provider":""}}}
+{"sequence":2,"timestamp":1718000002,"resourcePreEvent":{"metadata":{"op":"create","urn":"urn:pulumi:dev::app::aws:s3/bucket:Bucket::assets","type":"aws:s3/bucket:Bucket","old":null,"new":null,"provider":"urn:pulumi:dev::app::pulumi:providers:aws::default_6_0_0::04da6b54"}}}
+{"sequence":3,"timestamp":1718000002,"resourcePreEvent":{"metadata":{"op":"update","urn":"urn:pulumi:dev::app::aws:iam/role:Role::runner","type":"aws:iam/role:Role","old":null,"new":null,"provider":"","diffs":["tags"]}}}
+{"sequence":4,"timestamp":1718000003,"diagnosticEvent":{"urn":"urn:pulumi:dev::app::aws:iam/role:Role::runner","prefix":"warning: ","message":"<{%fg 3%}>inline policy is deprecated<{%reset%}>\n","color":"raw","severity":"warning"}}
+{"sequence":5,"timestamp":1718000004,"resOutputsEvent":{"metadata":{"op":"create","urn":"urn:pulumi:dev::app::aws:s3/bucket:Bucket::assets","type":"aws:s3/bucket:Bucket","old":null,"new":null,"provider":""}}}
+{"sequence":6,"timestamp":1718000005,"resOutputsEvent":{"metadata":{"op":"update","urn":"urn:pulumi:dev::app::aws:iam/role:Role::runner","type":"aws:iam/role:Role","old":null,"new":null,"provider":"","diffs":["tags"]}}}
+{"sequence":7,"timestamp":1718000005,"diagnosticEvent":{"prefix":"","message":"debug chatter\n","color":"raw","severity":"debug"}}
+{"sequence":8,"timestamp":1718000006,"resOutputsEvent":{"metadata":{"op":"same","urn":"urn:pulumi:dev::app::pulumi:pulumi:Stack::app-dev","type":"pulumi:pulumi:Stack","old":null,"new":null,"provider":""}}}
+{"sequence":9,"timestamp":1718000006,"summaryEvent":{"maybeCorrupt":false,"durationSeconds":6,"resourceChanges":{"create":1,"same":1,"update":1},"policyPacks":{}}}
+{"sequence":10,"timestamp":1718000006,"cancelEvent":{}}"#;
+
+    /// `pulumi up --event-log` output for a replacement whose delete step fails
+    const FAILED_REPLACE_EVENT_LOG: &str = r#"{"sequence":0,"timestamp":1718000100,"resourcePreEvent":{"metadata":{"op":"create-replacement","urn":"urn:pulumi:dev::app::aws:ec2/instance:Instance::web","type":"aws:ec2/instance:Instance","old":null,"new":null,"provider":""}}}
+{"sequence":1,"timestamp":1718000110,"resOutputsEvent":{"metadata":{"op":"create-replacement","urn":"urn:pulumi:dev::app::aws:ec2/instance:Instance::web","type":"aws:ec2/instance:Instance","old":null,"new":null,"provider":""}}}
+{"sequence":2,"timestamp":1718000110,"resourcePreEvent":{"metadata":{"op":"delete-replaced","urn":"urn:pulumi:dev::app::aws:ec2/instance:Instance::web","type":"aws:ec2/instance:Instance","old":null,"new":null,"provider":""}}}
+{"sequence":3,"timestamp":1718000111,"diagnosticEvent":{"urn":"urn:pulumi:dev::app::aws:ec2/instance:Instance::web","prefix":"error: ","message":"deleting instance: UnauthorizedOperation\n","color":"raw","severity":"error"}}
+{"sequence":4,"timestamp":1718000111,"resOpFailedEvent":{"metadata":{"op":"delete-replaced","urn":"urn:pulumi:dev::app::aws:ec2/instance:Instance::web","type":"aws:ec2/instance:Instance","old":null,"new":null,"provider":""},"status":0,"steps":1}}
+{"sequence":5,"timestamp":1718000112,"summaryEvent":{"maybeCorrupt":false,"durationSeconds":12,"resourceChanges":{"create-replacement":1},"policyPacks":{}}}"#;
+
+    fn replay(log: &str) -> EngineProgress {
+        let mut progress = EngineProgress::default();
+        for event in log.lines().filter_map(parse_engine_event) {
+            progress.apply(event);
+        }
+        progress
+    }
+
+    #[test]
+    fn event_log_builds_resource_table() {
+        let progress = replay(UP_EVENT_LOG);
+
+        let rows: Vec<(&str, &str, ResourceStatus)> = progress
+            .resources
+            .iter()
+            .map(|r| (r.name(), r.op.as_str(), r.status))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("app-dev", "same", ResourceStatus::Done),
+                ("assets", "create", ResourceStatus::Done),
+                ("runner", "update", ResourceStatus::Done),
+            ]
+        );
+        assert_eq!(progress.changed().count(), 2);
+        assert_eq!(progress.unchanged_count(), 1);
+        assert_eq!(progress.resources[2].diffs, vec!["tags".to_string()]);
+
+        assert_eq!(progress.diagnostics.len(), 1);
+        assert_eq!(
+            progress.diagnostics[0].message,
+            "inline policy is deprecated"
+        );
+
+        assert_eq!(
+            progress.summary.as_deref(),
+            Some("Finished in 6s: 1 create, 1 same, 1 update")
+        );
+    }
+
+    #[test]
+    fn event_log_keeps_failed_replacement_visible() {
+        let progress = replay(FAILED_REPLACE_EVENT_LOG);
+
+        assert_eq!(progress.resources.len(), 1);
+        let web = &progress.resources[0];
+        assert_eq!(web.op, "delete-replaced");
+        assert_eq!(web.status, ResourceStatus::Failed);
+        assert_eq!(progress.count(ResourceStatus::Failed), 1);
+        assert_eq!(progress.count(ResourceStatus::Running), 0);
+        assert_eq!(
+            progress.diagnostics[0].message,
+            "deleting instance: UnauthorizedOperation"
+        );
+    }
+
+    #[test]
+    fn partial_and_unknown_lines_are_ignored() {
+        let mut progress = EngineProgress::default();
+        for line in [
+            r#"{"sequence":0,"timestamp":1,"resourcePreEvent":{"metadata":{"op":"cre"#,
+            "",
+            r#"{"sequence":1,"timestamp":1,"policyEvent":{"message":"ok"}}"#,
+        ] {
+            if let Some(event) = parse_engine_event(line) {
+                progress.apply(event);
+            }
+        }
+
+        assert!(progress.resources.is_empty());
+        assert!(progress.summary.is_none());
+    }
+}
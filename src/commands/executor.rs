@@ -2,31 +2,63 @@
 //!
 //! Handles running commands as subprocesses with streaming output.
 //! Uses a pseudo-TTY (PTY) to make Pulumi output properly stream.
-
-use portable_pty::{native_pty_system, CommandBuilder, PtySize};
-use std::io::{BufRead, BufReader};
+//! Stack operations can additionally write an engine event log, which is
+//! tailed alongside the text output to drive the live resource table.
+//! Commands that may prompt get their raw output forwarded to a terminal
+//! emulator and accept keystrokes through a [`CommandInput`] handle.
+
+#[cfg(unix)]
+use nix::{
+    sys::signal::{killpg, Signal},
+    unistd::Pid,
+};
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, PtySize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 
-use super::types::{CommandExecution, CommandExecutionState, OutputLine};
+use super::terminal::{CommandInput, PtyInput};
+use super::types::{CommandExecution, CommandExecutionState, OutputLine, ParamType};
+use crate::api::{parse_engine_event, UpdateEvent};
+
+/// How long a cancelled command may take to shut down before it is killed
+const CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(30);
 
 /// Result from command execution
 #[derive(Debug)]
 pub enum CommandResult {
     /// New output line received
     OutputLine(OutputLine),
+    /// Engine event read from the event log
+    EngineEvent(UpdateEvent),
+    /// Raw terminal output from an interactive command
+    TerminalOutput(Vec<u8>),
     /// Command completed
     Completed { exit_code: i32 },
     /// Command failed to start
     Failed(String),
 }
 
-/// Start executing a command and stream output using PTY
-pub fn spawn_command(execution: &CommandExecution, tx: mpsc::Sender<CommandResult>) {
+/// Start executing a command and stream output using PTY.
+/// `size` is the output pane as (rows, cols); the returned handle forwards
+/// keystrokes and later size changes to the command's terminal.
+pub fn spawn_command(
+    execution: &CommandExecution,
+    tx: mpsc::Sender<CommandResult>,
+    size: (u16, u16),
+) -> CommandInput {
     let args = execution.build_args();
     let display = execution.display_with_params();
     let cwd = execution.get_working_directory();
+    let event_log = execution.uses_event_log().then(event_log_path);
+    let interactive = execution.is_interactive();
+    let (input, input_rx) = CommandInput::channel(size);
 
     // Clone values for the spawned thread
     let args_clone = args.clone();
@@ -45,10 +77,10 @@ pub fn spawn_command(execution: &CommandExecution, tx: mpsc::Sender<CommandResul
             // Create PTY system
             let pty_system = native_pty_system();
 
-            // Create a PTY pair with reasonable size
+            // Create a PTY pair matching the output pane
             let pair = match pty_system.openpty(PtySize {
-                rows: 50,
-                cols: 200,
+                rows: size.0,
+                cols: size.1,
                 pixel_width: 0,
                 pixel_height: 0,
             }) {
@@ -67,6 +99,10 @@ pub fn spawn_command(execution: &CommandExecution, tx: mpsc::Sender<CommandResul
             for arg in &args_clone {
                 cmd.arg(arg);
             }
+            if let Some(ref path) = event_log {
+                cmd.arg("--event-log");
+                cmd.arg(path);
+            }
 
             // Set working directory if specified
             if let Some(ref dir) = cwd_clone {
@@ -76,8 +112,11 @@ pub fn spawn_command(execution: &CommandExecution, tx: mpsc::Sender<CommandResul
             // Set environment variables
             cmd.env("PULUMI_SKIP_UPDATE_CHECK", "true");
             // Don't set PULUMI_NON_INTERACTIVE - we want TTY behavior
-            // Use raw output mode to get machine-readable output
-            cmd.env("PULUMI_COLOR", "never");
+            // Use raw output mode to get machine-readable output, except in the
+            // terminal emulator where prompts rely on color for the selection
+            if !interactive {
+                cmd.env("PULUMI_COLOR", "never");
+            }
             cmd.env("PYTHONUNBUFFERED", "1");
             cmd.env("TERM", "xterm-256color");
 
@@ -108,9 +147,93 @@ pub fn spawn_command(execution: &CommandExecution, tx: mpsc::Sender<CommandResul
                 }
             };
 
+            let pid = child.process_id();
+            let killer = child.clone_killer();
+            let exited = Arc::new(AtomicBool::new(false));
+            let exited_input = exited.clone();
+
+            // Keep the master writable for keystrokes, resizes and signals until
+            // the app drops its input handle
+            let mut writer = match pair.master.take_writer() {
+                Ok(writer) => writer,
+                Err(e) => {
+                    let _ = sync_tx.send(CommandResult::Failed(format!(
+                        "Failed to get PTY writer: {}",
+                        e
+                    )));
+                    return;
+                }
+            };
+            let master = pair.master;
+            thread::spawn(move || {
+                while let Ok(input) = input_rx.recv() {
+                    match input {
+                        PtyInput::Bytes(bytes) => {
+                            if writer
+                                .write_all(&bytes)
+                                .and_then(|_| writer.flush())
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                        PtyInput::Resize { rows, cols } => {
+                            let _ = master.resize(PtySize {
+                                rows,
+                                cols,
+                                pixel_width: 0,
+                                pixel_height: 0,
+                            });
+                        }
+                        PtyInput::Interrupt => {
+                            if exited_input.load(Ordering::Acquire) {
+                                continue;
+                            }
+                            log::info!("Interrupting command (pid {:?})", pid);
+                            interrupt_command(pid, &mut writer);
+
+                            // Escalate if the graceful cancel does not finish in time
+                            let exited_timer = exited_input.clone();
+                            let mut timer_killer = killer.clone_killer();
+                            thread::spawn(move || {
+                                thread::sleep(CANCEL_GRACE_PERIOD);
+                                if !exited_timer.load(Ordering::Acquire) {
+                                    log::warn!(
+                                        "Command ignored interrupt; killing (pid {:?})",
+                                        pid
+                                    );
+                                    kill_command(pid, timer_killer.as_mut());
+                                }
+                            });
+                        }
+                        PtyInput::Kill => {
+                            if !exited_input.load(Ordering::Acquire) {
+                                log::warn!("Killing command (pid {:?})", pid);
+                                let mut kill_killer = killer.clone_killer();
+                                kill_command(pid, kill_killer.as_mut());
+                            }
+                        }
+                    }
+                }
+            });
+
+            // Follow the event log until the process exits
+            let event_tail = event_log.map(|path| {
+                let done = Arc::new(AtomicBool::new(false));
+                let done_tail = done.clone();
+                let sync_tx_events = sync_tx.clone();
+                let handle = thread::spawn(move || tail_event_log(path, done_tail, sync_tx_events));
+                (done, handle)
+            });
+
             // Read output in a separate thread
             let sync_tx_reader = sync_tx.clone();
             let reader_thread = thread::spawn(move || {
+                if interactive {
+                    forward_terminal_output(reader, &sync_tx_reader);
+                    return;
+                }
+
                 let buf_reader = BufReader::new(reader);
                 let mut last_line: Option<String> = None;
 
@@ -160,7 +283,16 @@ pub fn spawn_command(execution: &CommandExecution, tx: mpsc::Sender<CommandResul
             });
 
             // Wait for process to complete
-            match child.wait() {
+            let status = child.wait();
+            exited.store(true, Ordering::Release);
+
+            // Drain the rest of the event log so every event arrives before completion
+            if let Some((done, handle)) = event_tail {
+                done.store(true, Ordering::Release);
+                let _ = handle.join();
+            }
+
+            match status {
                 Ok(status) => {
                     // Wait for reader to finish
                     let _ = reader_thread.join();
@@ -193,6 +325,117 @@ pub fn spawn_command(execution: &CommandExecution, tx: mpsc::Sender<CommandResul
         // Wait for PTY thread to finish
         let _ = pty_thread.join();
     });
+
+    input
+}
+
+/// Send SIGINT to the command's process group, as Ctrl+C in a terminal would.
+/// Signalling directly also works while a prompt has the terminal in raw mode.
+#[cfg(unix)]
+fn interrupt_command(pid: Option<u32>, _writer: &mut Box<dyn Write + Send>) {
+    if let Some(pid) = pid {
+        if let Err(e) = killpg(Pid::from_raw(pid as i32), Signal::SIGINT) {
+            log::warn!("Failed to interrupt command: {}", e);
+        }
+    }
+}
+
+/// Send Ctrl+C through the console, which delivers the interrupt to the command
+#[cfg(not(unix))]
+fn interrupt_command(_pid: Option<u32>, writer: &mut Box<dyn Write + Send>) {
+    let _ = writer.write_all(b"\x03").and_then(|_| writer.flush());
+}
+
+/// Kill the command's whole process group so plugins do not outlive it
+#[cfg(unix)]
+fn kill_command(pid: Option<u32>, _killer: &mut dyn ChildKiller) {
+    if let Some(pid) = pid {
+        if let Err(e) = killpg(Pid::from_raw(pid as i32), Signal::SIGKILL) {
+            log::warn!("Failed to kill command: {}", e);
+        }
+    }
+}
+
+/// Terminate the command process
+#[cfg(not(unix))]
+fn kill_command(_pid: Option<u32>, killer: &mut dyn ChildKiller) {
+    if let Err(e) = killer.kill() {
+        log::warn!("Failed to kill command: {}", e);
+    }
+}
+
+/// Forward raw PTY output chunks for the terminal emulator.
+/// Prompts are not newline-terminated, so output cannot be read line by line.
+fn forward_terminal_output(mut reader: Box<dyn Read + Send>, tx: &std_mpsc::Sender<CommandResult>) {
+    let mut buf = [0u8; 4096];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                if tx
+                    .send(CommandResult::TerminalOutput(buf[..n].to_vec()))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Unique temp file for a command's engine event log
+fn event_log_path() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!(
+        "lazy-pulumi-events-{}-{}.jsonl",
+        std::process::id(),
+        nanos
+    ))
+}
+
+/// Tail the engine event log, forwarding each complete line as a parsed event.
+/// Pulumi creates the file lazily and appends as it goes, so partial lines are
+/// buffered until their newline arrives. The file is removed once drained.
+fn tail_event_log(path: PathBuf, done: Arc<AtomicBool>, tx: std_mpsc::Sender<CommandResult>) {
+    let mut file: Option<File> = None;
+    let mut pending: Vec<u8> = Vec::new();
+    let mut buf = [0u8; 8192];
+
+    'tail: loop {
+        // Read the flag first so the pass after exit picks up everything written
+        let finished = done.load(Ordering::Acquire);
+
+        if file.is_none() {
+            file = File::open(&path).ok();
+        }
+        if let Some(ref mut f) = file {
+            loop {
+                match f.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => pending.extend_from_slice(&buf[..n]),
+                }
+            }
+        }
+
+        while let Some(pos) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=pos).collect();
+            if let Some(event) = parse_engine_event(String::from_utf8_lossy(&line).trim()) {
+                if tx.send(CommandResult::EngineEvent(event)).is_err() {
+                    break 'tail;
+                }
+            }
+        }
+
+        if finished {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    let _ = std::fs::remove_file(&path);
 }
 
 /// Check if two lines are duplicate progress updates
@@ -309,18 +552,8 @@ fn strip_ansi_codes(text: &str) -> String {
     result.trim().to_string()
 }
 
-/// Check if the command can be run (not interactive)
+/// Check if the command can be run (required parameters are filled in)
 pub fn can_run_command(execution: &CommandExecution) -> Result<(), String> {
-    use super::types::ExecutionMode;
-
-    if execution.command.execution_mode == ExecutionMode::Interactive {
-        return Err(format!(
-            "Command '{}' requires interactive mode and cannot be run in the TUI. \
-             Please run it directly in your terminal.",
-            execution.command.name
-        ));
-    }
-
     // Check required parameters
     for param in execution.command.params {
         if param.required {
@@ -329,6 +562,13 @@ pub fn can_run_command(execution: &CommandExecution) -> Result<(), String> {
                 return Err(format!("Required parameter '{}' is missing", param.name));
             }
         }
+        if param.param_type == ParamType::CommandLine {
+            if let Some(value) = execution.param_values.get(param.name) {
+                if shell_words::split(value).is_err() {
+                    return Err(format!("Unterminated quote in '{}'", param.name));
+                }
+            }
+        }
     }
 
     Ok(())
@@ -347,17 +587,111 @@ pub fn update_execution_state(execution: &mut CommandExecution, result: CommandR
             }
             execution.output_lines.push(line);
         }
+        CommandResult::TerminalOutput(bytes) => {
+            if let Some(terminal) = execution.terminal.as_mut() {
+                terminal.process(&bytes);
+            }
+        }
+        CommandResult::EngineEvent(event) => {
+            execution
+                .engine
+                .get_or_insert_with(Default::default)
+                .apply(event);
+        }
         CommandResult::Completed { exit_code } => {
             execution.exit_code = Some(exit_code);
             if exit_code == 0 {
                 execution.state = CommandExecutionState::Completed;
+            } else if execution.cancel_requested {
+                execution.state = CommandExecutionState::Cancelled;
             } else {
                 execution.state =
                     CommandExecutionState::Failed(format!("Exit code: {}", exit_code));
             }
         }
         CommandResult::Failed(error) => {
-            execution.state = CommandExecutionState::Failed(error);
+            execution.state = if execution.cancel_requested {
+                CommandExecutionState::Cancelled
+            } else {
+                CommandExecutionState::Failed(error)
+            };
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::PULUMI_COMMANDS;
+
+    fn running_execution() -> CommandExecution {
+        let mut execution = CommandExecution::new(&PULUMI_COMMANDS[0]);
+        execution.state = CommandExecutionState::Running;
+        update_execution_state(
+            &mut execution,
+            CommandResult::OutputLine(OutputLine {
+                text: "Updating (dev)".to_string(),
+                is_error: false,
+                timestamp: std::time::Instant::now(),
+            }),
+        );
+        execution
+    }
+
+    #[test]
+    fn cancelled_command_keeps_partial_output() {
+        let mut execution = running_execution();
+        execution.cancel_requested = true;
+
+        update_execution_state(&mut execution, CommandResult::Completed { exit_code: 255 });
+
+        assert_eq!(execution.state, CommandExecutionState::Cancelled);
+        assert_eq!(execution.exit_code, Some(255));
+        assert_eq!(execution.output_lines.len(), 1);
+    }
+
+    #[test]
+    fn command_finishing_before_cancel_takes_effect_is_completed() {
+        let mut execution = running_execution();
+        execution.cancel_requested = true;
+
+        update_execution_state(&mut execution, CommandResult::Completed { exit_code: 0 });
+
+        assert_eq!(execution.state, CommandExecutionState::Completed);
+    }
+
+    fn env_run(command: &str) -> CommandExecution {
+        let env_run = PULUMI_COMMANDS
+            .iter()
+            .find(|c| c.name == "env run")
+            .unwrap();
+        let mut execution = CommandExecution::new(env_run);
+        execution
+            .param_values
+            .insert("environment".to_string(), "shared/aws".to_string());
+        execution
+            .param_values
+            .insert("command".to_string(), command.to_string());
+        execution
+    }
+
+    #[test]
+    fn env_run_command_keeps_quoted_arguments_together() {
+        let execution = env_run(r#"sh -c "echo $FOO""#);
+
+        assert!(can_run_command(&execution).is_ok());
+        assert!(execution
+            .build_args()
+            .ends_with(&["--", "sh", "-c", "echo $FOO"].map(str::to_string)));
+    }
+
+    #[test]
+    fn env_run_command_with_unterminated_quote_cannot_run() {
+        let execution = env_run(r#"sh -c "echo $FOO"#);
+
+        assert_eq!(
+            can_run_command(&execution),
+            Err("Unterminated quote in 'command'".to_string())
+        );
+    }
+}
@@ -3,8 +3,12 @@
 //! This module defines the Pulumi CLI commands available in the TUI
 //! and handles their execution with parameter dialogs and output streaming.
 
+mod events;
 mod executor;
+mod terminal;
 mod types;
 
+pub use events::*;
 pub use executor::*;
+pub use terminal::*;
 pub use types::*;
@@ -0,0 +1,226 @@
+//! Terminal emulation for interactive command sessions
+//!
+//! Commands that may prompt have their raw PTY output fed into a vt100
+//! parser, so prompts and cursor-addressed output render the way a real
+//! terminal would. Keystrokes from the output view are encoded here and
+//! written back to the PTY.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::mpsc as std_mpsc;
+
+/// Lines of scrollback kept for interactive sessions
+const SCROLLBACK_LINES: usize = 5000;
+
+/// Emulated terminal screen for an interactive command
+pub struct TerminalScreen {
+    parser: vt100::Parser,
+}
+
+impl fmt::Debug for TerminalScreen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TerminalScreen")
+            .field("size", &self.parser.screen().size())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TerminalScreen {
+    pub fn new(rows: u16, cols: u16) -> Self {
+        Self {
+            parser: vt100::Parser::new(rows, cols, SCROLLBACK_LINES),
+        }
+    }
+
+    /// Feed raw PTY output into the emulator
+    pub fn process(&mut self, bytes: &[u8]) {
+        self.parser.process(bytes);
+    }
+
+    pub fn screen(&self) -> &vt100::Screen {
+        self.parser.screen()
+    }
+
+    /// Current size as (rows, cols)
+    pub fn size(&self) -> (u16, u16) {
+        self.parser.screen().size()
+    }
+
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        self.parser.screen_mut().set_size(rows, cols);
+    }
+
+    /// Scroll back into history by the given number of lines
+    pub fn scroll_up(&mut self, lines: usize) {
+        let offset = self.parser.screen().scrollback() + lines;
+        self.parser.screen_mut().set_scrollback(offset);
+    }
+
+    /// Scroll towards the live screen by the given number of lines
+    pub fn scroll_down(&mut self, lines: usize) {
+        let offset = self.parser.screen().scrollback().saturating_sub(lines);
+        self.parser.screen_mut().set_scrollback(offset);
+    }
+
+    /// Jump to the oldest line of history
+    pub fn scroll_to_top(&mut self) {
+        // The screen clamps the offset to the available scrollback
+        self.parser.screen_mut().set_scrollback(usize::MAX);
+    }
+
+    /// Jump back to the live screen
+    pub fn scroll_to_bottom(&mut self) {
+        self.parser.screen_mut().set_scrollback(0);
+    }
+}
+
+/// Input for a running PTY session
+#[derive(Debug)]
+pub(super) enum PtyInput {
+    /// Bytes to write to the PTY master
+    Bytes(Vec<u8>),
+    /// New PTY size
+    Resize { rows: u16, cols: u16 },
+    /// Graceful stop (SIGINT), escalating to a kill if the command lingers
+    Interrupt,
+    /// Immediate kill (SIGKILL)
+    Kill,
+}
+
+/// Handle for sending keystrokes, size changes and signals to a running command
+#[derive(Debug, Clone)]
+pub struct CommandInput {
+    tx: std_mpsc::Sender<PtyInput>,
+    /// Last size sent to the PTY as (rows, cols)
+    size: Cell<(u16, u16)>,
+}
+
+impl CommandInput {
+    pub(super) fn channel(size: (u16, u16)) -> (Self, std_mpsc::Receiver<PtyInput>) {
+        let (tx, rx) = std_mpsc::channel();
+        let input = Self {
+            tx,
+            size: Cell::new(size),
+        };
+        (input, rx)
+    }
+
+    /// Write bytes to the command's terminal
+    pub fn write(&self, bytes: Vec<u8>) {
+        let _ = self.tx.send(PtyInput::Bytes(bytes));
+    }
+
+    /// Ask the command to stop, as Ctrl+C would; it is killed if still running after a grace period
+    pub fn interrupt(&self) {
+        let _ = self.tx.send(PtyInput::Interrupt);
+    }
+
+    /// Kill the command immediately
+    pub fn kill(&self) {
+        let _ = self.tx.send(PtyInput::Kill);
+    }
+
+    /// Resize the command's terminal; does nothing if the size is unchanged
+    pub fn resize(&self, rows: u16, cols: u16) {
+        if self.size.replace((rows, cols)) != (rows, cols) {
+            let _ = self.tx.send(PtyInput::Resize { rows, cols });
+        }
+    }
+}
+
+/// Encode a key press as the bytes a terminal would send for it
+pub fn encode_key(key: &KeyEvent, application_cursor: bool) -> Option<Vec<u8>> {
+    let cursor = |normal: &[u8], application: &[u8]| {
+        Some(
+            if application_cursor {
+                application
+            } else {
+                normal
+            }
+            .to_vec(),
+        )
+    };
+
+    match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            // Ctrl+A..Ctrl+Z map to 0x01..0x1a
+            let c = c.to_ascii_lowercase();
+            c.is_ascii_lowercase().then(|| vec![c as u8 - b'a' + 1])
+        }
+        KeyCode::Char(c) => {
+            let mut bytes = Vec::new();
+            if key.modifiers.contains(KeyModifiers::ALT) {
+                bytes.push(0x1b);
+            }
+            let mut buf = [0u8; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            Some(bytes)
+        }
+        KeyCode::Enter => Some(vec![b'\r']),
+        KeyCode::Tab => Some(vec![b'\t']),
+        KeyCode::BackTab => Some(b"\x1b[Z".to_vec()),
+        KeyCode::Backspace => Some(vec![0x7f]),
+        KeyCode::Esc => Some(vec![0x1b]),
+        KeyCode::Up => cursor(b"\x1b[A", b"\x1bOA"),
+        KeyCode::Down => cursor(b"\x1b[B", b"\x1bOB"),
+        KeyCode::Right => cursor(b"\x1b[C", b"\x1bOC"),
+        KeyCode::Left => cursor(b"\x1b[D", b"\x1bOD"),
+        KeyCode::Home => cursor(b"\x1b[H", b"\x1bOH"),
+        KeyCode::End => cursor(b"\x1b[F", b"\x1bOF"),
+        KeyCode::Delete => Some(b"\x1b[3~".to_vec()),
+        KeyCode::PageUp => Some(b"\x1b[5~".to_vec()),
+        KeyCode::PageDown => Some(b"\x1b[6~".to_vec()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn encode_key_maps_control_and_cursor_keys() {
+        assert_eq!(
+            encode_key(&key(KeyCode::Char('c'), KeyModifiers::CONTROL), false),
+            Some(vec![0x03])
+        );
+        assert_eq!(
+            encode_key(&key(KeyCode::Char('é'), KeyModifiers::NONE), false),
+            Some("é".as_bytes().to_vec())
+        );
+        assert_eq!(
+            encode_key(&key(KeyCode::Enter, KeyModifiers::NONE), false),
+            Some(vec![b'\r'])
+        );
+        assert_eq!(
+            encode_key(&key(KeyCode::Up, KeyModifiers::NONE), false),
+            Some(b"\x1b[A".to_vec())
+        );
+        assert_eq!(
+            encode_key(&key(KeyCode::Up, KeyModifiers::NONE), true),
+            Some(b"\x1bOA".to_vec())
+        );
+        assert_eq!(
+            encode_key(&key(KeyCode::F(1), KeyModifiers::NONE), false),
+            None
+        );
+    }
+
+    #[test]
+    fn terminal_screen_applies_cursor_addressing() {
+        let mut term = TerminalScreen::new(4, 40);
+        // A prompt redrawn in place, then a cursor-addressed status line
+        term.process(b"Do you want to perform this update? no\r\x1b[2K");
+        term.process(b"> yes\r\n\x1b[4;3Hdone");
+
+        let rows: Vec<String> = term.screen().rows(0, 40).collect();
+        assert_eq!(rows[0], "> yes");
+        assert_eq!(rows[3], "  done");
+        assert_eq!(term.screen().cursor_position(), (3, 6));
+    }
+}
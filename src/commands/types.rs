@@ -4,6 +4,9 @@
 
 use std::fmt;
 
+use super::events::EngineProgress;
+use super::terminal::TerminalScreen;
+
 /// Category of Pulumi commands
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CommandCategory {
@@ -92,6 +95,8 @@ pub enum ParamType {
     Secret,
     /// Multi-line text
     MultiLine,
+    /// Command line passed after `--`, split into words with shell quoting
+    CommandLine,
 }
 
 /// Execution mode for a command
@@ -101,7 +106,7 @@ pub enum ExecutionMode {
     Streaming,
     /// Command runs quickly and shows result
     Quick,
-    /// Command opens interactive mode (not supported in TUI)
+    /// Command prompts for input and runs in an emulated terminal
     Interactive,
 }
 
@@ -298,6 +303,17 @@ const PARAM_CWD: CommandParam = CommandParam {
     param_type: ParamType::FilePath,
 };
 
+/// Engine event log parameter (special - handled by the executor)
+const PARAM_EVENTS: CommandParam = CommandParam {
+    name: "events",
+    short: None,
+    long: None,
+    description: "Show live resource table from the engine event log",
+    required: false,
+    default: Some("true"),
+    param_type: ParamType::Flag,
+};
+
 /// Description for new project
 const PARAM_DESCRIPTION: CommandParam = CommandParam {
     name: "description",
@@ -320,6 +336,39 @@ const PARAM_GENERATE_ONLY: CommandParam = CommandParam {
     param_type: ParamType::Flag,
 };
 
+/// ESC environment parameter (positional)
+const PARAM_ENVIRONMENT: CommandParam = CommandParam {
+    name: "environment",
+    short: None,
+    long: None,
+    description: "Environment (org/project/name)",
+    required: true,
+    default: None,
+    param_type: ParamType::Text,
+};
+
+/// Interactive flag for env run (connects the command to the terminal)
+const PARAM_INTERACTIVE: CommandParam = CommandParam {
+    name: "interactive",
+    short: Some("-i"),
+    long: Some("--interactive"),
+    description: "Run interactively (output is not redacted)",
+    required: false,
+    default: Some("true"),
+    param_type: ParamType::Flag,
+};
+
+/// Command run by env run
+const PARAM_RUN_COMMAND: CommandParam = CommandParam {
+    name: "command",
+    short: None,
+    long: None,
+    description: "Command to run with the environment's variables",
+    required: true,
+    default: None,
+    param_type: ParamType::CommandLine,
+};
+
 // ─────────────────────────────────────────────────────────────
 // All Commands
 // ─────────────────────────────────────────────────────────────
@@ -338,6 +387,7 @@ pub static PULUMI_COMMANDS: &[PulumiCommand] = &[
             PARAM_MESSAGE,
             PARAM_TARGET,
             PARAM_DIFF,
+            PARAM_EVENTS,
         ],
         needs_confirmation: true,
         execution_mode: ExecutionMode::Streaming,
@@ -349,7 +399,7 @@ pub static PULUMI_COMMANDS: &[PulumiCommand] = &[
         cli_args: &["preview"],
         description: "Preview changes without deploying",
         category: CommandCategory::StackOperations,
-        params: &[PARAM_CWD, PARAM_STACK, PARAM_DIFF, PARAM_JSON],
+        params: &[PARAM_CWD, PARAM_STACK, PARAM_DIFF, PARAM_JSON, PARAM_EVENTS],
         needs_confirmation: false,
         execution_mode: ExecutionMode::Streaming,
         shortcut: Some('p'),
@@ -360,7 +410,13 @@ pub static PULUMI_COMMANDS: &[PulumiCommand] = &[
         cli_args: &["destroy"],
         description: "Destroy all infrastructure",
         category: CommandCategory::StackOperations,
-        params: &[PARAM_CWD, PARAM_STACK, PARAM_YES, PARAM_TARGET],
+        params: &[
+            PARAM_CWD,
+            PARAM_STACK,
+            PARAM_YES,
+            PARAM_TARGET,
+            PARAM_EVENTS,
+        ],
         needs_confirmation: true,
         execution_mode: ExecutionMode::Streaming,
         shortcut: Some('d'),
@@ -371,7 +427,7 @@ pub static PULUMI_COMMANDS: &[PulumiCommand] = &[
         cli_args: &["refresh"],
         description: "Refresh state from cloud provider",
         category: CommandCategory::StackOperations,
-        params: &[PARAM_CWD, PARAM_STACK, PARAM_YES],
+        params: &[PARAM_CWD, PARAM_STACK, PARAM_YES, PARAM_EVENTS],
         needs_confirmation: true,
         execution_mode: ExecutionMode::Streaming,
         shortcut: Some('r'),
@@ -543,7 +599,7 @@ pub static PULUMI_COMMANDS: &[PulumiCommand] = &[
             PARAM_YES,
         ],
         needs_confirmation: false,
-        execution_mode: ExecutionMode::Streaming,
+        execution_mode: ExecutionMode::Interactive,
         shortcut: Some('n'),
         supports_cwd: true,
     },
@@ -588,7 +644,7 @@ pub static PULUMI_COMMANDS: &[PulumiCommand] = &[
         category: CommandCategory::AuthOrg,
         params: &[],
         needs_confirmation: false,
-        execution_mode: ExecutionMode::Quick,
+        execution_mode: ExecutionMode::Interactive,
         shortcut: None,
         supports_cwd: false,
     },
@@ -670,6 +726,22 @@ pub static PULUMI_COMMANDS: &[PulumiCommand] = &[
         shortcut: None,
         supports_cwd: false,
     },
+    PulumiCommand {
+        name: "env run",
+        cli_args: &["env", "run"],
+        description: "Run a command with an ESC environment",
+        category: CommandCategory::Utilities,
+        params: &[
+            PARAM_CWD,
+            PARAM_ENVIRONMENT,
+            PARAM_INTERACTIVE,
+            PARAM_RUN_COMMAND,
+        ],
+        needs_confirmation: false,
+        execution_mode: ExecutionMode::Interactive,
+        shortcut: None,
+        supports_cwd: true,
+    },
     PulumiCommand {
         name: "state delete",
         cli_args: &["state", "delete"],
@@ -716,10 +788,12 @@ pub enum CommandExecutionState {
     Completed,
     /// Failed with error
     Failed(String),
+    /// Stopped by the user before it finished
+    Cancelled,
 }
 
 /// A command execution instance with parameters
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct CommandExecution {
     /// The command being executed
     pub command: &'static PulumiCommand,
@@ -731,6 +805,12 @@ pub struct CommandExecution {
     pub output_lines: Vec<OutputLine>,
     /// Exit code if completed
     pub exit_code: Option<i32>,
+    /// Resource progress parsed from the engine event log (structured mode only)
+    pub engine: Option<EngineProgress>,
+    /// Emulated terminal for commands that may prompt
+    pub terminal: Option<TerminalScreen>,
+    /// Whether the user asked to cancel the running command
+    pub cancel_requested: bool,
 }
 
 /// A line of command output
@@ -753,9 +833,32 @@ impl CommandExecution {
             state: CommandExecutionState::AwaitingInput,
             output_lines: Vec::new(),
             exit_code: None,
+            engine: None,
+            terminal: None,
+            cancel_requested: false,
         }
     }
 
+    /// Whether the command may prompt for input.
+    /// Commands with a `--yes` flag prompt for confirmation when it is left unset.
+    pub fn is_interactive(&self) -> bool {
+        self.command.execution_mode == ExecutionMode::Interactive
+            || (self.command.params.iter().any(|p| p.name == "yes")
+                && !self
+                    .param_values
+                    .get("yes")
+                    .is_some_and(|v| v == "true" || v == "yes"))
+    }
+
+    /// Whether the engine event log should be tailed for a live resource table
+    pub fn uses_event_log(&self) -> bool {
+        self.command.params.iter().any(|p| p.name == "events")
+            && self
+                .param_values
+                .get("events")
+                .is_some_and(|v| v == "true" || v == "yes")
+    }
+
     /// Get the working directory (defaults to current directory if empty or unspecified)
     pub fn get_working_directory(&self) -> Option<String> {
         self.param_values
@@ -770,7 +873,7 @@ impl CommandExecution {
     }
 
     /// Build the full command line arguments
-    /// Note: The "cwd" parameter is not included here as it's handled via current_dir()
+    /// Note: The "cwd" and "events" parameters are not included here as the executor handles them
     pub fn build_args(&self) -> Vec<String> {
         let mut args: Vec<String> = self
             .command
@@ -780,8 +883,8 @@ impl CommandExecution {
             .collect();
 
         for param in self.command.params {
-            // Skip cwd parameter - it's handled separately via current_dir()
-            if param.name == "cwd" {
+            // Skip cwd and events parameters - they're handled separately by the executor
+            if param.name == "cwd" || param.name == "events" {
                 continue;
             }
 
@@ -791,6 +894,15 @@ impl CommandExecution {
                 }
 
                 match param.param_type {
+                    ParamType::CommandLine => {
+                        args.push("--".to_string());
+                        // can_run_command rejects unbalanced quotes before a run;
+                        // the display falls back to plain words
+                        match shell_words::split(value) {
+                            Ok(words) => args.extend(words),
+                            Err(_) => args.extend(value.split_whitespace().map(str::to_string)),
+                        }
+                    }
                     ParamType::Flag => {
                         if value == "true" || value == "yes" {
                             if let Some(long) = param.long {
@@ -3,6 +3,14 @@
 use crate::event::keys;
 use crossterm::event::KeyEvent;
 
+/// A validation problem shown in the editor gutter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorDiagnostic {
+    /// 0-based line the problem is on, if known
+    pub line: Option<usize>,
+    pub message: String,
+}
+
 /// A multi-line text editor with cursor support
 #[derive(Debug, Clone)]
 pub struct TextEditor {
@@ -18,6 +26,8 @@ pub struct TextEditor {
     visible_height: usize,
     /// Whether the editor has been modified
     modified: bool,
+    /// Problems from the last validation
+    diagnostics: Vec<EditorDiagnostic>,
 }
 
 impl Default for TextEditor {
@@ -29,6 +39,7 @@ impl Default for TextEditor {
             scroll_offset: 0,
             visible_height: 20,
             modified: false,
+            diagnostics: Vec::new(),
         }
     }
 }
@@ -61,6 +72,7 @@ impl TextEditor {
             scroll_offset: 0,
             visible_height: 20,
             modified: false,
+            diagnostics: Vec::new(),
         }
     }
 
@@ -96,6 +108,33 @@ impl TextEditor {
         self.modified
     }
 
+    /// Flag content that was loaded from elsewhere (e.g. an old revision) as unsaved
+    pub fn mark_modified(&mut self) {
+        self.modified = true;
+    }
+
+    /// Replace the diagnostics shown in the gutter
+    pub fn set_diagnostics(&mut self, diagnostics: Vec<EditorDiagnostic>) {
+        self.diagnostics = diagnostics;
+    }
+
+    /// Diagnostics from the last validation
+    pub fn diagnostics(&self) -> &[EditorDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// First diagnostic on the given 0-based line
+    pub fn diagnostic_at(&self, line: usize) -> Option<&EditorDiagnostic> {
+        self.diagnostics.iter().find(|d| d.line == Some(line))
+    }
+
+    /// Move the cursor to the start of a 0-based line
+    pub fn go_to_line(&mut self, line: usize) {
+        self.row = line.min(self.lines.len() - 1);
+        self.col = 0;
+        self.ensure_cursor_visible();
+    }
+
     /// Get total line count
     #[allow(dead_code)]
     pub fn line_count(&self) -> usize {
@@ -5,7 +5,7 @@ mod input;
 mod list;
 mod spinner;
 
-pub use editor::TextEditor;
+pub use editor::{EditorDiagnostic, TextEditor};
 pub use input::TextInput;
 pub use list::StatefulList;
 pub use spinner::Spinner;
@@ -1,8 +1,11 @@
 //! Configuration management
 //!
-//! Handles persisting user preferences like splash screen settings.
+//! Handles persisting user preferences like splash screen settings,
+//! where Pulumi projects are checked out locally, the command run
+//! with ESC environments, and where Neo conversations are exported.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -12,6 +15,18 @@ pub struct Config {
     /// Whether to show the splash screen on startup
     #[serde(default = "default_show_splash")]
     pub show_splash: bool,
+    /// Local checkout paths keyed by `org/project` or just `project`,
+    /// used as the working directory when running commands on a stack
+    #[serde(default)]
+    pub project_dirs: BTreeMap<String, String>,
+    /// Command that `!` in the ESC view runs with the selected environment's
+    /// variables; defaults to `$SHELL`
+    #[serde(default)]
+    pub esc_run_command: Option<String>,
+    /// Directory Neo conversations are exported to; defaults to the
+    /// current directory
+    #[serde(default)]
+    pub neo_export_dir: Option<String>,
 }
 
 fn default_show_splash() -> bool {
@@ -20,7 +35,12 @@ fn default_show_splash() -> bool {
 
 impl Default for Config {
     fn default() -> Self {
-        Self { show_splash: true }
+        Self {
+            show_splash: true,
+            project_dirs: BTreeMap::new(),
+            esc_run_command: None,
+            neo_export_dir: None,
+        }
     }
 }
 
@@ -58,6 +78,37 @@ impl Config {
         Self::default()
     }
 
+    /// Resolve the local checkout of a project.
+    /// An `org/project` entry wins over a bare `project` entry; `~/` expands to the home directory.
+    pub fn project_dir(&self, org: &str, project: &str) -> Option<String> {
+        let dir = self
+            .project_dirs
+            .get(&format!("{}/{}", org, project))
+            .or_else(|| self.project_dirs.get(project))?;
+
+        expand_home(dir)
+    }
+
+    /// Command to run with an ESC environment: `esc_run_command`, else `$SHELL`, else `sh`
+    pub fn esc_run_command(&self) -> String {
+        self.esc_run_command
+            .clone()
+            .filter(|command| !command.trim().is_empty())
+            .or_else(|| std::env::var("SHELL").ok())
+            .unwrap_or_else(|| "sh".to_string())
+    }
+
+    /// Directory to export Neo conversations to: `neo_export_dir`, else the
+    /// current directory; `~/` expands to the home directory
+    pub fn neo_export_dir(&self) -> PathBuf {
+        self.neo_export_dir
+            .as_deref()
+            .filter(|dir| !dir.trim().is_empty())
+            .and_then(expand_home)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
     /// Save configuration to file
     pub fn save(&self) {
         let path = Self::config_path();
@@ -74,3 +125,73 @@ impl Config {
         }
     }
 }
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(dir: &str) -> Option<String> {
+    match dir.strip_prefix("~/") {
+        Some(rest) => directories::BaseDirs::new()
+            .map(|dirs| dirs.home_dir().join(rest).to_string_lossy().into_owned()),
+        None => Some(dir.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_dir_prefers_org_qualified_entry() {
+        let config = Config {
+            project_dirs: BTreeMap::from([
+                ("infra".to_string(), "/src/infra".to_string()),
+                ("acme/infra".to_string(), "/work/acme-infra".to_string()),
+            ]),
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.project_dir("acme", "infra").as_deref(),
+            Some("/work/acme-infra")
+        );
+        assert_eq!(
+            config.project_dir("other", "infra").as_deref(),
+            Some("/src/infra")
+        );
+        assert_eq!(config.project_dir("acme", "web"), None);
+    }
+
+    #[test]
+    fn esc_run_command_prefers_configured_command() {
+        let config = Config {
+            esc_run_command: Some("zsh -l".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(config.esc_run_command(), "zsh -l");
+
+        // A blank entry falls back to the login shell
+        let blank = Config {
+            esc_run_command: Some("  ".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(blank.esc_run_command(), Config::default().esc_run_command());
+    }
+
+    #[test]
+    fn neo_export_dir_defaults_to_current_directory() {
+        assert_eq!(Config::default().neo_export_dir(), PathBuf::from("."));
+
+        let config = Config {
+            neo_export_dir: Some("/tmp/neo".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(config.neo_export_dir(), PathBuf::from("/tmp/neo"));
+    }
+
+    #[test]
+    fn config_without_project_dirs_still_loads() {
+        let config: Config = serde_json::from_str(r#"{"show_splash": false}"#).unwrap();
+
+        assert!(!config.show_splash);
+        assert!(config.project_dirs.is_empty());
+    }
+}
@@ -366,6 +366,7 @@ pub mod symbols {
     pub const CROSS_MARK: &str = "✗";
     pub const STAR: &str = "★";
     pub const DIAMOND: &str = "◆";
+    pub const CANCELLED: &str = "⊘";
 
     pub const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
@@ -8,15 +8,15 @@ use ratatui::{
     prelude::*,
     text::{Line, Span},
     widgets::{
-        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, Wrap,
+        Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Table, Wrap,
     },
 };
 use tui_scrollview::ScrollViewState;
 
 use crate::commands::{
-    commands_by_category, CommandCategory, CommandExecution, CommandExecutionState, ExecutionMode,
-    PulumiCommand,
+    commands_by_category, CommandCategory, CommandExecution, CommandExecutionState, EngineProgress,
+    ExecutionMode, PulumiCommand, ResourceStatus, TerminalScreen,
 };
 use crate::components::{StatefulList, TextInput};
 use crate::theme::{symbols, Theme};
@@ -32,6 +32,10 @@ pub struct CommandsViewProps<'a> {
     pub output_scroll: &'a mut ScrollViewState,
     pub filter_input: &'a TextInput,
     pub is_filtering: bool,
+    /// Show raw text output instead of the live resource table
+    pub show_raw_output: bool,
+    /// Receives the output pane size (rows, cols) so the PTY can match it
+    pub terminal_size: &'a mut (u16, u16),
 }
 
 /// Props for rendering the sidebar
@@ -86,16 +90,24 @@ pub fn render_commands_view(
         },
     );
 
-    // Right panel: command details or output
-    render_main_panel(
-        frame,
-        theme,
-        main_chunks[1],
-        props.view_state,
-        props.command_list.selected().copied(),
-        props.current_execution,
-        props.output_scroll,
-    );
+    // Right panel: output while in output view, otherwise command details
+    match props.current_execution {
+        Some(exec) if props.view_state == CommandsViewState::OutputView => render_output_view(
+            frame,
+            theme,
+            main_chunks[1],
+            exec,
+            props.output_scroll,
+            props.show_raw_output,
+            props.terminal_size,
+        ),
+        _ => render_command_details(
+            frame,
+            theme,
+            main_chunks[1],
+            props.command_list.selected().copied(),
+        ),
+    }
 
     // Overlay dialogs
     if props.view_state == CommandsViewState::InputDialog {
@@ -363,28 +375,6 @@ fn render_commands_list(
     frame.render_stateful_widget(list, area, &mut command_list.state);
 }
 
-/// Render the main panel (details or output)
-fn render_main_panel(
-    frame: &mut Frame,
-    theme: &Theme,
-    area: Rect,
-    view_state: CommandsViewState,
-    selected_command: Option<&'static PulumiCommand>,
-    current_execution: Option<&CommandExecution>,
-    output_scroll: &mut ScrollViewState,
-) {
-    // If we're in output view, show the output
-    if view_state == CommandsViewState::OutputView {
-        if let Some(exec) = current_execution {
-            render_output_view(frame, theme, area, exec, output_scroll);
-            return;
-        }
-    }
-
-    // Otherwise, show command details
-    render_command_details(frame, theme, area, selected_command);
-}
-
 /// Render command details panel
 fn render_command_details(
     frame: &mut Frame,
@@ -432,7 +422,7 @@ fn render_command_details(
                         match cmd.execution_mode {
                             ExecutionMode::Streaming => "Streaming output",
                             ExecutionMode::Quick => "Quick execution",
-                            ExecutionMode::Interactive => "Interactive (not supported)",
+                            ExecutionMode::Interactive => "Interactive terminal",
                         },
                         match cmd.execution_mode {
                             ExecutionMode::Interactive => theme.warning(),
@@ -492,7 +482,7 @@ fn render_command_details(
 
             // Hint at bottom
             let hint = if cmd.execution_mode == ExecutionMode::Interactive {
-                "This command requires interactive input and cannot run in the TUI"
+                "Press Enter to run; keystrokes are sent to the command while it runs"
             } else if cmd.needs_confirmation {
                 "Press Enter to configure and run (requires confirmation)"
             } else {
@@ -526,6 +516,8 @@ fn render_output_view(
     area: Rect,
     execution: &CommandExecution,
     scroll_state: &mut ScrollViewState,
+    show_raw_output: bool,
+    terminal_size: &mut (u16, u16),
 ) {
     // Split into header and output
     let chunks = Layout::default()
@@ -542,27 +534,54 @@ fn render_output_view(
         CommandExecutionState::Running => theme.warning(),
         CommandExecutionState::Completed => theme.success(),
         CommandExecutionState::Failed(_) => theme.error(),
+        CommandExecutionState::Cancelled => theme.warning(),
         _ => theme.text(),
     };
 
     let status_text = match &execution.state {
+        CommandExecutionState::Running if execution.cancel_requested => {
+            "Cancelling... (press again to kill)".to_string()
+        }
         CommandExecutionState::Running => "Running...".to_string(),
         CommandExecutionState::Completed => {
             format!("Completed (exit: {})", execution.exit_code.unwrap_or(0))
         }
         CommandExecutionState::Failed(e) => format!("Failed: {}", e),
+        CommandExecutionState::Cancelled => match execution.exit_code {
+            Some(code) => format!("Cancelled (exit: {})", code),
+            None => "Cancelled".to_string(),
+        },
         _ => "".to_string(),
     };
 
+    let mut status_spans = vec![
+        Span::styled("Status: ", theme.text_secondary()),
+        Span::styled(status_text, status_style),
+    ];
+    if let Some(engine) = &execution.engine {
+        status_spans.extend([
+            Span::styled("  |  ", theme.text_muted()),
+            Span::styled(
+                format!("{} running ", engine.count(ResourceStatus::Running)),
+                theme.warning(),
+            ),
+            Span::styled(
+                format!("{} done ", engine.count(ResourceStatus::Done)),
+                theme.success(),
+            ),
+            Span::styled(
+                format!("{} failed", engine.count(ResourceStatus::Failed)),
+                theme.error(),
+            ),
+        ]);
+    }
+
     let header_lines = vec![
         Line::from(vec![
             Span::styled("$ ", theme.primary()),
             Span::styled(execution.display_with_params(), theme.text()),
         ]),
-        Line::from(vec![
-            Span::styled("Status: ", theme.text_secondary()),
-            Span::styled(status_text, status_style),
-        ]),
+        Line::from(status_spans),
     ];
 
     let header_block = Block::default()
@@ -575,6 +594,71 @@ fn render_output_view(
     frame.render_widget(header_block, chunks[0]);
     frame.render_widget(Paragraph::new(header_lines), header_inner);
 
+    // The PTY follows the size of the output pane inside its borders
+    let pane = chunks[1].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    *terminal_size = (pane.height, pane.width);
+
+    let is_running = execution.state == CommandExecutionState::Running;
+    let scroll_hint = if let Some(engine) = execution.engine.as_ref().filter(|_| !show_raw_output) {
+        render_engine_progress(frame, theme, chunks[1], engine, scroll_state)
+    } else if let Some(terminal) = &execution.terminal {
+        render_terminal_output(frame, theme, chunks[1], terminal, is_running)
+    } else {
+        render_raw_output(frame, theme, chunks[1], execution, scroll_state)
+    };
+    let view_hint = if execution.engine.is_none() {
+        ""
+    } else if show_raw_output {
+        " | v: resources"
+    } else {
+        " | v: raw output"
+    };
+
+    let cancel_hint = if execution.cancel_requested {
+        "force kill"
+    } else {
+        "cancel"
+    };
+    let status_bar = match &execution.state {
+        CommandExecutionState::Running if execution.terminal.is_some() => {
+            format!(
+                "Keys are sent to the command | Ctrl+C: {}{}",
+                cancel_hint, scroll_hint
+            )
+        }
+        CommandExecutionState::Running => {
+            format!(
+                "j/k: scroll | G: bottom | X: {}{}{}",
+                cancel_hint, view_hint, scroll_hint
+            )
+        }
+        CommandExecutionState::Completed
+        | CommandExecutionState::Failed(_)
+        | CommandExecutionState::Cancelled => {
+            format!(
+                "j/k: scroll | g/G: top/bottom{} | Esc: close{}",
+                view_hint, scroll_hint
+            )
+        }
+        _ => String::new(),
+    };
+    let status = Paragraph::new(status_bar)
+        .style(theme.text_muted())
+        .alignment(Alignment::Center);
+    frame.render_widget(status, chunks[2]);
+}
+
+/// Render the command's text output; returns the scroll position hint
+fn render_raw_output(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    execution: &CommandExecution,
+    scroll_state: &mut ScrollViewState,
+) -> String {
     // Output area
     let output_block = Block::default()
         .borders(Borders::ALL)
@@ -582,8 +666,8 @@ fn render_output_view(
         .title(" Output ")
         .title_style(theme.subtitle());
 
-    let output_inner = output_block.inner(chunks[1]);
-    frame.render_widget(output_block, chunks[1]);
+    let output_inner = output_block.inner(area);
+    frame.render_widget(output_block, area);
 
     // Render output lines
     let output_lines: Vec<Line> = execution
@@ -630,7 +714,7 @@ fn render_output_view(
 
         frame.render_stateful_widget(
             scrollbar,
-            chunks[1].inner(Margin {
+            area.inner(Margin {
                 vertical: 1,
                 horizontal: 0,
             }),
@@ -638,8 +722,8 @@ fn render_output_view(
         );
     }
 
-    // Status bar with scroll hints
-    let scroll_hint = if total_lines > visible_height {
+    // Scroll hint for the status bar
+    if total_lines > visible_height {
         format!(
             " | Line {}-{}/{}",
             scroll_offset + 1,
@@ -648,19 +732,254 @@ fn render_output_view(
         )
     } else {
         String::new()
-    };
+    }
+}
 
-    let status_bar = match &execution.state {
-        CommandExecutionState::Running => format!("j/k: scroll | G: bottom{}", scroll_hint),
-        CommandExecutionState::Completed | CommandExecutionState::Failed(_) => {
-            format!("j/k: scroll | g/G: top/bottom | Esc: close{}", scroll_hint)
+/// Render an interactive command's emulated terminal; returns the scroll position hint
+fn render_terminal_output(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    terminal: &TerminalScreen,
+    is_running: bool,
+) -> String {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(" Terminal ")
+        .title_style(theme.subtitle());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let screen = terminal.screen();
+    let (rows, cols) = screen.size();
+    let lines: Vec<Line> = (0..rows.min(inner.height))
+        .map(|row| {
+            let mut spans: Vec<Span> = Vec::new();
+            let mut text = String::new();
+            let mut style = Style::default();
+            for col in 0..cols.min(inner.width) {
+                let Some(cell) = screen.cell(row, col) else {
+                    continue;
+                };
+                if cell.is_wide_continuation() {
+                    continue;
+                }
+                let cell_style = terminal_cell_style(cell);
+                if cell_style != style && !text.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut text), style));
+                }
+                style = cell_style;
+                if cell.has_contents() {
+                    text.push_str(cell.contents());
+                } else {
+                    text.push(' ');
+                }
+            }
+            if !text.is_empty() {
+                spans.push(Span::styled(text, style));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+
+    // Show the cursor where the command expects input
+    let scrollback = screen.scrollback();
+    if is_running && scrollback == 0 && !screen.hide_cursor() {
+        let (row, col) = screen.cursor_position();
+        if row < inner.height && col < inner.width {
+            frame.set_cursor_position((inner.x + col, inner.y + row));
         }
-        _ => String::new(),
+    }
+
+    if scrollback > 0 {
+        format!(" | Scrolled back {} lines", scrollback)
+    } else {
+        String::new()
+    }
+}
+
+/// Map a vt100 cell's colors and attributes onto a ratatui style
+fn terminal_cell_style(cell: &vt100::Cell) -> Style {
+    let color = |color: vt100::Color| match color {
+        vt100::Color::Default => None,
+        vt100::Color::Idx(i) => Some(Color::Indexed(i)),
+        vt100::Color::Rgb(r, g, b) => Some(Color::Rgb(r, g, b)),
     };
-    let status = Paragraph::new(status_bar)
-        .style(theme.text_muted())
-        .alignment(Alignment::Center);
-    frame.render_widget(status, chunks[2]);
+
+    let mut style = Style::default();
+    if let Some(fg) = color(cell.fgcolor()) {
+        style = style.fg(fg);
+    }
+    if let Some(bg) = color(cell.bgcolor()) {
+        style = style.bg(bg);
+    }
+    let mut modifiers = Modifier::empty();
+    modifiers.set(Modifier::BOLD, cell.bold());
+    modifiers.set(Modifier::DIM, cell.dim());
+    modifiers.set(Modifier::ITALIC, cell.italic());
+    modifiers.set(Modifier::UNDERLINED, cell.underline());
+    modifiers.set(Modifier::REVERSED, cell.inverse());
+    style.add_modifier(modifiers)
+}
+
+/// Render the live resource table and diagnostics; returns the scroll position hint
+fn render_engine_progress(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    engine: &EngineProgress,
+    scroll_state: &mut ScrollViewState,
+) -> String {
+    // Diagnostics take up to six lines below the table
+    let diagnostics_height = if engine.diagnostics.is_empty() {
+        0
+    } else {
+        engine.diagnostics.len().min(6) as u16 + 2
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(diagnostics_height)])
+        .split(area);
+
+    let unchanged = engine.unchanged_count();
+    let title = if unchanged > 0 {
+        format!(" Resources ({} unchanged) ", unchanged)
+    } else {
+        " Resources ".to_string()
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(title)
+        .title_style(theme.subtitle());
+    if let Some(summary) = &engine.summary {
+        block = block.title_bottom(Line::styled(format!(" {} ", summary), theme.success()));
+    }
+
+    let changed: Vec<_> = engine.changed().collect();
+    let inner = block.inner(chunks[0]);
+    // Header row and its margin
+    let visible_height = inner.height.saturating_sub(2) as usize;
+    let total_rows = changed.len();
+    let scroll_offset =
+        (scroll_state.offset().y as usize).min(total_rows.saturating_sub(visible_height));
+
+    if changed.is_empty() {
+        frame.render_widget(block, chunks[0]);
+        let waiting = if engine.summary.is_some() {
+            "No resource changes"
+        } else {
+            "Waiting for engine events..."
+        };
+        let empty = Paragraph::new(waiting)
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+    } else {
+        let rows: Vec<Row> = changed
+            .iter()
+            .skip(scroll_offset)
+            .take(visible_height)
+            .map(|resource| {
+                let (icon, icon_style) = match resource.status {
+                    ResourceStatus::Running => (symbols::ARROW_RIGHT, theme.warning()),
+                    ResourceStatus::Done => (symbols::CHECK, theme.success()),
+                    ResourceStatus::Failed => (symbols::CROSS_MARK, theme.error()),
+                };
+                Row::new(vec![
+                    Cell::from(Span::styled(icon, icon_style)),
+                    Cell::from(Span::styled(
+                        resource.op.clone(),
+                        op_style(theme, &resource.op),
+                    )),
+                    Cell::from(Span::styled(
+                        resource.resource_type.clone(),
+                        theme.text_secondary(),
+                    )),
+                    Cell::from(resource.name().to_string()),
+                    Cell::from(Span::styled(resource.diffs.join(", "), theme.text_muted())),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(2),
+                Constraint::Length(18),
+                Constraint::Percentage(35),
+                Constraint::Percentage(30),
+                Constraint::Min(8),
+            ],
+        )
+        .header(
+            Row::new(vec!["", "Op", "Type", "Name", "Changes"])
+                .style(theme.subtitle())
+                .bottom_margin(1),
+        )
+        .block(block);
+        frame.render_widget(table, chunks[0]);
+    }
+
+    if diagnostics_height > 0 {
+        let skip = engine.diagnostics.len().saturating_sub(6);
+        let lines: Vec<Line> = engine.diagnostics[skip..]
+            .iter()
+            .map(|diag| {
+                let severity = diag.severity.as_deref().unwrap_or("info");
+                let style = if severity.starts_with("error") {
+                    theme.error()
+                } else {
+                    theme.warning()
+                };
+                let mut spans = vec![Span::styled(format!("{}: ", severity), style)];
+                if let Some(name) = diag.resource_name() {
+                    spans.push(Span::styled(format!("[{}] ", name), theme.text_secondary()));
+                }
+                spans.push(Span::styled(
+                    diag.message.lines().next().unwrap_or_default().to_string(),
+                    theme.text(),
+                ));
+                Line::from(spans)
+            })
+            .collect();
+        let diagnostics = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.border())
+                .title(format!(" Diagnostics ({}) ", engine.diagnostics.len()))
+                .title_style(theme.subtitle()),
+        );
+        frame.render_widget(diagnostics, chunks[1]);
+    }
+
+    if total_rows > visible_height {
+        format!(
+            " | Row {}-{}/{}",
+            scroll_offset + 1,
+            (scroll_offset + visible_height).min(total_rows),
+            total_rows
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Style for a step operation (create, update, delete, replace, ...)
+fn op_style(theme: &Theme, op: &str) -> Style {
+    if op.contains("replace") {
+        theme.accent()
+    } else if op.starts_with("create") {
+        theme.success()
+    } else if op.starts_with("delete") || op.starts_with("discard") {
+        theme.error()
+    } else if op == "update" {
+        theme.warning()
+    } else {
+        theme.text_secondary()
+    }
 }
 
 /// Colorize Pulumi output based on content
@@ -110,7 +110,7 @@ fn render_stats_cards(frame: &mut Frame, theme: &Theme, area: Rect, state: &AppS
     );
 
     // Resources card
-    let resource_count = state.resources.len();
+    let resource_count = state.resource_count;
     render_stat_card(
         frame,
         theme,
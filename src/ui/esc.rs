@@ -8,11 +8,18 @@ use ratatui::{
         Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
     },
 };
+use std::collections::HashSet;
+
 use tui_scrollview::{ScrollView, ScrollViewState};
 
-use crate::api::EscEnvironmentSummary;
-use crate::app::EscPane;
-use crate::components::StatefulList;
+use crate::api::{
+    EscChangeRequest, EscEnvironmentSummary, EscRevision, EscRotationEvent, EscSchedule,
+};
+use crate::app::{
+    EscChangeAction, EscChangeView, EscEnvDialog, EscExportFormat, EscGraphMark, EscGraphNode,
+    EscGraphRow, EscPane, EscRevisionPrompt, EscRotationPane, EscRotator,
+};
+use crate::components::{StatefulList, TextInput};
 use crate::theme::{symbols, Theme};
 
 /// Props for rendering the ESC view
@@ -22,11 +29,69 @@ pub struct EscViewProps<'a> {
     /// Cached syntax-highlighted lines for YAML (avoids re-highlighting on every frame)
     pub selected_env_yaml_highlighted: Option<&'a Vec<Line<'static>>>,
     pub selected_env_values: Option<&'a serde_json::Value>,
-    /// Cached syntax-highlighted lines for resolved values (avoids re-highlighting on every frame)
-    pub selected_env_values_highlighted: Option<&'a Vec<Line<'static>>>,
+    /// Resolved values flattened into rows, with secrets masked unless revealed
+    pub values: &'a mut StatefulList<EscValue>,
+    /// Paths of revealed secrets
+    pub revealed_values: &'a HashSet<String>,
+    /// What was last copied from the selected value ("path" or "value")
+    pub value_copied: Option<&'static str>,
+    /// File the environment's variables were last exported to
+    pub exported: Option<&'a str>,
     pub focused_pane: EscPane,
     pub definition_scroll: &'a mut ScrollViewState,
-    pub values_scroll: &'a mut ScrollViewState,
+    /// Revision history, shown in place of the details while open
+    pub revisions: Option<EscRevisionsProps<'a>>,
+    /// Import graph, shown in place of the details while open
+    pub graph: Option<EscGraphProps<'a>>,
+    /// Secret rotation, shown in place of the details while open
+    pub rotation: Option<EscRotationProps<'a>>,
+    /// Open change requests, shown in place of the details while open
+    pub change_requests: Option<EscChangeRequestsProps<'a>>,
+}
+
+/// Props for rendering the import graph pane
+pub struct EscGraphProps<'a> {
+    /// Environment the graph is rooted at (`project/name`)
+    pub root: String,
+    pub rows: &'a mut StatefulList<EscGraphRow>,
+}
+
+/// Props for rendering the change requests pane
+pub struct EscChangeRequestsProps<'a> {
+    pub requests: &'a mut StatefulList<EscChangeRequest>,
+    pub view: EscChangeView,
+    /// Highlighted diff of the selected request against the current definition
+    pub content: Option<&'a [Line<'static>]>,
+    /// Values the selected request's draft resolves to
+    pub values: Option<&'a [EscValue]>,
+    pub scroll: &'a mut ScrollViewState,
+    pub confirm: Option<EscChangeAction>,
+    /// Description input while submitting a draft
+    pub prompt: Option<&'a TextInput>,
+}
+
+/// Props for rendering the secret rotation pane
+pub struct EscRotationProps<'a> {
+    /// Environment the rotators belong to (`project/name`)
+    pub env: String,
+    pub rotators: &'a mut StatefulList<EscRotator>,
+    pub schedules: &'a mut StatefulList<EscSchedule>,
+    /// Rotation runs, newest first
+    pub history: &'a [EscRotationEvent],
+    pub focus: EscRotationPane,
+    /// Rotator paths waiting for confirmation; empty means all of them
+    pub confirm: Option<&'a [String]>,
+}
+
+/// Props for rendering the revisions pane
+pub struct EscRevisionsProps<'a> {
+    pub revisions: &'a mut StatefulList<EscRevision>,
+    /// Revision marked as the left side of a diff
+    pub diff_base: Option<i64>,
+    /// Highlighted YAML (or diff against the base) of the selected revision
+    pub content: Option<&'a [Line<'static>]>,
+    pub scroll: &'a mut ScrollViewState,
+    pub prompt: Option<(EscRevisionPrompt, &'a TextInput)>,
 }
 
 /// Builder for rendering scrollable panes
@@ -101,11 +166,13 @@ struct EnvironmentDetailsProps<'a> {
     selected: Option<&'a EscEnvironmentSummary>,
     yaml: Option<&'a str>,
     yaml_highlighted: Option<&'a Vec<Line<'static>>>,
-    values: Option<&'a serde_json::Value>,
-    values_highlighted: Option<&'a Vec<Line<'static>>>,
+    values_loaded: bool,
+    values: &'a mut StatefulList<EscValue>,
+    revealed_values: &'a HashSet<String>,
+    value_copied: Option<&'static str>,
+    exported: Option<&'a str>,
     focused_pane: EscPane,
     definition_scroll: &'a mut ScrollViewState,
-    values_scroll: &'a mut ScrollViewState,
 }
 
 /// Render the ESC environments view
@@ -116,6 +183,22 @@ pub fn render_esc_view(frame: &mut Frame, theme: &Theme, area: Rect, props: EscV
         .split(area);
 
     render_environments_list(frame, theme, chunks[0], props.environments);
+    if let Some(change_requests) = props.change_requests {
+        render_change_requests_pane(frame, theme, chunks[1], change_requests);
+        return;
+    }
+    if let Some(rotation) = props.rotation {
+        render_rotation_pane(frame, theme, chunks[1], rotation);
+        return;
+    }
+    if let Some(graph) = props.graph {
+        render_graph_pane(frame, theme, chunks[1], graph);
+        return;
+    }
+    if let Some(revisions) = props.revisions {
+        render_revisions_pane(frame, theme, chunks[1], revisions);
+        return;
+    }
     render_environment_details(
         frame,
         theme,
@@ -124,11 +207,13 @@ pub fn render_esc_view(frame: &mut Frame, theme: &Theme, area: Rect, props: EscV
             selected: props.environments.selected(),
             yaml: props.selected_env_yaml,
             yaml_highlighted: props.selected_env_yaml_highlighted,
-            values: props.selected_env_values,
-            values_highlighted: props.selected_env_values_highlighted,
+            values_loaded: props.selected_env_values.is_some(),
+            values: props.values,
+            revealed_values: props.revealed_values,
+            value_copied: props.value_copied,
+            exported: props.exported,
             focused_pane: props.focused_pane,
             definition_scroll: props.definition_scroll,
-            values_scroll: props.values_scroll,
         },
     );
 }
@@ -320,62 +405,294 @@ fn filter_trace_info(value: &serde_json::Value) -> serde_json::Value {
     }
 }
 
-/// Convert JSON value to YAML string
-pub fn json_to_yaml(value: &serde_json::Value) -> String {
-    // Use serde_yaml if available, otherwise format manually
-    match serde_json::to_value(value) {
-        Ok(v) => format_as_yaml(&v, 0),
-        Err(_) => "Error converting to YAML".to_string(),
+/// Placeholder shown for a masked secret (fixed width, so it does not leak the length)
+const SECRET_MASK: &str = "••••••••";
+
+/// One row of the Resolved Values pane: a property and its resolved value
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscValue {
+    /// Property path, e.g. `environmentVariables.AWS_ACCESS_KEY_ID` or `hosts[0]`
+    pub path: String,
+    /// Nesting depth, for indentation
+    pub depth: usize,
+    /// Key (or `-` for array items)
+    pub label: String,
+    /// Resolved value, with trace metadata removed
+    pub value: serde_json::Value,
+    /// Marked `secret: true`, or inside a secret
+    pub secret: bool,
+    /// Where the value was defined, followed by the definitions it overrode
+    pub sources: Vec<EscValueSource>,
+}
+
+/// A definition of a value, from the property's `trace`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscValueSource {
+    /// Environment containing the definition, e.g. `shared/aws-base`
+    pub environment: String,
+    /// 1-based line and column of the definition
+    pub position: Option<(u64, u64)>,
+    /// Function that produced the value, e.g. `fn::open::aws-login`
+    pub function: Option<String>,
+    pub value: serde_json::Value,
+    pub secret: bool,
+}
+
+impl EscValue {
+    /// Whether the value has its own rows below it
+    pub fn is_container(&self) -> bool {
+        match &self.value {
+            serde_json::Value::Object(obj) => !obj.is_empty(),
+            serde_json::Value::Array(arr) => !arr.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Whether this value, or one of its parents, has been revealed
+    pub fn is_revealed(&self, revealed: &HashSet<String>) -> bool {
+        revealed.iter().any(|path| {
+            self.path == *path
+                || self
+                    .path
+                    .strip_prefix(path.as_str())
+                    .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+        })
+    }
+
+    /// Text put on the clipboard: strings as-is, everything else as JSON
+    pub fn clipboard_text(&self) -> String {
+        match &self.value {
+            serde_json::Value::String(s) => s.clone(),
+            value if self.is_container() => serde_json::to_string_pretty(value).unwrap_or_default(),
+            value => value.to_string(),
+        }
     }
 }
 
-/// Format a JSON value as YAML-like string
-fn format_as_yaml(value: &serde_json::Value, indent: usize) -> String {
-    let indent_str = "  ".repeat(indent);
-    match value {
-        serde_json::Value::Object(obj) => {
-            if obj.is_empty() {
-                return "{}".to_string();
+/// Flatten an ESC open response into rows, keeping the `secret` markers of
+/// the property tree so secret leaves can be masked
+pub fn flatten_values(values: &serde_json::Value) -> Vec<EscValue> {
+    let mut rows = Vec::new();
+    match values.get("properties").and_then(|p| p.as_object()) {
+        Some(properties) => {
+            for (key, prop) in properties {
+                if key != "executionContext" {
+                    flatten_property(
+                        &mut rows,
+                        key_path("", key),
+                        key.clone(),
+                        0,
+                        prop,
+                        false,
+                        None,
+                    );
+                }
             }
-            let mut lines = Vec::new();
-            for (key, val) in obj {
-                match val {
-                    serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
-                        let nested = format_as_yaml(val, indent + 1);
-                        if nested.starts_with('{') || nested.starts_with('[') {
-                            lines.push(format!("{}{}: {}", indent_str, key, nested));
-                        } else {
-                            lines.push(format!("{}{}:", indent_str, key));
-                            lines.push(nested);
-                        }
-                    }
-                    _ => {
-                        lines.push(format!("{}{}: {}", indent_str, key, format_scalar(val)));
-                    }
+
+            // Expressions cover the opened environment only, not its imports
+            if let Some(exprs) = values.get("exprs").and_then(|e| e.as_object()) {
+                let sources = rows.iter_mut().flat_map(|row| row.sources.iter_mut());
+                for source in sources {
+                    let Some(position) = source.position else {
+                        continue;
+                    };
+                    source.function = exprs
+                        .values()
+                        .find_map(|expr| function_at(expr, &source.environment, position));
                 }
             }
-            lines.join("\n")
         }
-        serde_json::Value::Array(arr) => {
-            if arr.is_empty() {
-                return "[]".to_string();
-            }
-            let mut lines = Vec::new();
-            for item in arr {
-                match item {
-                    serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
-                        let nested = format_as_yaml(item, indent + 1);
-                        lines.push(format!("{}- ", indent_str));
-                        lines.push(nested);
-                    }
-                    _ => {
-                        lines.push(format!("{}- {}", indent_str, format_scalar(item)));
-                    }
+        // No property tree (and so no secret markers): show the plain values
+        None => {
+            if let serde_json::Value::Object(obj) = extract_values(values) {
+                for (key, value) in &obj {
+                    flatten_property(
+                        &mut rows,
+                        key_path("", key),
+                        key.clone(),
+                        0,
+                        value,
+                        false,
+                        None,
+                    );
                 }
             }
-            lines.join("\n")
         }
-        _ => format!("{}{}", indent_str, format_scalar(value)),
+    }
+    rows
+}
+
+/// Add a row for a property (a `{ "value", "secret", "trace" }` wrapper or
+/// a plain value) and rows for its children
+fn flatten_property(
+    rows: &mut Vec<EscValue>,
+    path: String,
+    label: String,
+    depth: usize,
+    prop: &serde_json::Value,
+    parent_secret: bool,
+    parent_source: Option<&EscValueSource>,
+) {
+    let (inner, secret) = match prop.as_object().and_then(|obj| obj.get("value")) {
+        Some(inner) => (
+            inner,
+            parent_secret || prop.get("secret").and_then(|s| s.as_bool()) == Some(true),
+        ),
+        None => (prop, parent_secret),
+    };
+    let value = extract_property_value(inner);
+
+    let mut sources = trace_sources(prop, parent_secret);
+    // Provider outputs carry no trace of their own; they come from the parent's definition
+    if sources.is_empty() {
+        if let Some(parent) = parent_source {
+            sources.push(EscValueSource {
+                value: value.clone(),
+                secret,
+                ..parent.clone()
+            });
+        }
+    }
+    let source = sources.first().cloned();
+
+    rows.push(EscValue {
+        path: path.clone(),
+        depth,
+        label,
+        value,
+        secret,
+        sources,
+    });
+
+    match inner {
+        serde_json::Value::Object(obj) => {
+            for (key, child) in obj {
+                let child_path = key_path(&path, key);
+                flatten_property(
+                    rows,
+                    child_path,
+                    key.clone(),
+                    depth + 1,
+                    child,
+                    secret,
+                    source.as_ref(),
+                );
+            }
+        }
+        serde_json::Value::Array(arr) => {
+            for (i, child) in arr.iter().enumerate() {
+                let child_path = format!("{}[{}]", path, i);
+                flatten_property(
+                    rows,
+                    child_path,
+                    "-".to_string(),
+                    depth + 1,
+                    child,
+                    secret,
+                    source.as_ref(),
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Follow a property's `trace.def` and `trace.base` chain: the definition
+/// that won, then each definition it overrode (e.g. from imports)
+fn trace_sources(prop: &serde_json::Value, parent_secret: bool) -> Vec<EscValueSource> {
+    let mut sources = Vec::new();
+    let mut current = Some(prop);
+    while let Some(prop) = current {
+        let Some(trace) = prop.get("trace") else {
+            break;
+        };
+        let def = trace.get("def");
+        let environment = def
+            .and_then(|d| d.get("environment"))
+            .and_then(|e| e.as_str())
+            .unwrap_or_default();
+        // Values computed at runtime have no source range
+        if !environment.is_empty() {
+            let begin = def.and_then(|d| d.get("begin"));
+            let coordinate = |name: &str| begin.and_then(|b| b.get(name)).and_then(|v| v.as_u64());
+            sources.push(EscValueSource {
+                environment: environment.to_string(),
+                position: coordinate("line").zip(coordinate("column")),
+                function: None,
+                value: extract_property_value(
+                    prop.get("value").unwrap_or(&serde_json::Value::Null),
+                ),
+                secret: parent_secret || prop.get("secret").and_then(|s| s.as_bool()) == Some(true),
+            });
+        }
+        current = trace.get("base").filter(|base| base.is_object());
+    }
+    sources
+}
+
+/// Name of the innermost function call (`fn::open`, `fn::secret`, ...) in
+/// the environment's expressions whose range contains the position
+fn function_at(
+    expr: &serde_json::Value,
+    environment: &str,
+    position: (u64, u64),
+) -> Option<String> {
+    if let Some(range) = expr.get("range") {
+        let point = |name: &str| {
+            let p = range.get(name)?;
+            Some((p.get("line")?.as_u64()?, p.get("column")?.as_u64()?))
+        };
+        let in_environment = range.get("environment").and_then(|e| e.as_str()) == Some(environment);
+        match (point("begin"), point("end")) {
+            (Some(begin), Some(end)) if in_environment && begin <= position && position <= end => {}
+            _ => return None,
+        }
+    }
+
+    let builtin = expr.get("builtin");
+    let children = expr
+        .get("object")
+        .and_then(|o| o.as_object())
+        .into_iter()
+        .flat_map(|o| o.values())
+        .chain(
+            expr.get("list")
+                .and_then(|l| l.as_array())
+                .into_iter()
+                .flatten(),
+        )
+        .chain(builtin.and_then(|b| b.get("arg")));
+    for child in children {
+        if let Some(name) = function_at(child, environment, position) {
+            return Some(name);
+        }
+    }
+
+    let builtin = builtin?;
+    let name = builtin.get("name")?.as_str()?;
+    // `fn::open` takes the provider as an argument
+    let provider = builtin
+        .get("arg")
+        .and_then(|arg| arg.get("object"))
+        .and_then(|o| o.get("provider"))
+        .and_then(|p| p.get("literal"))
+        .and_then(|l| l.as_str());
+    Some(match provider {
+        Some(provider) if name == "fn::open" => format!("fn::open::{}", provider),
+        _ => name.to_string(),
+    })
+}
+
+/// Append a key to a property path, quoting keys that are not plain identifiers
+fn key_path(parent: &str, key: &str) -> String {
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    match (plain, parent.is_empty()) {
+        (true, true) => key.to_string(),
+        (true, false) => format!("{}.{}", parent, key),
+        (false, _) => format!("{}[{:?}]", parent, key),
     }
 }
 
@@ -487,28 +804,232 @@ fn render_environment_details(
         .render(frame, theme, content_chunks[0]);
 
     // Resolved values pane
-    let is_values_focused = props.focused_pane == EscPane::ResolvedValues;
-    // Only compute values_content if we don't have cached highlighted lines
-    let values_content = if props.values_highlighted.is_none() {
-        props.values.map(|v| {
-            let filtered = extract_values(v);
-            json_to_yaml(&filtered)
-        })
-    } else {
-        None
-    };
-    let values_hint = if props.selected.is_some() {
+    let values_hint = if props.values_loaded {
+        "No values"
+    } else if props.selected.is_some() {
         "Press 'o' to open & resolve"
     } else {
         "Select an environment"
     };
-    ScrollablePaneBuilder::new(" Resolved Values ", props.values_scroll)
-        .content(values_content)
-        .highlighted(props.values_highlighted) // Use cached highlighted lines
-        .focused(is_values_focused)
-        .hint(values_hint)
-        .syntax_highlight(true)
-        .render(frame, theme, content_chunks[1]);
+    render_values_pane(
+        frame,
+        theme,
+        content_chunks[1],
+        ValuesPaneProps {
+            values: props.values,
+            revealed: props.revealed_values,
+            copied: props.value_copied,
+            exported: props.exported,
+            is_focused: props.focused_pane == EscPane::ResolvedValues,
+            hint: values_hint,
+        },
+    );
+}
+
+struct ValuesPaneProps<'a> {
+    values: &'a mut StatefulList<EscValue>,
+    revealed: &'a HashSet<String>,
+    copied: Option<&'static str>,
+    exported: Option<&'a str>,
+    is_focused: bool,
+    hint: &'a str,
+}
+
+/// Render resolved values as an indented tree, one selectable row per property
+fn render_values_pane(frame: &mut Frame, theme: &Theme, area: Rect, props: ValuesPaneProps<'_>) {
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(if props.is_focused {
+            theme.border_focused()
+        } else {
+            theme.border()
+        })
+        .title(" Resolved Values ")
+        .title_style(if props.is_focused {
+            theme.title()
+        } else {
+            theme.subtitle()
+        });
+    if let Some(what) = props.copied {
+        block = block.title_bottom(
+            Line::styled(
+                format!(" {} Copied {} ", symbols::CHECK, what),
+                theme.success(),
+            )
+            .right_aligned(),
+        );
+    } else if let Some(path) = props.exported {
+        block = block.title_bottom(
+            Line::styled(
+                format!(" {} Exported to {} ", symbols::CHECK, path),
+                theme.success(),
+            )
+            .right_aligned(),
+        );
+    } else if props.is_focused && !props.values.is_empty() {
+        block = block.title_bottom(Line::from(vec![
+            Span::styled(" Space", theme.key_hint()),
+            Span::styled(": reveal | ", theme.key_desc()),
+            Span::styled("y", theme.key_hint()),
+            Span::styled(": copy value | ", theme.key_desc()),
+            Span::styled("Y", theme.key_hint()),
+            Span::styled(": copy path ", theme.key_desc()),
+        ]));
+    }
+
+    if props.values.is_empty() {
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let empty = Paragraph::new(props.hint)
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+        return;
+    }
+
+    let items: Vec<ListItem> = props
+        .values
+        .items()
+        .iter()
+        .map(|row| {
+            let mut spans = vec![
+                Span::raw("  ".repeat(row.depth)),
+                Span::styled(
+                    if row.label == "-" {
+                        "- ".to_string()
+                    } else {
+                        format!("{}: ", row.label)
+                    },
+                    theme.primary(),
+                ),
+            ];
+            if !row.is_container() {
+                let masked = row.secret && !row.is_revealed(props.revealed);
+                spans.push(value_span(theme, &row.value, masked));
+            }
+            if row.secret && (row.is_container() || row.is_revealed(props.revealed)) {
+                spans.push(Span::styled(" (secret)", theme.warning()));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    // Provenance of the selected value below the list
+    let provenance = props
+        .values
+        .selected()
+        .filter(|_| props.is_focused)
+        .map(|row| provenance_lines(theme, row, props.revealed));
+    let (list_area, provenance_area) = match provenance {
+        Some(ref lines) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(3),
+                    Constraint::Length(lines.len() as u16 + 2),
+                ])
+                .split(area);
+            (chunks[0], Some(chunks[1]))
+        }
+        None => (area, None),
+    };
+
+    let list = List::new(items).block(block);
+    let list = if props.is_focused {
+        list.highlight_style(theme.selected())
+    } else {
+        list
+    };
+    frame.render_stateful_widget(list, list_area, &mut props.values.state);
+
+    if let (Some(lines), Some(provenance_area)) = (provenance, provenance_area) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.border())
+            .title(" Provenance ")
+            .title_style(theme.subtitle());
+        frame.render_widget(Paragraph::new(lines).block(block), provenance_area);
+    }
+}
+
+/// A resolved value as a styled span; containers are abbreviated
+fn value_span(theme: &Theme, value: &serde_json::Value, masked: bool) -> Span<'static> {
+    if masked {
+        return Span::styled(SECRET_MASK, theme.text_muted());
+    }
+    match value {
+        serde_json::Value::Object(obj) if !obj.is_empty() => {
+            Span::styled("{…}", theme.text_muted())
+        }
+        serde_json::Value::Array(arr) if !arr.is_empty() => Span::styled("[…]", theme.text_muted()),
+        serde_json::Value::String(_) => Span::styled(format_scalar(value), theme.text()),
+        serde_json::Value::Null => Span::styled("null", theme.text_muted()),
+        _ => Span::styled(format_scalar(value), theme.accent()),
+    }
+}
+
+/// Where the selected value was defined, and which definitions it overrode
+fn provenance_lines(
+    theme: &Theme,
+    row: &EscValue,
+    revealed: &HashSet<String>,
+) -> Vec<Line<'static>> {
+    /// Overridden definitions shown before summarizing the rest
+    const MAX_OVERRIDES: usize = 4;
+
+    let location = |source: &EscValueSource| {
+        let mut spans = vec![Span::styled(source.environment.clone(), theme.highlight())];
+        if let Some((line, column)) = source.position {
+            spans.push(Span::styled(
+                format!(" line {}, col {}", line, column),
+                theme.text_muted(),
+            ));
+        }
+        if let Some(ref function) = source.function {
+            spans.push(Span::styled(format!("  {}", function), theme.primary()));
+        }
+        spans
+    };
+
+    let Some((definition, overrides)) = row.sources.split_first() else {
+        return vec![Line::styled(
+            "No source recorded (computed value)",
+            theme.text_muted(),
+        )];
+    };
+
+    let mut defined = vec![Span::styled("Defined in  ", theme.text_secondary())];
+    defined.extend(location(definition));
+    let mut lines = vec![Line::from(defined)];
+    let revealed = row.is_revealed(revealed);
+    for (i, source) in overrides.iter().take(MAX_OVERRIDES).enumerate() {
+        let mut spans = vec![Span::styled(
+            if i == 0 {
+                "Overrides   "
+            } else {
+                "            "
+            },
+            theme.text_secondary(),
+        )];
+        spans.extend(location(source));
+        spans.push(Span::styled(
+            format!(" {} ", symbols::ARROW_RIGHT),
+            theme.text_muted(),
+        ));
+        spans.push(value_span(
+            theme,
+            &source.value,
+            (row.secret || source.secret) && !revealed,
+        ));
+        lines.push(Line::from(spans));
+    }
+    if overrides.len() > MAX_OVERRIDES {
+        lines.push(Line::styled(
+            format!("            … and {} more", overrides.len() - MAX_OVERRIDES),
+            theme.text_muted(),
+        ));
+    }
+    lines
 }
 
 /// Internal implementation for rendering scrollable panes
@@ -648,6 +1169,7 @@ pub fn render_esc_editor(
         ""
     };
     let title = format!(" Edit: {} {}", env_name, modified_indicator);
+    let diagnostics = editor.diagnostics();
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -656,7 +1178,7 @@ pub fn render_esc_editor(
         .title_style(theme.title())
         .title_bottom(Line::from(vec![
             Span::styled(" Esc", theme.key_hint()),
-            Span::styled(": Save & Close | ", theme.key_desc()),
+            Span::styled(": Check & Save | ", theme.key_desc()),
             Span::styled("Ctrl+C", theme.key_hint()),
             Span::styled(": Cancel | ", theme.key_desc()),
             Span::styled("Tab", theme.key_hint()),
@@ -665,11 +1187,34 @@ pub fn render_esc_editor(
             Span::styled(": Delete line ", theme.key_desc()),
         ]));
 
-    let inner = block.inner(dialog_area);
+    let mut inner = block.inner(dialog_area);
     frame.render_widget(block, dialog_area);
 
-    // Split inner area: line numbers | editor content | scrollbar
-    let line_number_width = 4u16; // "999 " format
+    // Reserve the last row for the diagnostic on (or nearest to) the cursor line
+    if !diagnostics.is_empty() && inner.height > 1 {
+        let status_area = Rect::new(inner.x, inner.bottom() - 1, inner.width, 1);
+        inner.height -= 1;
+
+        let (cursor_row, _) = editor.cursor();
+        let current = editor.diagnostic_at(cursor_row).unwrap_or(&diagnostics[0]);
+        let location = current
+            .line
+            .map(|l| format!("line {}: ", l + 1))
+            .unwrap_or_default();
+        let more = match diagnostics.len() {
+            1 => String::new(),
+            n => format!("  ({} problems)", n),
+        };
+        let status = Line::from(vec![
+            Span::styled(format!(" {} ", symbols::CROSS_MARK), theme.error()),
+            Span::styled(format!("{}{}", location, current.message), theme.error()),
+            Span::styled(more, theme.text_muted()),
+        ]);
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+
+    // Split inner area: gutter (diagnostic marker + line numbers) | editor content | scrollbar
+    let line_number_width = 5u16; // "●999 " format
     let editor_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -693,14 +1238,21 @@ pub fn render_esc_editor(
     let line_numbers: Vec<Line> = (scroll_offset..scroll_offset + visible_height)
         .map(|i| {
             if i < total_lines {
-                let style = if i == cursor_row {
+                let has_diagnostic = editor.diagnostic_at(i).is_some();
+                let style = if has_diagnostic {
+                    theme.error()
+                } else if i == cursor_row {
                     theme.highlight()
                 } else {
                     theme.text_muted()
                 };
-                Line::from(Span::styled(format!("{:>3} ", i + 1), style))
+                let marker = if has_diagnostic { "●" } else { " " };
+                Line::from(vec![
+                    Span::styled(marker, theme.error()),
+                    Span::styled(format!("{:>3} ", i + 1), style),
+                ])
             } else {
-                Line::from(Span::styled("    ", theme.text_muted()))
+                Line::from(Span::styled("     ", theme.text_muted()))
             }
         })
         .collect();
@@ -756,3 +1308,1372 @@ pub fn render_esc_editor(
         frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
     }
 }
+
+fn render_graph_pane(frame: &mut Frame, theme: &Theme, area: Rect, props: EscGraphProps<'_>) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(format!(" Import Graph: {} ", props.root))
+        .title_style(theme.title())
+        .title_bottom(Line::from(vec![
+            Span::styled(" Enter", theme.key_hint()),
+            Span::styled(": graph of selected env | ", theme.key_desc()),
+            Span::styled("Esc", theme.key_hint()),
+            Span::styled(": close ", theme.key_desc()),
+        ]));
+
+    let rows = props.rows.items();
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut spans = vec![Span::styled(tree_prefix(rows, i), theme.text_muted())];
+            match &row.node {
+                EscGraphNode::Heading(title) => {
+                    spans.push(Span::styled(*title, theme.subtitle()));
+                }
+                EscGraphNode::Environment { env, version } => {
+                    let style = if row.depth == 0 {
+                        theme.highlight()
+                    } else {
+                        theme.text()
+                    };
+                    spans.push(Span::styled(env.clone(), style));
+                    if let Some(version) = version {
+                        spans.push(Span::styled(format!("@{}", version), theme.text_muted()));
+                    }
+                }
+                EscGraphNode::Stack {
+                    project,
+                    stack,
+                    version,
+                } => {
+                    spans.push(Span::styled("stack ", theme.text_secondary()));
+                    spans.push(Span::styled(
+                        format!("{}/{}", project, stack),
+                        theme.primary(),
+                    ));
+                    spans.push(Span::styled(format!(" (v{})", version), theme.text_muted()));
+                }
+                EscGraphNode::InsightsAccount(name) => {
+                    spans.push(Span::styled("insights account ", theme.text_secondary()));
+                    spans.push(Span::styled(name.clone(), theme.primary()));
+                }
+                EscGraphNode::Cycle(envs) => {
+                    spans.push(Span::styled(
+                        envs.join(&format!(" {} ", symbols::ARROW_RIGHT)),
+                        theme.error(),
+                    ));
+                }
+                EscGraphNode::Error(message) => {
+                    spans.push(Span::styled(message.clone(), theme.text_muted()));
+                }
+            }
+            match row.mark {
+                EscGraphMark::None => {}
+                EscGraphMark::Repeated => {
+                    spans.push(Span::styled(" (see above)", theme.text_muted()));
+                }
+                EscGraphMark::Cycle => {
+                    spans.push(Span::styled(
+                        format!(" {} cycle", symbols::CROSS_MARK),
+                        theme.error(),
+                    ));
+                }
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme.selected());
+    frame.render_stateful_widget(list, area, &mut props.rows.state);
+}
+
+/// Tree connectors for a row: `├─`/`└─` before it, and `│` for each
+/// ancestor that has siblings further down
+fn tree_prefix(rows: &[EscGraphRow], index: usize) -> String {
+    let depth = rows[index].depth;
+    // Whether a later row continues at `level` before the tree moves above it
+    let continues = |level: usize| {
+        rows[index + 1..]
+            .iter()
+            .find(|row| row.depth <= level)
+            .is_some_and(|row| row.depth == level)
+    };
+    (1..=depth)
+        .map(|level| match (level == depth, continues(level)) {
+            (true, true) => "├─ ",
+            (true, false) => "└─ ",
+            (false, true) => "│  ",
+            (false, false) => "   ",
+        })
+        .collect()
+}
+
+fn render_change_requests_pane(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    props: EscChangeRequestsProps<'_>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(35), Constraint::Min(8)])
+        .split(area);
+
+    let items: Vec<ListItem> = props
+        .requests
+        .items()
+        .iter()
+        .map(|request| {
+            let env = match request.env_ref() {
+                Some((project, name)) => format!("{}/{}", project, name),
+                None => "-".to_string(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<9}", request.status),
+                    change_status_style(theme, &request.status),
+                ),
+                Span::styled(format!("{:<24} ", env), theme.highlight()),
+                Span::styled(
+                    format!("{:<17}", short_time(&request.created)),
+                    theme.text_muted(),
+                ),
+                Span::styled(format!("{:<16} ", request.created_by), theme.text()),
+                Span::styled(request.description.clone(), theme.text_secondary()),
+            ]))
+        })
+        .collect();
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(" Change Requests ")
+        .title_style(theme.title());
+    block = match props.confirm {
+        Some(action) => block.title_bottom(Line::from(vec![
+            Span::styled(
+                format!(" {} this change request? ", capitalize(action.verb())),
+                theme.warning(),
+            ),
+            Span::styled("y", theme.key_hint()),
+            Span::styled(format!(": {} | ", action.verb()), theme.key_desc()),
+            Span::styled("any key", theme.key_hint()),
+            Span::styled(": cancel ", theme.key_desc()),
+        ])),
+        None => block.title_bottom(Line::from(vec![
+            Span::styled(" s", theme.key_hint()),
+            Span::styled(": submit | ", theme.key_desc()),
+            Span::styled("A", theme.key_hint()),
+            Span::styled(": approve | ", theme.key_desc()),
+            Span::styled("a", theme.key_hint()),
+            Span::styled(": apply | ", theme.key_desc()),
+            Span::styled("X", theme.key_hint()),
+            Span::styled(": close ", theme.key_desc()),
+        ])),
+    };
+    if items.is_empty() {
+        let inner = block.inner(chunks[0]);
+        frame.render_widget(block, chunks[0]);
+        let empty = Paragraph::new("No open change requests. Save a draft from the editor with d.")
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+    } else {
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.selected());
+        frame.render_stateful_widget(list, chunks[0], &mut props.requests.state);
+    }
+
+    let selected = props.requests.selected();
+    let title = match (selected, props.view) {
+        (Some(request), EscChangeView::Diff) => {
+            format!(" Revision {}: diff against current ", request.revision)
+        }
+        (Some(request), EscChangeView::Values) => {
+            format!(" Revision {}: resolved values ", request.revision)
+        }
+        (None, _) => " Change Request ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title(title)
+        .title_style(theme.subtitle())
+        .title_bottom(Line::from(vec![
+            Span::styled(" v", theme.key_hint()),
+            Span::styled(": diff/values | ", theme.key_desc()),
+            Span::styled("j/k", theme.key_hint()),
+            Span::styled(": scroll ", theme.key_desc()),
+        ]));
+    let inner = block.inner(chunks[1]);
+    frame.render_widget(block, chunks[1]);
+    let Some(request) = selected else {
+        return;
+    };
+
+    let mut lines = approval_lines(theme, request);
+    lines.push(Line::from(""));
+    let loaded = match (props.view, props.content, props.values) {
+        (EscChangeView::Diff, Some([]), _) => {
+            lines.push(Line::styled("No differences", theme.text_muted()));
+            true
+        }
+        (EscChangeView::Diff, Some(content), _) => {
+            lines.extend(content.iter().cloned());
+            true
+        }
+        (EscChangeView::Values, _, Some(values)) => {
+            // Secrets stay masked; reveal them from the environment once applied
+            lines.extend(values.iter().filter(|v| !v.is_container()).map(|value| {
+                Line::from(vec![
+                    Span::styled(format!("{} ", value.path), theme.text_secondary()),
+                    value_span(theme, &value.value, value.secret),
+                ])
+            }));
+            true
+        }
+        _ => false,
+    };
+    if !loaded {
+        lines.push(Line::styled("Loading...", theme.text_muted()));
+    }
+
+    let content_height = (lines.len() as u16).max(inner.height);
+    let content_width = inner.width.saturating_sub(1);
+    let mut scroll_view = ScrollView::new(Size::new(content_width, content_height));
+    scroll_view.render_widget(
+        Paragraph::new(lines),
+        Rect::new(0, 0, content_width, content_height),
+    );
+    frame.render_stateful_widget(scroll_view, inner, props.scroll);
+
+    if let Some(input) = props.prompt {
+        render_prompt(
+            frame,
+            theme,
+            " Submit for approval ".to_string(),
+            "Description (optional) | Enter: submit | Esc: cancel",
+            input,
+        );
+    }
+}
+
+/// Approval gates of a change request, with who approved
+fn approval_lines(theme: &Theme, request: &EscChangeRequest) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Requested by ", theme.text_secondary()),
+        Span::styled(request.created_by.clone(), theme.text()),
+        Span::styled(format!("  {}", request.id), theme.text_muted()),
+    ])];
+    if !request.description.is_empty() {
+        lines.push(Line::styled(request.description.clone(), theme.text()));
+    }
+    let Some(ref approval) = request.approval else {
+        return lines;
+    };
+
+    lines.push(if approval.satisfied {
+        Line::styled(
+            format!("{} Ready to apply", symbols::CHECK),
+            theme.success(),
+        )
+    } else {
+        Line::styled(
+            format!("{} Awaiting approval", symbols::CROSS_MARK),
+            theme.warning(),
+        )
+    });
+    for gate in &approval.gates {
+        let (mark, style) = if gate.satisfied {
+            (symbols::CHECK, theme.success())
+        } else {
+            (symbols::CROSS_MARK, theme.warning())
+        };
+        let mut spans = vec![
+            Span::styled(format!("  {} ", mark), style),
+            Span::styled(gate.name.clone(), theme.text()),
+        ];
+        if let Some(required) = gate.required_approvals {
+            spans.push(Span::styled(
+                format!("  {}/{} approvals", gate.approvers.len(), required),
+                theme.text_muted(),
+            ));
+        }
+        if !gate.approvers.is_empty() {
+            spans.push(Span::styled(
+                format!("  ({})", gate.approvers.join(", ")),
+                theme.text_secondary(),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+fn change_status_style(theme: &Theme, status: &str) -> Style {
+    match status {
+        "draft" => theme.text_muted(),
+        "approved" => theme.success(),
+        _ => theme.info(),
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn render_rotation_pane(frame: &mut Frame, theme: &Theme, area: Rect, props: EscRotationProps<'_>) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(35),
+            Constraint::Percentage(25),
+            Constraint::Min(6),
+        ])
+        .split(area);
+    let border = |focused: bool| {
+        if focused {
+            theme.border_focused()
+        } else {
+            theme.border()
+        }
+    };
+
+    let rotator_items: Vec<ListItem> = props
+        .rotators
+        .items()
+        .iter()
+        .map(|rotator| {
+            let mut spans = vec![
+                Span::styled(format!("{:<28} ", rotator.path), theme.highlight()),
+                Span::styled(
+                    format!(
+                        "{:<14} ",
+                        if rotator.provider.is_empty() {
+                            "-"
+                        } else {
+                            rotator.provider.as_str()
+                        }
+                    ),
+                    theme.text_secondary(),
+                ),
+            ];
+            // Latest run that rotated this path
+            let last = props.history.iter().find_map(|event| {
+                event
+                    .rotations
+                    .iter()
+                    .find(|r| same_rotation_path(&r.path, &rotator.path))
+                    .map(|r| (event, r))
+            });
+            match last {
+                Some((event, rotation)) => {
+                    spans.push(Span::styled(
+                        format!("{} ", rotation.status),
+                        rotation_status_style(theme, &rotation.status),
+                    ));
+                    spans.push(Span::styled(short_time(&event.created), theme.text_muted()));
+                    if let Some(ref error) = rotation.error {
+                        spans.push(Span::styled(format!("  {}", error), theme.error()));
+                    }
+                }
+                None => spans.push(Span::styled("never rotated", theme.text_muted())),
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border(props.focus == EscRotationPane::Rotators))
+        .title(format!(" Rotators: {} ", props.env))
+        .title_style(theme.title());
+    block = match props.confirm {
+        Some(paths) => {
+            let target = if paths.is_empty() {
+                "all rotators".to_string()
+            } else {
+                paths.join(", ")
+            };
+            block.title_bottom(Line::from(vec![
+                Span::styled(format!(" Rotate {} now? ", target), theme.warning()),
+                Span::styled("y", theme.key_hint()),
+                Span::styled(": rotate | ", theme.key_desc()),
+                Span::styled("any key", theme.key_hint()),
+                Span::styled(": cancel ", theme.key_desc()),
+            ]))
+        }
+        None => block.title_bottom(Line::from(vec![
+            Span::styled(" Enter", theme.key_hint()),
+            Span::styled(": rotate | ", theme.key_desc()),
+            Span::styled("a", theme.key_hint()),
+            Span::styled(": rotate all ", theme.key_desc()),
+        ])),
+    };
+    if rotator_items.is_empty() {
+        let inner = block.inner(chunks[0]);
+        frame.render_widget(block, chunks[0]);
+        let empty = Paragraph::new("No fn::rotate in this environment's values")
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+    } else {
+        let list = List::new(rotator_items)
+            .block(block)
+            .highlight_style(theme.selected());
+        frame.render_stateful_widget(list, chunks[0], &mut props.rotators.state);
+    }
+
+    let schedule_items: Vec<ListItem> = props
+        .schedules
+        .items()
+        .iter()
+        .map(|schedule| {
+            let (state, state_style) = if schedule.paused {
+                ("paused ", theme.warning())
+            } else {
+                ("active ", theme.success())
+            };
+            let when = match (&schedule.cron, &schedule.once) {
+                (Some(cron), _) => cron.clone(),
+                (None, Some(once)) => format!("once {}", short_time(once)),
+                (None, None) => "-".to_string(),
+            };
+            let time = |t: &Option<String>| t.as_deref().map(short_time).unwrap_or("-".into());
+            ListItem::new(Line::from(vec![
+                Span::styled(state, state_style),
+                Span::styled(format!("{:<20} ", when), theme.text()),
+                Span::styled("next ", theme.text_secondary()),
+                Span::styled(
+                    format!("{:<17} ", time(&schedule.next_execution)),
+                    theme.text(),
+                ),
+                Span::styled("last ", theme.text_secondary()),
+                Span::styled(
+                    format!("{:<17} ", time(&schedule.last_executed)),
+                    theme.text(),
+                ),
+                Span::styled(schedule.kind.clone(), theme.text_muted()),
+            ]))
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border(props.focus == EscRotationPane::Schedules))
+        .title(" Schedules ")
+        .title_style(theme.subtitle())
+        .title_bottom(Line::from(vec![
+            Span::styled(" p", theme.key_hint()),
+            Span::styled(": pause/resume ", theme.key_desc()),
+        ]));
+    if schedule_items.is_empty() {
+        let inner = block.inner(chunks[1]);
+        frame.render_widget(block, chunks[1]);
+        let empty = Paragraph::new("No schedules")
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+    } else {
+        let list = List::new(schedule_items)
+            .block(block)
+            .highlight_style(theme.selected());
+        frame.render_stateful_widget(list, chunks[1], &mut props.schedules.state);
+    }
+
+    let mut lines = Vec::new();
+    for event in props.history {
+        let revisions = match event.post_revision {
+            Some(post) => format!(
+                "rev {} {} {}",
+                event.pre_revision,
+                symbols::ARROW_RIGHT,
+                post
+            ),
+            None => format!("rev {}", event.pre_revision),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<17} ", short_time(&event.created)),
+                theme.text_muted(),
+            ),
+            Span::styled(
+                format!("{:<12} ", event.status),
+                rotation_status_style(theme, &event.status),
+            ),
+            Span::styled(format!("{:<14} ", revisions), theme.text()),
+            Span::styled(
+                if event.scheduled {
+                    "scheduled"
+                } else {
+                    "manual"
+                },
+                theme.text_secondary(),
+            ),
+        ]));
+        for rotation in &event.rotations {
+            let mut spans = vec![
+                Span::styled(format!("  {} ", symbols::BULLET), theme.primary()),
+                Span::styled(format!("{} ", rotation.path), theme.text()),
+                Span::styled(
+                    rotation.status.clone(),
+                    rotation_status_style(theme, &rotation.status),
+                ),
+            ];
+            if let Some(ref error) = rotation.error {
+                spans.push(Span::styled(format!("  {}", error), theme.error()));
+            }
+            lines.push(Line::from(spans));
+        }
+        if let Some(ref error) = event.error {
+            lines.push(Line::styled(format!("  {}", error), theme.error()));
+        }
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title(" History ")
+        .title_style(theme.subtitle());
+    if lines.is_empty() {
+        let inner = block.inner(chunks[2]);
+        frame.render_widget(block, chunks[2]);
+        let empty = Paragraph::new("No rotations yet")
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+    } else {
+        frame.render_widget(Paragraph::new(lines).block(block), chunks[2]);
+    }
+}
+
+/// Rotation paths may or may not carry the leading `values.`
+fn same_rotation_path(a: &str, b: &str) -> bool {
+    a.strip_prefix("values.").unwrap_or(a) == b.strip_prefix("values.").unwrap_or(b)
+}
+
+fn rotation_status_style(theme: &Theme, status: &str) -> Style {
+    match status {
+        "succeeded" => theme.success(),
+        "failed" => theme.error(),
+        _ => theme.info(),
+    }
+}
+
+/// Shorten an RFC 3339 timestamp to date and minutes
+fn short_time(timestamp: &str) -> String {
+    timestamp.get(..16).unwrap_or(timestamp).replace('T', " ")
+}
+
+fn render_revisions_pane(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    props: EscRevisionsProps<'_>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(40), Constraint::Min(8)])
+        .split(area);
+
+    let selected = props.revisions.selected().map(|r| r.number);
+    let latest = props.revisions.items().first().map(|r| r.number);
+    let items: Vec<ListItem> = props
+        .revisions
+        .items()
+        .iter()
+        .map(|revision| {
+            let marker = if props.diff_base == Some(revision.number) {
+                Span::styled(format!("{} ", symbols::DIAMOND), theme.warning())
+            } else {
+                Span::raw("  ")
+            };
+            let number_style = if revision.retracted.is_some() {
+                theme.text_muted()
+            } else {
+                theme.highlight()
+            };
+            let mut spans = vec![
+                marker,
+                Span::styled(format!("{:>5}  ", revision.number), number_style),
+                Span::styled(
+                    format!("{:<17}", short_time(&revision.created)),
+                    theme.text_muted(),
+                ),
+                Span::styled(
+                    format!("{:<20} ", revision.creator.as_deref().unwrap_or("-")),
+                    theme.text(),
+                ),
+            ];
+            spans.extend(
+                revision
+                    .tags
+                    .iter()
+                    .map(|tag| Span::styled(format!("[{}] ", tag), theme.primary())),
+            );
+            if let Some(ref retracted) = revision.retracted {
+                spans.push(Span::styled(
+                    format!("retracted → {}", retracted.replacement),
+                    theme.error(),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .title(" Revisions ")
+                .title_style(theme.title())
+                .title_bottom(Line::from(vec![
+                    Span::styled(" d", theme.key_hint()),
+                    Span::styled(": diff base | ", theme.key_desc()),
+                    Span::styled("t", theme.key_hint()),
+                    Span::styled(": tag | ", theme.key_desc()),
+                    Span::styled("R", theme.key_hint()),
+                    Span::styled(": roll back | ", theme.key_desc()),
+                    Span::styled("X", theme.key_hint()),
+                    Span::styled(": retract ", theme.key_desc()),
+                ])),
+        )
+        .highlight_style(theme.selected());
+    frame.render_stateful_widget(list, chunks[0], &mut props.revisions.state);
+
+    let title = match (props.diff_base, selected) {
+        (Some(base), Some(number)) if base != number => {
+            format!(" Diff {} {} {} ", base, symbols::ARROW_RIGHT, number)
+        }
+        (_, Some(number)) if Some(number) == latest => format!(" Revision {} (current) ", number),
+        (_, Some(number)) => format!(" Revision {} ", number),
+        (_, None) => " Revision ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title(title)
+        .title_style(theme.subtitle());
+    let inner = block.inner(chunks[1]);
+    frame.render_widget(block, chunks[1]);
+
+    match props.content {
+        Some(lines) if !lines.is_empty() => {
+            let content_height = (lines.len() as u16).max(inner.height);
+            let content_width = inner.width.saturating_sub(1);
+            let mut scroll_view = ScrollView::new(Size::new(content_width, content_height));
+            scroll_view.render_widget(
+                Paragraph::new(lines.to_vec()),
+                Rect::new(0, 0, content_width, content_height),
+            );
+            frame.render_stateful_widget(scroll_view, inner, props.scroll);
+        }
+        Some(_) => {
+            let message = match (props.diff_base, selected) {
+                (Some(base), Some(number)) if base != number => "No differences",
+                _ => "Empty definition",
+            };
+            let empty = Paragraph::new(message)
+                .style(theme.text_muted())
+                .alignment(Alignment::Center);
+            frame.render_widget(empty, inner);
+        }
+        None => {
+            let empty = Paragraph::new("Loading...")
+                .style(theme.text_muted())
+                .alignment(Alignment::Center);
+            frame.render_widget(empty, inner);
+        }
+    }
+
+    if let (Some((prompt, input)), Some(number)) = (props.prompt, selected) {
+        render_revision_prompt(frame, theme, prompt, input, number);
+    }
+}
+
+fn render_revision_prompt(
+    frame: &mut Frame,
+    theme: &Theme,
+    prompt: EscRevisionPrompt,
+    input: &TextInput,
+    revision: i64,
+) {
+    let (title, hint) = match prompt {
+        EscRevisionPrompt::Tag => (
+            format!(" Tag revision {} ", revision),
+            "Enter: create or move tag | Esc: cancel",
+        ),
+        EscRevisionPrompt::Retract => (
+            format!(" Retract revision {} ", revision),
+            "Reason (optional) | Enter: retract | Esc: cancel",
+        ),
+    };
+    render_prompt(frame, theme, title, hint, input);
+}
+
+/// Single-line text input centered over the view
+fn render_prompt(frame: &mut Frame, theme: &Theme, title: String, hint: &str, input: &TextInput) {
+    use ratatui::widgets::Clear;
+
+    let column = super::centered_rect(50, 100, frame.area());
+    let area = Rect::new(
+        column.x,
+        column.y + column.height.saturating_sub(3) / 2,
+        column.width,
+        3.min(column.height),
+    );
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(title)
+        .title_style(theme.title())
+        .title_bottom(Line::styled(format!(" {} ", hint), theme.key_desc()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(input.value()).style(theme.text()), inner);
+
+    let cursor_x = inner.x + input.cursor() as u16;
+    if cursor_x < inner.x + inner.width {
+        frame.set_cursor_position((cursor_x, inner.y));
+    }
+}
+
+/// Props for the create / clone / delete environment dialog
+pub struct EscEnvDialogProps<'a> {
+    pub dialog: EscEnvDialog,
+    /// Selected environment as `project/name` (the clone source or delete target)
+    pub selected: Option<String>,
+    pub project_input: &'a TextInput,
+    pub name_input: &'a TextInput,
+    pub focus: usize,
+    pub keep_history: bool,
+    pub keep_tags: bool,
+}
+
+/// Render the create / clone / delete environment dialog
+pub fn render_esc_env_dialog(frame: &mut Frame, theme: &Theme, props: EscEnvDialogProps<'_>) {
+    use ratatui::widgets::Clear;
+
+    let selected = props.selected.unwrap_or_default();
+    let (title, height) = match props.dialog {
+        EscEnvDialog::Create => (" New Environment ".to_string(), 10),
+        EscEnvDialog::Clone => (format!(" Clone {} ", selected), 12),
+        EscEnvDialog::Delete => (format!(" Delete {} ", selected), 9),
+    };
+
+    let column = super::centered_rect(60, 100, frame.area());
+    let area = Rect::new(
+        column.x,
+        column.y + column.height.saturating_sub(height) / 2,
+        column.width,
+        height.min(column.height),
+    );
+    frame.render_widget(Clear, area);
+
+    let border = match props.dialog {
+        EscEnvDialog::Delete => theme.error(),
+        _ => theme.border_focused(),
+    };
+    let action = match props.dialog {
+        EscEnvDialog::Create => ": create | ",
+        EscEnvDialog::Clone => ": clone | ",
+        EscEnvDialog::Delete => ": delete | ",
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border)
+        .title(title)
+        .title_style(theme.title())
+        .title_bottom(Line::from(vec![
+            Span::styled(" Enter", theme.key_hint()),
+            Span::styled(action, theme.key_desc()),
+            Span::styled("Tab", theme.key_hint()),
+            Span::styled(": next field | ", theme.key_desc()),
+            Span::styled("Esc", theme.key_hint()),
+            Span::styled(": cancel ", theme.key_desc()),
+        ]));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let field = |label: &str, focused: bool| {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(if focused {
+                theme.border_focused()
+            } else {
+                theme.border()
+            })
+            .title(format!(" {} ", label))
+            .title_style(if focused {
+                theme.primary()
+            } else {
+                theme.subtitle()
+            })
+    };
+    let render_input =
+        |frame: &mut Frame, label: &str, input: &TextInput, focused: bool, area: Rect| {
+            let block = field(label, focused);
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+            frame.render_widget(Paragraph::new(input.value()).style(theme.text()), inner);
+            if focused {
+                let cursor_x = inner.x + input.cursor() as u16;
+                if cursor_x < inner.x + inner.width {
+                    frame.set_cursor_position((cursor_x, inner.y));
+                }
+            }
+        };
+
+    if props.dialog == EscEnvDialog::Delete {
+        let env_name = selected.rsplit('/').next().unwrap_or_default().to_string();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ])
+            .split(inner);
+        let confirmed = props.name_input.value() == env_name;
+        let warning = Paragraph::new(vec![
+            Line::styled(
+                "This deletes the environment and its revision history.",
+                theme.warning(),
+            ),
+            Line::from(vec![
+                Span::styled("Type ", theme.text_secondary()),
+                Span::styled(env_name.clone(), theme.highlight()),
+                Span::styled(" to confirm.", theme.text_secondary()),
+            ]),
+        ]);
+        frame.render_widget(warning, chunks[0]);
+        render_input(
+            frame,
+            if confirmed { "Name ✓" } else { "Name" },
+            props.name_input,
+            true,
+            chunks[1],
+        );
+        return;
+    }
+
+    let mut constraints = vec![Constraint::Length(3), Constraint::Length(3)];
+    if props.dialog == EscEnvDialog::Clone {
+        constraints.extend([Constraint::Length(1), Constraint::Length(1)]);
+    }
+    constraints.push(Constraint::Min(0));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(inner);
+
+    render_input(
+        frame,
+        "Project",
+        props.project_input,
+        props.focus == 0,
+        chunks[0],
+    );
+    render_input(frame, "Name", props.name_input, props.focus == 1, chunks[1]);
+
+    match props.dialog {
+        EscEnvDialog::Clone => {
+            for (i, (label, checked)) in [
+                ("Keep revision history", props.keep_history),
+                ("Keep environment and version tags", props.keep_tags),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let focused = props.focus == i + 2;
+                let line = Line::from(vec![
+                    Span::styled(
+                        if checked { " [x] " } else { " [ ] " },
+                        if focused {
+                            theme.primary()
+                        } else {
+                            theme.text()
+                        },
+                    ),
+                    Span::styled(
+                        label,
+                        if focused {
+                            theme.highlight()
+                        } else {
+                            theme.text_secondary()
+                        },
+                    ),
+                    Span::styled(
+                        if focused { "  (Space to toggle)" } else { "" },
+                        theme.text_muted(),
+                    ),
+                ]);
+                frame.render_widget(Paragraph::new(line), chunks[i + 2]);
+            }
+        }
+        _ => {
+            let note = Paragraph::new(
+                " Starts from a template with environmentVariables and pulumiConfig.",
+            )
+            .style(theme.text_muted())
+            .wrap(ratatui::widgets::Wrap { trim: true });
+            frame.render_widget(note, chunks[2]);
+        }
+    }
+}
+
+/// Props for the environment variables export dialog
+pub struct EscExportProps<'a> {
+    /// Exported environment as `project/name`
+    pub env: String,
+    pub format: EscExportFormat,
+    pub path_input: &'a TextInput,
+    /// Number of variables written, and how many of them are secrets
+    pub variables: usize,
+    pub secrets: usize,
+    /// Asking before secrets are written to disk or a file is overwritten
+    pub confirm: bool,
+    /// The file already exists
+    pub overwrite: bool,
+}
+
+/// Render the dialog exporting an environment's variables to a file
+pub fn render_esc_export_dialog(frame: &mut Frame, theme: &Theme, props: EscExportProps<'_>) {
+    use ratatui::widgets::Clear;
+
+    let height = 8;
+    let column = super::centered_rect(60, 100, frame.area());
+    let area = Rect::new(
+        column.x,
+        column.y + column.height.saturating_sub(height) / 2,
+        column.width,
+        height.min(column.height),
+    );
+    frame.render_widget(Clear, area);
+
+    let path = props.path_input.value().trim();
+    let hint = if props.confirm {
+        let secrets = format!(
+            "{} secret{}",
+            props.secrets,
+            if props.secrets == 1 { "" } else { "s" }
+        );
+        let question = match (props.overwrite, props.secrets > 0) {
+            (true, true) => format!(" Overwrite {} with {}? ", path, secrets),
+            (true, false) => format!(" Overwrite {}? ", path),
+            (false, _) => format!(" Write {} to {}? ", secrets, path),
+        };
+        Line::from(vec![
+            Span::styled(question, theme.warning()),
+            Span::styled("y", theme.key_hint()),
+            Span::styled(": write | ", theme.key_desc()),
+            Span::styled("any key", theme.key_hint()),
+            Span::styled(": back ", theme.key_desc()),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" Enter", theme.key_hint()),
+            Span::styled(": export | ", theme.key_desc()),
+            Span::styled("Tab", theme.key_hint()),
+            Span::styled(": format | ", theme.key_desc()),
+            Span::styled("Esc", theme.key_hint()),
+            Span::styled(": cancel ", theme.key_desc()),
+        ])
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(if props.confirm {
+            theme.warning()
+        } else {
+            theme.border_focused()
+        })
+        .title(format!(" Export {} ", props.env))
+        .title_style(theme.title())
+        .title_bottom(hint);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let mut formats = vec![Span::styled(" Format: ", theme.text_secondary())];
+    for format in EscExportFormat::all() {
+        let style = if *format == props.format {
+            theme.highlight()
+        } else {
+            theme.text_muted()
+        };
+        formats.push(Span::styled(format!(" {} ", format.label()), style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(formats)), chunks[0]);
+
+    let field = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(" File ")
+        .title_style(theme.primary());
+    let field_inner = field.inner(chunks[1]);
+    frame.render_widget(field, chunks[1]);
+    frame.render_widget(
+        Paragraph::new(props.path_input.value()).style(theme.text()),
+        field_inner,
+    );
+    if !props.confirm {
+        let cursor_x = field_inner.x + props.path_input.cursor() as u16;
+        if cursor_x < field_inner.x + field_inner.width {
+            frame.set_cursor_position((cursor_x, field_inner.y));
+        }
+    }
+
+    let summary = if props.secrets > 0 {
+        Line::from(vec![
+            Span::styled(
+                format!(" {} environmentVariables, ", props.variables),
+                theme.text_muted(),
+            ),
+            Span::styled(
+                format!(
+                    "{} secret{} written in plain text",
+                    props.secrets,
+                    if props.secrets == 1 { "" } else { "s" }
+                ),
+                theme.warning(),
+            ),
+        ])
+    } else {
+        Line::styled(
+            format!(" {} environmentVariables", props.variables),
+            theme.text_muted(),
+        )
+    };
+    frame.render_widget(Paragraph::new(summary), chunks[2]);
+}
+
+/// One line of a unified diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// `@@ -a,b +c,d @@` hunk header
+    Hunk(String),
+    Context(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Unified diff (3 lines of context) between two versions of a YAML definition.
+/// A missing trailing newline is ignored, since the editor drops it.
+pub fn unified_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    use similar::{ChangeTag, TextDiff};
+
+    let old = format!("{}\n", old.trim_end_matches('\n'));
+    let new = format!("{}\n", new.trim_end_matches('\n'));
+    let diff = TextDiff::from_lines(&old, &new);
+
+    let mut lines = Vec::new();
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        lines.push(DiffLine::Hunk(hunk.header().to_string()));
+        for change in hunk.iter_changes() {
+            let text = change.value().trim_end_matches('\n').to_string();
+            lines.push(match change.tag() {
+                ChangeTag::Equal => DiffLine::Context(text),
+                ChangeTag::Insert => DiffLine::Added(text),
+                ChangeTag::Delete => DiffLine::Removed(text),
+            });
+        }
+    }
+    lines
+}
+
+/// Diff lines with +/- markers and the changed YAML syntax-highlighted
+pub fn highlight_diff(theme: &Theme, diff: &[DiffLine]) -> Vec<Line<'static>> {
+    let highlighted = |marker: &'static str, style: Style, text: &str| {
+        let mut spans = vec![Span::styled(marker, style)];
+        if let Some(line) = super::syntax::highlight_yaml(text).into_iter().next() {
+            spans.extend(line.spans);
+        }
+        Line::from(spans)
+    };
+
+    diff.iter()
+        .map(|line| match line {
+            DiffLine::Hunk(header) => Line::styled(header.clone(), theme.info()),
+            DiffLine::Context(text) => highlighted(" ", theme.text_muted(), text),
+            DiffLine::Added(text) => highlighted("+", theme.success(), text),
+            DiffLine::Removed(text) => highlighted("-", theme.error(), text),
+        })
+        .collect()
+}
+
+/// Render the save confirmation with the diff of the pending changes
+pub fn render_esc_save_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    env_name: &str,
+    diff: &[DiffLine],
+    scroll_state: &mut ScrollViewState,
+) {
+    use ratatui::widgets::Clear;
+
+    let area = super::centered_rect(80, 80, frame.area());
+    frame.render_widget(Clear, area);
+
+    let added = diff
+        .iter()
+        .filter(|l| matches!(l, DiffLine::Added(_)))
+        .count();
+    let removed = diff
+        .iter()
+        .filter(|l| matches!(l, DiffLine::Removed(_)))
+        .count();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(format!(" Save {}? ", env_name))
+        .title_style(theme.title())
+        .title(
+            Line::from(vec![
+                Span::styled(format!(" +{} ", added), theme.success()),
+                Span::styled(format!("-{} ", removed), theme.error()),
+            ])
+            .right_aligned(),
+        )
+        .title_bottom(Line::from(vec![
+            Span::styled(" y/Enter", theme.key_hint()),
+            Span::styled(": Save | ", theme.key_desc()),
+            Span::styled("d", theme.key_hint()),
+            Span::styled(": Save as draft | ", theme.key_desc()),
+            Span::styled("n/Esc", theme.key_hint()),
+            Span::styled(": Back to editor | ", theme.key_desc()),
+            Span::styled("j/k", theme.key_hint()),
+            Span::styled(": Scroll ", theme.key_desc()),
+        ]));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines: Vec<Line> = diff
+        .iter()
+        .map(|line| match line {
+            DiffLine::Hunk(header) => Line::styled(header.clone(), theme.info()),
+            DiffLine::Context(text) => Line::styled(format!(" {}", text), theme.text_muted()),
+            DiffLine::Added(text) => Line::styled(format!("+{}", text), theme.success()),
+            DiffLine::Removed(text) => Line::styled(format!("-{}", text), theme.error()),
+        })
+        .collect();
+
+    let content_height = (lines.len() as u16).max(inner.height);
+    let content_width = inner.width.saturating_sub(1);
+    let mut scroll_view = ScrollView::new(Size::new(content_width, content_height));
+    scroll_view.render_widget(
+        Paragraph::new(lines),
+        Rect::new(0, 0, content_width, content_height),
+    );
+    frame.render_stateful_widget(scroll_view, inner, scroll_state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_shows_changed_lines_with_context() {
+        let old = "values:\n  region: us-east-1\n  size: small\n";
+        let new = "values:\n  region: eu-west-1\n  size: small";
+
+        assert_eq!(
+            unified_diff(old, new),
+            vec![
+                DiffLine::Hunk("@@ -1,3 +1,3 @@".to_string()),
+                DiffLine::Context("values:".to_string()),
+                DiffLine::Removed("  region: us-east-1".to_string()),
+                DiffLine::Added("  region: eu-west-1".to_string()),
+                DiffLine::Context("  size: small".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn flatten_values_keeps_paths_and_secret_markers() {
+        let values = serde_json::json!({
+            "exprs": {},
+            "properties": {
+                "environmentVariables": {
+                    "value": {
+                        "AWS_ACCESS_KEY_ID": { "value": "AKIA123", "trace": {} },
+                        "AWS_SECRET_ACCESS_KEY": { "value": "s3cr3t", "secret": true, "trace": {} }
+                    },
+                    "trace": {}
+                },
+                "db": {
+                    "value": {
+                        "hosts": { "value": [{ "value": "a.internal" }] },
+                        "my.key": { "value": 5432 }
+                    },
+                    "secret": true
+                },
+                "executionContext": { "value": {} }
+            }
+        });
+
+        let rows = flatten_values(&values);
+        let summary: Vec<(&str, usize, bool)> = rows
+            .iter()
+            .map(|row| (row.path.as_str(), row.depth, row.secret))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("db", 0, true),
+                ("db.hosts", 1, true),
+                ("db.hosts[0]", 2, true),
+                ("db[\"my.key\"]", 1, true),
+                ("environmentVariables", 0, false),
+                ("environmentVariables.AWS_ACCESS_KEY_ID", 1, false),
+                ("environmentVariables.AWS_SECRET_ACCESS_KEY", 1, true),
+            ]
+        );
+        assert_eq!(rows[3].clipboard_text(), "5432");
+        assert_eq!(rows[6].clipboard_text(), "s3cr3t");
+        assert_eq!(
+            rows[0].value,
+            serde_json::json!({ "hosts": ["a.internal"], "my.key": 5432 })
+        );
+    }
+
+    #[test]
+    fn revealing_a_value_reveals_values_below_it() {
+        let rows = flatten_values(&serde_json::json!({
+            "properties": {
+                "creds": { "value": { "key": { "value": "k" } }, "secret": true },
+                "credsBackup": { "value": "b", "secret": true }
+            }
+        }));
+        let revealed: HashSet<String> = ["creds".to_string()].into();
+
+        let shown: Vec<(&str, bool)> = rows
+            .iter()
+            .map(|row| (row.path.as_str(), row.is_revealed(&revealed)))
+            .collect();
+        assert_eq!(
+            shown,
+            vec![("creds", true), ("creds.key", true), ("credsBackup", false)]
+        );
+    }
+
+    #[test]
+    fn flatten_values_traces_definitions_overrides_and_providers() {
+        fn range(env: &str, begin: (u64, u64), end: (u64, u64)) -> serde_json::Value {
+            serde_json::json!({
+                "environment": env,
+                "begin": { "line": begin.0, "column": begin.1, "byte": 0 },
+                "end": { "line": end.0, "column": end.1, "byte": 0 }
+            })
+        }
+
+        let values = serde_json::json!({
+            "exprs": {
+                "aws": {
+                    "range": range("app/dev", (2, 3), (9, 1)),
+                    "object": {
+                        "creds": {
+                            "range": range("app/dev", (3, 5), (8, 1)),
+                            "builtin": {
+                                "name": "fn::open",
+                                "arg": {
+                                    "range": range("app/dev", (4, 7), (8, 1)),
+                                    "object": {
+                                        "provider": { "literal": "aws-login" },
+                                        "inputs": { "range": range("app/dev", (5, 9), (8, 1)) }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "properties": {
+                "region": {
+                    "value": "eu-west-1",
+                    "trace": {
+                        "def": range("app/dev", (10, 11), (10, 20)),
+                        "base": {
+                            "value": "us-east-1",
+                            "trace": {
+                                "def": range("shared/base", (4, 11), (4, 20)),
+                                "base": {
+                                    "value": "us-west-2",
+                                    "trace": { "def": range("shared/defaults", (2, 11), (2, 20)) }
+                                }
+                            }
+                        }
+                    }
+                },
+                "aws": {
+                    "value": {
+                        "creds": {
+                            "value": { "accessKeyId": "AKIA123" },
+                            "secret": true,
+                            "trace": { "def": range("app/dev", (3, 5), (8, 1)) }
+                        }
+                    },
+                    "trace": { "def": range("app/dev", (2, 3), (9, 1)) }
+                }
+            }
+        });
+
+        let rows = flatten_values(&values);
+        let sources = |path: &str| {
+            rows.iter()
+                .find(|row| row.path == path)
+                .map(|row| {
+                    row.sources
+                        .iter()
+                        .map(|s| (s.environment.as_str(), s.position, s.function.as_deref()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap()
+        };
+
+        assert_eq!(
+            sources("region"),
+            vec![
+                ("app/dev", Some((10, 11)), None),
+                ("shared/base", Some((4, 11)), None),
+                ("shared/defaults", Some((2, 11)), None),
+            ]
+        );
+        assert_eq!(
+            rows.iter().find(|r| r.path == "region").unwrap().sources[1].value,
+            serde_json::json!("us-east-1")
+        );
+        assert_eq!(sources("aws"), vec![("app/dev", Some((2, 3)), None)]);
+        // Provider outputs without a trace inherit the `fn::open` definition
+        assert_eq!(
+            sources("aws.creds.accessKeyId"),
+            vec![("app/dev", Some((3, 5)), Some("fn::open::aws-login"))]
+        );
+    }
+
+    #[test]
+    fn unified_diff_ignores_trailing_newline() {
+        assert!(unified_diff("values:\n  a: 1\n", "values:\n  a: 1").is_empty());
+    }
+}
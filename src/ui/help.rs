@@ -51,8 +51,34 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
         (
             "Stacks View",
             vec![
-                ("Enter", "View stack details"),
-                ("u", "View update history"),
+                ("Enter", "Load stack details and resources"),
+                ("u", "Jump to update history"),
+                (
+                    "Enter (Updates)",
+                    "Open update: summary, changes, people, events",
+                ),
+                (
+                    "←/→",
+                    "Switch focus: Stacks / Updates / Resources / Properties",
+                ),
+                ("↑/↓", "Navigate focused list or scroll properties"),
+                ("J/K", "Page scroll resource properties"),
+                ("p", "Preview selected stack"),
+                ("U", "Update (pulumi up) selected stack"),
+                ("R", "Refresh selected stack"),
+                ("D", "Destroy selected stack"),
+                ("N", "Ask Neo about stack, update or resource"),
+                ("Esc", "Back to previous pane"),
+            ],
+        ),
+        (
+            "Resources View",
+            vec![
+                ("/", "Edit search query (type:, stack:, project:, ...)"),
+                ("↑/↓", "Navigate results"),
+                ("s / S", "Sort by next column / reverse order"),
+                ("] / [", "Next / previous page"),
+                ("N", "Ask Neo about selected resource"),
             ],
         ),
         (
@@ -64,7 +90,32 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
                 ("J/K", "Page scroll focused pane"),
                 ("Enter", "Load environment definition"),
                 ("o", "Open & resolve environment values"),
+                ("Space", "Reveal / hide selected secret value"),
+                ("y / Y", "Copy selected value / its path"),
                 ("e", "Edit environment YAML"),
+                (
+                    "Esc (editor)",
+                    "Check, review diff, then y to save or d to save as draft",
+                ),
+                (
+                    "v",
+                    "Revision history (d diff, t tag, R roll back, X retract)",
+                ),
+                ("n", "Create environment from a starter template"),
+                ("c", "Clone selected environment"),
+                ("D", "Delete selected environment (type its name)"),
+                ("i", "Import graph and referrers (Enter re-roots)"),
+                (
+                    "R",
+                    "Secret rotation (Enter/a rotate, p pause/resume schedule)",
+                ),
+                (
+                    "C",
+                    "Change requests (s submit, A approve, a apply, X close)",
+                ),
+                ("x", "Export variables (dotenv, shell, JSON)"),
+                ("!", "Run shell or command with env (pulumi env run)"),
+                ("N", "Ask Neo about selected environment"),
                 ("O", "Select organization (in this tab)"),
             ],
         ),
@@ -72,11 +123,20 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
             "Neo View",
             vec![
                 ("n", "Start new task"),
+                ("f", "Filter tasks by name (Esc clears)"),
+                ("s / m", "Filter tasks by status / mine or everyone's"),
+                ("S", "Sort tasks by updated / created time"),
                 ("i", "Focus input field"),
                 ("d", "Show task details"),
+                ("s / y", "Share task / copy link (in details)"),
+                ("e / E", "Export conversation (Markdown / JSON)"),
+                ("a / x", "Approve / reject pending request"),
+                ("X", "Cancel running task"),
                 ("Enter", "Send/select task"),
                 ("Esc", "Show task list"),
                 ("j/k", "Scroll messages"),
+                ("t / T", "Next / previous tool call"),
+                ("Space", "Expand/collapse tool call"),
             ],
         ),
         (
@@ -88,6 +148,20 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
                 ("j/k", "Scroll description (Components/Templates)"),
                 ("J/K", "Page scroll description"),
                 ("g / G", "Go to first/last item"),
+                ("N", "Ask Neo about selected item"),
+            ],
+        ),
+        (
+            "Commands Output",
+            vec![
+                ("j/k", "Scroll output / resource table"),
+                ("J/K", "Page scroll"),
+                ("g / G", "Go to top/bottom"),
+                ("v", "Toggle live resources / raw output"),
+                ("Esc", "Close output"),
+                ("X", "Cancel running command (again: kill)"),
+                ("(running)", "Prompting commands get every key"),
+                ("Ctrl+C", "Cancel an interactive command"),
             ],
         ),
     ];
@@ -10,24 +10,36 @@ mod help;
 mod logs;
 mod markdown;
 mod neo;
+mod neo_cache;
+mod neo_tools;
 mod platform;
+mod resources;
 mod splash;
 mod stacks;
 pub mod syntax;
 
 pub use commands::{render_commands_view, CommandsViewProps, CommandsViewState};
 pub use dashboard::render_dashboard;
-pub use esc::{extract_values, json_to_yaml, render_esc_editor, render_esc_view, EscViewProps};
+pub use esc::{
+    flatten_values, highlight_diff, render_esc_editor, render_esc_env_dialog,
+    render_esc_export_dialog, render_esc_save_dialog, render_esc_view, unified_diff, DiffLine,
+    EscChangeRequestsProps, EscEnvDialogProps, EscExportProps, EscGraphProps, EscRevisionsProps,
+    EscRotationProps, EscValue, EscViewProps,
+};
 pub use header::render_header;
 pub use help::render_help;
 pub use logs::render_logs;
 pub use neo::{
-    render_neo_details_dialog, render_neo_view, render_slash_commands_dialog, CommandPickerProps,
-    NeoViewProps, SlashCommandsDialogProps,
+    render_neo_approval_dialog, render_neo_cancel_dialog, render_neo_details_dialog,
+    render_neo_view, render_slash_commands_dialog, CommandPickerProps, NeoViewProps,
+    SlashCommandsDialogProps,
 };
+pub use neo_cache::NeoRenderCache;
+pub use neo_tools::NeoToolCallView;
 pub use platform::{render_platform_view, PlatformViewProps};
+pub use resources::{render_resources_view, ResourcesViewProps};
 pub use splash::render_splash;
-pub use stacks::render_stacks_view;
+pub use stacks::{render_stacks_view, render_update_details_dialog, StacksViewProps};
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
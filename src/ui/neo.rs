@@ -13,12 +13,14 @@ use ratatui::{
 use tui_scrollview::ScrollViewState;
 
 use crate::api::{NeoMessage, NeoMessageType, NeoTask};
-use crate::app::SlashCommandsDialogView;
+use crate::app::{NeoTaskSearchState, NeoTaskStatusFilter, SlashCommandsDialogView};
 use crate::components::{StatefulList, TextEditor, TextInput};
 use crate::theme::{symbols, Theme};
 
 use super::centered_rect;
 use super::markdown::render_markdown_content;
+use super::neo_cache::NeoRenderCache;
+use super::neo_tools::{tool_call_lines, NeoToolCallView, ToolCalls};
 
 // Tool-related symbols
 const TOOL_ICON: &str = "🔧";
@@ -43,6 +45,13 @@ pub struct CommandPickerProps<'a> {
 /// Props for rendering the Neo view
 pub struct NeoViewProps<'a> {
     pub tasks: &'a mut StatefulList<NeoTask>,
+    /// Filters, sort order and paging of the task list
+    pub task_search: &'a NeoTaskSearchState,
+    /// Task name filter input
+    pub task_query_input: &'a TextInput,
+    pub is_editing_task_query: bool,
+    /// Number of tasks loaded, before filtering
+    pub loaded_tasks: usize,
     pub messages: &'a [NeoMessage],
     pub input: &'a TextInput,
     pub scroll_state: &'a mut ScrollViewState,
@@ -51,6 +60,20 @@ pub struct NeoViewProps<'a> {
     pub spinner_char: &'a str,
     pub hide_task_list: bool,
     pub command_picker: CommandPickerProps<'a>,
+    /// Approval request Neo is waiting on (if any)
+    pub pending_approval: Option<&'a NeoMessage>,
+    /// Rendered messages kept across frames
+    pub render_cache: &'a mut NeoRenderCache,
+    /// File the conversation was last exported to
+    pub exported: Option<&'a str>,
+    /// Existing file an export is asking to overwrite
+    pub export_overwrite: Option<&'a str>,
+    /// Entity the next new task is started with, e.g. `stack web/prod`
+    pub attached: Option<&'a str>,
+    /// Expanded and selected tool calls
+    pub tool_calls: NeoToolCallView<'a>,
+    /// Scroll the selected tool call into view on the next frame
+    pub reveal_tool_call: &'a mut bool,
 }
 
 /// Props for chat view (internal)
@@ -62,6 +85,13 @@ struct ChatViewProps<'a> {
     is_loading: bool,
     spinner_char: &'a str,
     command_picker: CommandPickerProps<'a>,
+    pending_approval: Option<&'a NeoMessage>,
+    render_cache: &'a mut NeoRenderCache,
+    exported: Option<&'a str>,
+    export_overwrite: Option<&'a str>,
+    attached: Option<&'a str>,
+    tool_calls: NeoToolCallView<'a>,
+    reveal_tool_call: &'a mut bool,
 }
 
 /// Render the Neo chat view
@@ -74,6 +104,13 @@ pub fn render_neo_view(frame: &mut Frame, theme: &Theme, area: Rect, props: NeoV
         is_loading: props.is_loading,
         spinner_char: props.spinner_char,
         command_picker: props.command_picker,
+        pending_approval: props.pending_approval,
+        render_cache: props.render_cache,
+        exported: props.exported,
+        export_overwrite: props.export_overwrite,
+        attached: props.attached,
+        tool_calls: props.tool_calls,
+        reveal_tool_call: props.reveal_tool_call,
     };
 
     if props.hide_task_list {
@@ -86,16 +123,83 @@ pub fn render_neo_view(frame: &mut Frame, theme: &Theme, area: Rect, props: NeoV
             .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
             .split(area);
 
-        render_tasks_list(frame, theme, chunks[0], props.tasks);
+        let list_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)])
+            .split(chunks[0]);
+
+        render_task_query_input(
+            frame,
+            theme,
+            list_chunks[0],
+            props.task_query_input,
+            props.task_search,
+            props.is_editing_task_query,
+        );
+        render_tasks_list(
+            frame,
+            theme,
+            list_chunks[1],
+            props.tasks,
+            props.task_search,
+            props.loaded_tasks,
+        );
         render_chat_view(frame, theme, chunks[1], chat_props);
     }
 }
 
+fn render_task_query_input(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    input: &TextInput,
+    search: &NeoTaskSearchState,
+    is_editing: bool,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(if is_editing {
+            theme.border_focused()
+        } else {
+            theme.border()
+        })
+        .title(" f Filter ")
+        .title_style(if is_editing {
+            theme.title()
+        } else {
+            theme.subtitle()
+        });
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let value = if is_editing {
+        input.value()
+    } else {
+        search.query.as_str()
+    };
+    let text = if value.is_empty() && !is_editing {
+        Paragraph::new("Type f to filter by name").style(theme.text_muted())
+    } else {
+        Paragraph::new(value).style(theme.text())
+    };
+    frame.render_widget(text, inner);
+
+    if is_editing {
+        let cursor_x = inner.x + input.cursor() as u16;
+        if cursor_x < inner.x + inner.width {
+            frame.set_cursor_position((cursor_x, inner.y));
+        }
+    }
+}
+
 fn render_tasks_list(
     frame: &mut Frame,
     theme: &Theme,
     area: Rect,
     tasks: &mut StatefulList<NeoTask>,
+    search: &NeoTaskSearchState,
+    loaded: usize,
 ) {
     // Get values before borrowing items
     let selected_idx = tasks.selected_index();
@@ -123,6 +227,7 @@ fn render_tasks_list(
                 Some("completed") => symbols::CHECK,
                 Some("running") | Some("in_progress") => symbols::SPINNER[0],
                 Some("failed") => symbols::CROSS_MARK,
+                Some("cancelled") | Some("canceled") => symbols::CANCELLED,
                 _ => symbols::BULLET,
             };
 
@@ -130,6 +235,7 @@ fn render_tasks_list(
                 Some("completed") => theme.success(),
                 Some("running") | Some("in_progress") => theme.warning(),
                 Some("failed") => theme.error(),
+                Some("cancelled") | Some("canceled") => theme.text_muted(),
                 _ => theme.text_secondary(),
             };
 
@@ -150,14 +256,70 @@ fn render_tasks_list(
         })
         .collect();
 
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(theme.border())
-                .title(" Neo Tasks ")
-                .title_style(theme.title()),
+    // Filters and sort order, e.g. "running · mine · updated ↓"
+    let mut filters = Vec::new();
+    if search.status != NeoTaskStatusFilter::All {
+        filters.push(search.status.title().to_string());
+    }
+    if search.mine {
+        filters.push("mine".to_string());
+    }
+    filters.push(format!(
+        "{} {}",
+        search.sort.title(),
+        if search.ascending {
+            symbols::ARROW_UP
+        } else {
+            symbols::ARROW_DOWN
+        }
+    ));
+
+    let more = if search.continuation_token.is_some() {
+        "+"
+    } else {
+        ""
+    };
+    let count = if search.is_filtered() {
+        format!(" {} of {}{} ", task_data.len(), loaded, more)
+    } else {
+        format!(" {}{} ", loaded, more)
+    };
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title(" Neo Tasks ")
+        .title_style(theme.title())
+        .title(
+            Line::styled(format!(" {} ", filters.join(" · ")), theme.text_muted()).right_aligned(),
         )
+        .title_bottom(Line::styled(count, theme.text_muted()));
+    if let Some(ref error) = search.error {
+        block =
+            block.title_bottom(Line::styled(format!(" {} ", error), theme.error()).right_aligned());
+    } else if search.loading {
+        block = block.title_bottom(Line::styled(" Loading... ", theme.info()).right_aligned());
+    }
+
+    if task_data.is_empty() {
+        let message = if search.loading {
+            "Loading..."
+        } else if search.is_filtered() {
+            "No tasks match the filters"
+        } else {
+            "No tasks yet"
+        };
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let empty = Paragraph::new(message)
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+        return;
+    }
+
+    let list = List::new(items)
+        .block(block)
         .highlight_style(theme.selected())
         .highlight_symbol("");
 
@@ -168,7 +330,13 @@ fn render_tasks_list(
 const COMMAND_ICON: &str = "⌘";
 
 fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatViewProps<'_>) {
-    // Layout: messages area, thinking indicator (if loading), command picker (if showing), input area
+    // Layout: messages area, approval prompt (if pending), thinking indicator (if loading),
+    // command picker (if showing), input area
+    let approval_height = if props.pending_approval.is_some() {
+        4
+    } else {
+        0
+    };
     let thinking_height = if props.is_loading { 2 } else { 0 };
     let command_picker_height = if props.command_picker.show {
         // Show up to 8 commands + 2 for borders
@@ -181,6 +349,7 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(10),
+            Constraint::Length(approval_height),
             Constraint::Length(thinking_height),
             Constraint::Length(command_picker_height),
             Constraint::Length(3),
@@ -188,7 +357,7 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
         .split(area);
 
     // Messages area
-    let messages_block = Block::default()
+    let mut messages_block = Block::default()
         .borders(Borders::ALL)
         .border_style(if props.input.is_focused() {
             theme.border()
@@ -197,6 +366,20 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
         })
         .title(" Chat ")
         .title_style(theme.subtitle());
+    if let Some(path) = props.export_overwrite {
+        messages_block = messages_block.title_bottom(Line::from(vec![
+            Span::styled(format!(" Overwrite {}? ", path), theme.warning()),
+            Span::styled("y", theme.key_hint()),
+            Span::styled(": overwrite | ", theme.key_desc()),
+            Span::styled("any key", theme.key_hint()),
+            Span::styled(": back ", theme.key_desc()),
+        ]));
+    } else if let Some(path) = props.exported {
+        messages_block = messages_block.title_bottom(Line::from(Span::styled(
+            format!(" {} Exported to {} ", symbols::CHECK, path),
+            theme.success(),
+        )));
+    }
 
     let messages_inner = messages_block.inner(chunks[0]);
     frame.render_widget(messages_block, chunks[0]);
@@ -257,151 +440,45 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
             frame.render_widget(welcome, messages_inner);
         }
     } else {
-        // Build message lines - all left-aligned for simplicity
-        let mut lines: Vec<Line> = Vec::new();
-
-        for msg in props.messages.iter() {
-            match msg.message_type {
-                NeoMessageType::UserMessage => {
-                    // User messages with arrow indicator
-                    lines.push(Line::from(Span::styled(
-                        format!("{} You:", symbols::ARROW_RIGHT),
-                        theme.user_message().add_modifier(Modifier::BOLD),
-                    )));
-                    for line in msg.content.lines() {
-                        lines.push(Line::from(Span::styled(
-                            format!("    {}", line),
-                            theme.text(),
-                        )));
-                    }
-                    lines.push(Line::from(""));
-                }
-                NeoMessageType::AssistantMessage => {
-                    // Neo messages with star indicator
-                    lines.push(Line::from(Span::styled(
-                        format!("{} Neo:", symbols::STAR),
-                        theme.neo_message().add_modifier(Modifier::BOLD),
-                    )));
-                    let md_lines = render_markdown_content(&msg.content, theme, "    ");
-                    lines.extend(md_lines);
-                    if !msg.tool_calls.is_empty() {
-                        lines.push(Line::from(""));
-                        for tc in &msg.tool_calls {
-                            lines.push(Line::from(vec![
-                                Span::styled(format!("    {} ", TOOL_ICON), theme.warning()),
-                                Span::styled("Calling: ", theme.text_muted()),
-                                Span::styled(
-                                    tc.name.clone(),
-                                    theme.accent().add_modifier(Modifier::BOLD),
-                                ),
-                            ]));
-                        }
-                    }
-                    lines.push(Line::from(""));
-                }
-                NeoMessageType::ToolCall => {
-                    lines.push(Line::from(vec![
-                        Span::styled(format!("  {} ", TOOL_ICON), theme.warning()),
-                        Span::styled(msg.content.clone(), theme.text_muted()),
-                    ]));
-                }
-                NeoMessageType::ToolResponse => {
-                    lines.push(Line::from(vec![
-                        Span::styled(format!("  {} ", RESULT_ICON), theme.success()),
-                        Span::styled(
-                            msg.tool_name
-                                .clone()
-                                .unwrap_or_else(|| "Result".to_string()),
-                            theme.text_secondary(),
-                        ),
-                        Span::styled(": ", theme.text_muted()),
-                    ]));
-                    let content = if msg.content.len() > 200 {
-                        format!("{}...", &msg.content[..200])
-                    } else {
-                        msg.content.clone()
-                    };
-                    for line in content.lines().take(5) {
-                        lines.push(Line::from(Span::styled(
-                            format!("    {}", line),
-                            theme.text_muted(),
-                        )));
-                    }
-                }
-                NeoMessageType::ToolError => {
-                    // Show tool error with red error styling
-                    lines.push(Line::from(vec![
-                        Span::styled(format!("  {} ", ERROR_ICON), theme.error()),
-                        Span::styled(
-                            format!(
-                                "Error running {}",
-                                msg.tool_name.clone().unwrap_or_else(|| "tool".to_string())
-                            ),
-                            theme.error().add_modifier(Modifier::BOLD),
-                        ),
-                    ]));
-                    // Show the error message (don't truncate as much for errors)
-                    for line in msg.content.lines().take(10) {
-                        lines.push(Line::from(Span::styled(
-                            format!("    {}", line),
-                            theme.error(),
-                        )));
-                    }
-                }
-                NeoMessageType::ApprovalRequest => {
-                    lines.push(Line::from(vec![
-                        Span::styled(format!("  {} ", APPROVAL_ICON), theme.warning()),
-                        Span::styled(
-                            "Approval needed: ",
-                            theme.warning().add_modifier(Modifier::BOLD),
-                        ),
-                    ]));
-                    for line in msg.content.lines() {
-                        lines.push(Line::from(Span::styled(
-                            format!("    {}", line),
-                            theme.text(),
-                        )));
-                    }
-                    lines.push(Line::from(""));
-                }
-                NeoMessageType::TaskNameChange => {
-                    lines.push(Line::from(vec![
-                        Span::styled(format!("  {} ", INFO_ICON), theme.text_muted()),
-                        Span::styled(
-                            msg.content.clone(),
-                            theme.text_secondary().add_modifier(Modifier::ITALIC),
-                        ),
-                    ]));
-                }
-            }
-        }
-
-        // === Direct scrolling using Ratatui's line_count() ===
-        //
-        // Using the unstable-rendered-line-info feature, we get the EXACT line count
-        // after wrapping, eliminating all estimation guesswork.
+        // Only new or changed messages are rendered again, and only the
+        // messages in view are laid out
+        props
+            .render_cache
+            .update(props.messages, props.tool_calls, theme);
 
         let visible_height = messages_inner.height as usize;
-
-        // Create paragraph with wrapping to get accurate line count
-        let content_para = Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false });
-
-        // Get EXACT line count from Ratatui (accounts for actual word wrapping)
-        let total_lines = content_para.line_count(messages_inner.width);
+        let total_lines = props.render_cache.total_height(messages_inner.width);
         let max_scroll = total_lines.saturating_sub(visible_height);
 
         // Determine scroll position
-        let scroll_y: u16 = if props.auto_scroll.load(Ordering::Relaxed) {
+        let mut scroll_y = if props.auto_scroll.load(Ordering::Relaxed) {
             // When auto-scroll is enabled, go to exact bottom
-            max_scroll as u16
+            max_scroll
         } else {
             // Manual scroll: use the stored offset, clamped to max
             let current_offset = props.scroll_state.offset();
-            (current_offset.y as usize).min(max_scroll) as u16
+            (current_offset.y as usize).min(max_scroll)
         };
 
-        // Apply scroll and render
-        let content_para = content_para.scroll((scroll_y, 0));
+        // A tool call jumped to with t/T is scrolled to just below the top
+        if std::mem::take(props.reveal_tool_call) {
+            let row = props
+                .tool_calls
+                .selected
+                .and_then(|id| props.render_cache.tool_call_row(id, messages_inner.width));
+            if let Some(row) = row {
+                scroll_y = row.saturating_sub(1).min(max_scroll);
+                props
+                    .scroll_state
+                    .set_offset(Position::new(0, scroll_y as u16));
+            }
+        }
+
+        // Render the visible window, starting partway into its first message
+        let (window, skip) = props.render_cache.window(scroll_y, visible_height);
+        let content_para = Paragraph::new(window)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .scroll((skip as u16, 0));
         frame.render_widget(content_para, messages_inner);
 
         // Render scrollbar manually if content exceeds viewport
@@ -423,11 +500,9 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
 
             // Calculate thumb position and size
             let thumb_height = ((visible_height * visible_height) / total_lines).max(1);
-            let thumb_pos = if max_scroll > 0 {
-                (scrollbar_pos.min(max_scroll) * (visible_height - thumb_height)) / max_scroll
-            } else {
-                0
-            };
+            let thumb_pos = (scrollbar_pos.min(max_scroll) * (visible_height - thumb_height))
+                .checked_div(max_scroll)
+                .unwrap_or(0);
 
             // Draw scrollbar track and thumb (using Violet for on-brand look)
             for y in 0..messages_inner.height {
@@ -447,6 +522,11 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
         }
     }
 
+    // Approval prompt (shown while Neo waits for the user to approve/reject)
+    if let Some(request) = props.pending_approval {
+        render_approval_prompt(frame, theme, chunks[1], request);
+    }
+
     // Thinking indicator (always visible when loading)
     if props.is_loading {
         let thinking_line = Line::from(vec![
@@ -459,7 +539,7 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
         let thinking_para = Paragraph::new(thinking_line)
             .style(Style::default().bg(theme.bg_medium))
             .alignment(ratatui::layout::Alignment::Center);
-        frame.render_widget(thinking_para, chunks[1]);
+        frame.render_widget(thinking_para, chunks[2]);
     }
 
     // Slash command picker (shown above input when typing '/')
@@ -467,7 +547,7 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
         render_command_picker(
             frame,
             theme,
-            chunks[2],
+            chunks[3],
             props.command_picker.filtered_commands,
             props.command_picker.index,
         );
@@ -489,7 +569,7 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
     };
 
     // Input area
-    let input_block = Block::default()
+    let mut input_block = Block::default()
         .borders(Borders::ALL)
         .border_style(if props.input.is_focused() {
             theme.border_focused()
@@ -502,9 +582,14 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
         } else {
             theme.subtitle()
         });
+    if let Some(attached) = props.attached {
+        input_block = input_block.title(
+            Line::from(Span::styled(format!(" 📎 {} ", attached), theme.accent())).right_aligned(),
+        );
+    }
 
-    let input_inner = input_block.inner(chunks[3]);
-    frame.render_widget(input_block, chunks[3]);
+    let input_inner = input_block.inner(chunks[4]);
+    frame.render_widget(input_block, chunks[4]);
 
     // Input text with cursor - highlight slash commands with purple background
     let input_value = props.input.value();
@@ -533,6 +618,164 @@ fn render_chat_view(frame: &mut Frame, theme: &Theme, area: Rect, props: ChatVie
     }
 }
 
+/// Lines for one chat message, left-aligned and not yet wrapped, and the
+/// line each of its tool call blocks starts at
+pub(super) fn message_lines(
+    msg: &NeoMessage,
+    tools: &ToolCalls,
+    theme: &Theme,
+) -> (Vec<Line<'static>>, Vec<(String, usize)>) {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut tool_call_starts: Vec<(String, usize)> = Vec::new();
+    if tools.is_paired(msg) {
+        // Drawn in the block of the call it runs or answers
+        return (lines, tool_call_starts);
+    }
+    match msg.message_type {
+        NeoMessageType::UserMessage => {
+            // User messages with arrow indicator
+            lines.push(Line::from(Span::styled(
+                format!("{} You:", symbols::ARROW_RIGHT),
+                theme.user_message().add_modifier(Modifier::BOLD),
+            )));
+            for line in msg.content.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", line),
+                    theme.text(),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+        NeoMessageType::AssistantMessage => {
+            // Neo messages with star indicator
+            lines.push(Line::from(Span::styled(
+                format!("{} Neo:", symbols::STAR),
+                theme.neo_message().add_modifier(Modifier::BOLD),
+            )));
+            let md_lines = render_markdown_content(&msg.content, theme, "    ");
+            lines.extend(md_lines);
+            if !msg.tool_calls.is_empty() {
+                lines.push(Line::from(""));
+                for call in &msg.tool_calls {
+                    tool_call_starts.push((call.id.clone(), lines.len()));
+                    lines.extend(tool_call_lines(call, tools, theme));
+                }
+            }
+            lines.push(Line::from(""));
+        }
+        NeoMessageType::ToolCall => {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", TOOL_ICON), theme.warning()),
+                Span::styled(msg.content.clone(), theme.text_muted()),
+            ]));
+        }
+        NeoMessageType::ToolResponse => {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", RESULT_ICON), theme.success()),
+                Span::styled(
+                    msg.tool_name
+                        .clone()
+                        .unwrap_or_else(|| "Result".to_string()),
+                    theme.text_secondary(),
+                ),
+                Span::styled(": ", theme.text_muted()),
+            ]));
+            // Results are kept in full (for export) but shortened here
+            let content = if msg.content.chars().count() > 200 {
+                format!("{}...", msg.content.chars().take(200).collect::<String>())
+            } else {
+                msg.content.clone()
+            };
+            for line in content.lines().take(5) {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", line),
+                    theme.text_muted(),
+                )));
+            }
+        }
+        NeoMessageType::ToolError => {
+            // Show tool error with red error styling
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", ERROR_ICON), theme.error()),
+                Span::styled(
+                    format!(
+                        "Error running {}",
+                        msg.tool_name.clone().unwrap_or_else(|| "tool".to_string())
+                    ),
+                    theme.error().add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            // Show the error message (don't truncate as much for errors)
+            for line in msg.content.lines().take(10) {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", line),
+                    theme.error(),
+                )));
+            }
+        }
+        NeoMessageType::ApprovalRequest => {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", APPROVAL_ICON), theme.warning()),
+                Span::styled(
+                    "Approval needed: ",
+                    theme.warning().add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            for line in msg.content.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", line),
+                    theme.text(),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+        NeoMessageType::TaskNameChange => {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", INFO_ICON), theme.text_muted()),
+                Span::styled(
+                    msg.content.clone(),
+                    theme.text_secondary().add_modifier(Modifier::ITALIC),
+                ),
+            ]));
+        }
+    }
+    (lines, tool_call_starts)
+}
+
+/// Render the approval prompt bar above the input
+fn render_approval_prompt(frame: &mut Frame, theme: &Theme, area: Rect, request: &NeoMessage) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.warning())
+        .title(" Approval needed ")
+        .title_style(theme.warning().add_modifier(Modifier::BOLD));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let summary = request
+        .content
+        .lines()
+        .next()
+        .unwrap_or("Approval requested");
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(format!(" {} ", APPROVAL_ICON), theme.warning()),
+            Span::styled(summary.to_string(), theme.text()),
+        ]),
+        Line::from(vec![
+            Span::styled("   Press ", theme.text_muted()),
+            Span::styled("a", theme.key_hint()),
+            Span::styled(" to approve, ", theme.text_muted()),
+            Span::styled("x", theme.key_hint()),
+            Span::styled(" to reject", theme.text_muted()),
+        ]),
+    ];
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
 /// Render input text with slash commands highlighted in purple (focused mode with cursor)
 fn render_input_with_commands<'a>(
     input: &'a str,
@@ -735,9 +978,17 @@ const USER_ICON: &str = "👤";
 const PR_ICON: &str = "🔀";
 const ENTITY_ICON: &str = "◆";
 const POLICY_ICON: &str = "🛡️";
+const SHARE_ICON: &str = "🔗";
+const PRIVATE_ICON: &str = "🔒";
 
 /// Render the Neo task details dialog
-pub fn render_neo_details_dialog(frame: &mut Frame, theme: &Theme, task: &NeoTask) {
+pub fn render_neo_details_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    task: &NeoTask,
+    share_url: Option<&str>,
+    link_copied: bool,
+) {
     let area = centered_rect(25, 70, frame.area());
 
     // Clear background
@@ -770,6 +1021,7 @@ pub fn render_neo_details_dialog(frame: &mut Frame, theme: &Theme, task: &NeoTas
         "idle" | "completed" => theme.success(),
         "running" | "in_progress" => theme.warning(),
         "failed" | "error" => theme.error(),
+        "cancelled" | "canceled" => theme.text_muted(),
         _ => theme.text_secondary(),
     };
 
@@ -778,6 +1030,47 @@ pub fn render_neo_details_dialog(frame: &mut Frame, theme: &Theme, task: &NeoTas
         Span::styled(status, status_style),
     ]));
 
+    // Sharing section
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        " Sharing",
+        theme.subtitle().add_modifier(Modifier::BOLD),
+    )));
+    lines.push(Line::from(Span::styled(
+        " ─".repeat(18),
+        theme.text_muted(),
+    )));
+
+    if task.is_shared.unwrap_or(false) {
+        let shared_since = task
+            .shared_at
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .map(|dt| format!(" since {}", dt.format("%b %d, %Y")))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", SHARE_ICON), theme.success()),
+            Span::styled(
+                format!("Shared with organization{}", shared_since),
+                theme.success(),
+            ),
+        ]));
+        if let Some(url) = share_url {
+            lines.push(Line::from(Span::styled(format!("  {}", url), theme.info())));
+            if link_copied {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {} ", symbols::CHECK), theme.success()),
+                    Span::styled("Link copied to clipboard", theme.text_muted()),
+                ]));
+            }
+        }
+    } else {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", PRIVATE_ICON), theme.text_secondary()),
+            Span::styled("Private", theme.text()),
+        ]));
+    }
+
     // Started on section
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
@@ -945,15 +1238,165 @@ pub fn render_neo_details_dialog(frame: &mut Frame, theme: &Theme, task: &NeoTas
     // Footer hint
     lines.push(Line::from(""));
     lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        " Press d or Esc to close ",
-        theme.text_muted(),
-    )));
+    let share_action = if task.is_shared.unwrap_or(false) {
+        ": unshare | "
+    } else {
+        ": share | "
+    };
+    let mut footer = vec![
+        Span::styled(" s", theme.key_hint()),
+        Span::styled(share_action, theme.text_muted()),
+    ];
+    if share_url.is_some() {
+        footer.push(Span::styled("y", theme.key_hint()));
+        footer.push(Span::styled(": copy link | ", theme.text_muted()));
+    }
+    footer.push(Span::styled("d/Esc", theme.key_hint()));
+    footer.push(Span::styled(": close", theme.text_muted()));
+    lines.push(Line::from(footer));
 
     let details_para = Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false });
     frame.render_widget(details_para, inner);
 }
 
+/// Render the approval dialog (approve/reject with an optional comment)
+pub fn render_neo_approval_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    request: &NeoMessage,
+    approve: bool,
+    comment: &TextInput,
+) {
+    let area = centered_rect(60, 50, frame.area());
+
+    // Clear background
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.warning())
+        .title(" Respond to Approval Request ")
+        .title_style(theme.warning());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Request content
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    lines.extend(render_markdown_content(&request.content, theme, "  "));
+    let request_para = Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false });
+    frame.render_widget(request_para, chunks[0]);
+
+    // Decision selector
+    let (approve_style, reject_style) = if approve {
+        (
+            theme
+                .success()
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            theme.text_muted(),
+        )
+    } else {
+        (
+            theme.text_muted(),
+            theme
+                .error()
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
+    };
+    let decision = Line::from(vec![
+        Span::styled("  Decision: ", theme.text_secondary()),
+        Span::styled(format!(" {} Approve ", symbols::CHECK), approve_style),
+        Span::raw("  "),
+        Span::styled(format!(" {} Reject ", symbols::CROSS_MARK), reject_style),
+    ]);
+    frame.render_widget(Paragraph::new(decision), chunks[1]);
+
+    // Comment input
+    let comment_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(" Comment (optional) ")
+        .title_style(theme.subtitle());
+    let comment_inner = comment_block.inner(chunks[2]);
+    frame.render_widget(comment_block, chunks[2]);
+    let spans = render_input_with_cursor(comment.value(), comment.cursor(), theme);
+    frame.render_widget(Paragraph::new(Line::from(spans)), comment_inner);
+
+    // Footer
+    let footer = Line::from(vec![
+        Span::styled("  Tab", theme.key_hint()),
+        Span::styled(": approve/reject | ", theme.text_muted()),
+        Span::styled("Enter", theme.key_hint()),
+        Span::styled(": send | ", theme.text_muted()),
+        Span::styled("Esc", theme.key_hint()),
+        Span::styled(": cancel", theme.text_muted()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[3]);
+}
+
+/// Render the cancel task confirmation dialog
+pub fn render_neo_cancel_dialog(frame: &mut Frame, theme: &Theme, task: &NeoTask) {
+    let area = centered_rect(50, 30, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.warning())
+        .title(" Cancel Task ")
+        .title_style(theme.warning());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let name = task
+        .name
+        .clone()
+        .unwrap_or_else(|| task.id[..8.min(task.id.len())].to_string());
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " Stop Neo from working on this task?",
+            theme.text(),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("  {} ", symbols::ARROW_RIGHT), theme.primary()),
+            Span::styled(name, theme.primary().add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Work already done by Neo is not rolled back.",
+            theme.text_muted(),
+        )),
+        Line::from(""),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Press ", theme.text_muted()),
+            Span::styled("y", theme.key_hint()),
+            Span::styled(" to cancel the task, ", theme.text_muted()),
+            Span::styled("n", theme.key_hint()),
+            Span::styled(" or ", theme.text_muted()),
+            Span::styled("Esc", theme.key_hint()),
+            Span::styled(" to keep it running", theme.text_muted()),
+        ]),
+    ];
+
+    let para = Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false });
+    frame.render_widget(para, inner);
+}
+
 // ─────────────────────────────────────────────────────────────
 // Slash Commands Management Dialog
 // ─────────────────────────────────────────────────────────────
@@ -1227,11 +1670,9 @@ fn render_slash_command_detail(
         );
 
         let thumb_height = ((visible_height * visible_height) / total_lines).max(1);
-        let thumb_pos = if max_scroll > 0 {
-            (scroll_y as usize * (visible_height - thumb_height)) / max_scroll
-        } else {
-            0
-        };
+        let thumb_pos = (scroll_y as usize * (visible_height - thumb_height))
+            .checked_div(max_scroll)
+            .unwrap_or(0);
 
         for y in 0..chunks[0].height {
             let y_pos = scrollbar_area.y + y;
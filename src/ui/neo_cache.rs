@@ -0,0 +1,389 @@
+//! Render cache for the Neo chat
+//!
+//! Markdown rendering is the expensive part of drawing a conversation, and
+//! the chat is drawn on every tick. Each message's lines are cached under a
+//! hash of what they are rendered from, and their wrapped height under the
+//! wrap width, so a frame renders only new or changed messages and lays out
+//! only the messages in view. Messages are only hashed again when some were
+//! added, or when the app reports an edit with `invalidate` (a merged poll,
+//! another conversation, an expanded or selected tool call), so a frame of an
+//! unchanged chat does no per-message work. A message's key covers its tool
+//! call blocks, so expanding a call or receiving its response renders the
+//! message again.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use ratatui::{
+    text::{Line, Text},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::api::NeoMessage;
+use crate::theme::Theme;
+
+use super::neo::message_lines;
+use super::neo_tools::{NeoToolCallView, ToolCalls};
+
+/// Rendered lines of one message
+struct CachedMessage {
+    /// Hash of the message fields the lines are rendered from
+    key: u64,
+    lines: Vec<Line<'static>>,
+    /// Call ID and first line of each tool call block
+    tool_calls: Vec<(String, usize)>,
+    /// Height after wrapping, as (width, rows)
+    height: Option<(u16, usize)>,
+}
+
+/// Rendered chat messages, kept across frames
+#[derive(Default)]
+pub struct NeoRenderCache {
+    messages: Vec<CachedMessage>,
+    /// Messages or the tool call view changed in place since the last update
+    stale: bool,
+}
+
+/// Hash of everything `message_lines` reads from a message
+fn message_key(msg: &NeoMessage, tools: &ToolCalls) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    std::mem::discriminant(&msg.message_type).hash(&mut hasher);
+    msg.content.hash(&mut hasher);
+    msg.tool_name.hash(&mut hasher);
+    tools.is_paired(msg).hash(&mut hasher);
+    for call in &msg.tool_calls {
+        tools.hash_call(call, &mut hasher);
+    }
+    hasher.finish()
+}
+
+impl CachedMessage {
+    fn new(key: u64, msg: &NeoMessage, tools: &ToolCalls, theme: &Theme) -> Self {
+        let (lines, tool_calls) = message_lines(msg, tools, theme);
+        Self {
+            key,
+            lines,
+            tool_calls,
+            height: None,
+        }
+    }
+}
+
+/// Rows `lines` take up when wrapped to `width`
+fn wrapped_height(lines: &[Line<'static>], width: u16) -> usize {
+    Paragraph::new(Text::from(lines.to_vec()))
+        .wrap(Wrap { trim: false })
+        .line_count(width)
+}
+
+impl NeoRenderCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check every message again on the next update, after messages or the
+    /// tool call view changed without the number of messages changing
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    /// Render messages that are new or changed since the last call, and drop
+    /// messages that are gone; without added messages or an `invalidate`
+    /// nothing is checked
+    pub fn update(&mut self, messages: &[NeoMessage], view: NeoToolCallView, theme: &Theme) {
+        if !self.stale && self.messages.len() == messages.len() {
+            return;
+        }
+        self.stale = false;
+        let tools = ToolCalls::new(messages, view);
+        self.messages.truncate(messages.len());
+        for (i, msg) in messages.iter().enumerate() {
+            let key = message_key(msg, &tools);
+            match self.messages.get_mut(i) {
+                Some(cached) if cached.key == key => {}
+                Some(cached) => *cached = CachedMessage::new(key, msg, &tools, theme),
+                None => self
+                    .messages
+                    .push(CachedMessage::new(key, msg, &tools, theme)),
+            }
+        }
+    }
+
+    /// Total rows of the chat wrapped to `width`; only messages without a
+    /// height at this width are wrapped
+    pub fn total_height(&mut self, width: u16) -> usize {
+        self.messages
+            .iter_mut()
+            .map(|cached| match cached.height {
+                Some((w, rows)) if w == width => rows,
+                _ => {
+                    let rows = wrapped_height(&cached.lines, width);
+                    cached.height = Some((width, rows));
+                    rows
+                }
+            })
+            .sum()
+    }
+
+    /// Row the block of tool call `id` starts at when wrapped to `width`.
+    /// Heights must be current, from `total_height`.
+    pub fn tool_call_row(&self, id: &str, width: u16) -> Option<usize> {
+        let mut row = 0;
+        for cached in &self.messages {
+            if let Some((_, line)) = cached.tool_calls.iter().find(|(call, _)| call == id) {
+                return Some(row + wrapped_height(&cached.lines[..*line], width));
+            }
+            row += cached.height.map(|(_, rows)| rows).unwrap_or_default();
+        }
+        None
+    }
+
+    /// Lines of the messages covering rows `top..top + rows`, and how many
+    /// wrapped rows of the first of them lie above `top`.
+    /// Heights must be current, from `total_height`.
+    pub fn window(&self, top: usize, rows: usize) -> (Vec<Line<'static>>, usize) {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        let mut skip = 0;
+        let mut row = 0;
+        for cached in &self.messages {
+            let height = cached.height.map(|(_, rows)| rows).unwrap_or_default();
+            let end = row + height;
+            if end > top && row < top + rows {
+                if lines.is_empty() {
+                    skip = top.saturating_sub(row);
+                }
+                lines.extend(cached.lines.iter().cloned());
+            }
+            if end >= top + rows {
+                break;
+            }
+            row = end;
+        }
+        (lines, skip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::NeoMessageType;
+    use ratatui::{backend::TestBackend, buffer::Buffer, layout::Rect, Terminal};
+    use std::collections::HashSet;
+
+    /// Messages recorded from a Neo task
+    const RECORDED_CONVERSATION: &str = include_str!("testdata/neo_conversation.json");
+
+    fn recorded_messages() -> Vec<NeoMessage> {
+        let events: Vec<serde_json::Value> =
+            serde_json::from_str(RECORDED_CONVERSATION).expect("recorded conversation");
+        events
+            .iter()
+            .map(|event| {
+                let text = |key: &str| event.get(key).and_then(|v| v.as_str()).map(String::from);
+                let message_type = match text("type").as_deref() {
+                    Some("user") => NeoMessageType::UserMessage,
+                    Some("tool_call") => NeoMessageType::ToolCall,
+                    Some("tool_response") => NeoMessageType::ToolResponse,
+                    Some("tool_error") => NeoMessageType::ToolError,
+                    Some("approval") => NeoMessageType::ApprovalRequest,
+                    Some("task_name") => NeoMessageType::TaskNameChange,
+                    _ => NeoMessageType::AssistantMessage,
+                };
+                NeoMessage {
+                    role: String::new(),
+                    content: text("content").unwrap_or_default(),
+                    message_type,
+                    timestamp: None,
+                    tool_calls: event
+                        .get("toolCalls")
+                        .map(|calls| serde_json::from_value(calls.clone()).expect("tool calls"))
+                        .unwrap_or_default(),
+                    tool_name: text("toolName"),
+                    tool_call_id: text("toolCallId"),
+                    approval_request_id: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Every line of the chat, rendered without the cache
+    fn all_lines(
+        messages: &[NeoMessage],
+        view: NeoToolCallView,
+        theme: &Theme,
+    ) -> Vec<Line<'static>> {
+        let tools = ToolCalls::new(messages, view);
+        messages
+            .iter()
+            .flat_map(|msg| message_lines(msg, &tools, theme).0)
+            .collect()
+    }
+
+    /// Draw rows `top..top + area.height` of the chat, as the view did before caching:
+    /// every message rendered and wrapped on every frame
+    fn draw_uncached(
+        messages: &[NeoMessage],
+        view: NeoToolCallView,
+        theme: &Theme,
+        area: Rect,
+        top: usize,
+    ) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+        terminal
+            .draw(|frame| {
+                let lines = all_lines(messages, view, theme);
+                let para = Paragraph::new(lines)
+                    .wrap(Wrap { trim: false })
+                    .scroll((top as u16, 0));
+                frame.render_widget(para, area);
+            })
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn draw_cached(
+        cache: &mut NeoRenderCache,
+        messages: &[NeoMessage],
+        view: NeoToolCallView,
+        theme: &Theme,
+        area: Rect,
+        top: usize,
+    ) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+        terminal
+            .draw(|frame| {
+                cache.update(messages, view, theme);
+                cache.total_height(area.width);
+                let (window, skip) = cache.window(top, area.height as usize);
+                let para = Paragraph::new(window)
+                    .wrap(Wrap { trim: false })
+                    .scroll((skip as u16, 0));
+                frame.render_widget(para, area);
+            })
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn cached_window_draws_the_same_rows_as_a_full_render() {
+        let theme = Theme::new();
+        let mut messages = recorded_messages();
+        let mut cache = NeoRenderCache::new();
+        let expanded = HashSet::new();
+        let view = NeoToolCallView {
+            expanded: &expanded,
+            selected: None,
+        };
+
+        for width in [60, 100] {
+            let area = Rect::new(0, 0, width, 20);
+            let total = cache.update_and_height(&messages, view, &theme, width);
+            for top in [0, 7, total / 2, total.saturating_sub(20)] {
+                assert_eq!(
+                    draw_cached(&mut cache, &messages, view, &theme, area, top),
+                    draw_uncached(&messages, view, &theme, area, top),
+                    "width {} top {}",
+                    width,
+                    top
+                );
+            }
+        }
+
+        // A message that changes (e.g. streamed content) is rendered again
+        let last = messages.len() - 1;
+        messages[last].content.push_str("\n\nOne more **line**.");
+        cache.invalidate();
+        let area = Rect::new(0, 0, 80, 20);
+        let total = cache.update_and_height(&messages, view, &theme, 80);
+        assert_eq!(
+            draw_cached(&mut cache, &messages, view, &theme, area, total - 20),
+            draw_uncached(&messages, view, &theme, area, total - 20)
+        );
+
+        // So is a message whose tool call is expanded or selected
+        let expanded = HashSet::from(["call_02".to_string(), "call_03".to_string()]);
+        let view = NeoToolCallView {
+            expanded: &expanded,
+            selected: Some("call_03"),
+        };
+        cache.invalidate();
+        let total = cache.update_and_height(&messages, view, &theme, 80);
+        for top in [0, total / 3, total - 20] {
+            assert_eq!(
+                draw_cached(&mut cache, &messages, view, &theme, area, top),
+                draw_uncached(&messages, view, &theme, area, top),
+                "top {}",
+                top
+            );
+        }
+    }
+
+    #[test]
+    fn update_checks_messages_only_after_additions_or_invalidate() {
+        let theme = Theme::new();
+        let mut messages = recorded_messages();
+        let mut cache = NeoRenderCache::new();
+        let expanded = HashSet::new();
+        let view = NeoToolCallView {
+            expanded: &expanded,
+            selected: None,
+        };
+        let total = cache.update_and_height(&messages, view, &theme, 80);
+
+        // An in-place edit is not looked for on every frame
+        let last = messages.len() - 1;
+        messages[last].content.push_str("\n\nEdited.");
+        assert_eq!(cache.update_and_height(&messages, view, &theme, 80), total);
+
+        cache.invalidate();
+        assert!(cache.update_and_height(&messages, view, &theme, 80) > total);
+    }
+
+    #[test]
+    fn tool_call_row_is_where_the_block_is_drawn() {
+        let theme = Theme::new();
+        let messages = recorded_messages();
+        let mut cache = NeoRenderCache::new();
+        let expanded = HashSet::from(["call_01".to_string()]);
+        let view = NeoToolCallView {
+            expanded: &expanded,
+            selected: None,
+        };
+        cache.update_and_height(&messages, view, &theme, 70);
+
+        let lines = all_lines(&messages, view, &theme);
+        let rows = |lines: &[Line<'static>]| wrapped_height(lines, 70);
+        for id in ["call_01", "call_03", "call_07"] {
+            let row = cache.tool_call_row(id, 70).expect(id);
+            // The rows above the block are exactly the lines before its header
+            let header = (0..lines.len())
+                .find(|&i| rows(&lines[..i]) == row && rows(&lines[..=i]) > row)
+                .expect(id);
+            let text: String = lines[header]
+                .spans
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect();
+            assert!(
+                text.contains('▸') || text.contains('▾'),
+                "{}: {:?}",
+                id,
+                text
+            );
+        }
+        assert_eq!(cache.tool_call_row("call_99", 70), None);
+    }
+
+    impl NeoRenderCache {
+        fn update_and_height(
+            &mut self,
+            messages: &[NeoMessage],
+            view: NeoToolCallView,
+            theme: &Theme,
+            width: u16,
+        ) -> usize {
+            self.update(messages, view, theme);
+            self.total_height(width)
+        }
+    }
+}
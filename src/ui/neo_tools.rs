@@ -0,0 +1,317 @@
+//! Tool call blocks for the Neo chat
+//!
+//! Each tool call of an assistant message is drawn as a block, collapsed to
+//! the tool name, a one-line summary of its arguments and its outcome, or
+//! expanded to the highlighted arguments and the response. Responses are
+//! paired with their calls by call ID and are not drawn on their own.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use ratatui::{
+    style::Modifier,
+    text::{Line, Span},
+};
+
+use crate::api::{NeoMessage, NeoMessageType, NeoToolCall};
+use crate::theme::Theme;
+
+use super::syntax::highlight_json;
+
+const TOOL_ICON: &str = "🔧";
+
+/// Longest argument summary shown on a collapsed block, in characters
+const SUMMARY_CHARS: usize = 60;
+
+/// Response lines shown in an expanded block
+const RESPONSE_LINES: usize = 40;
+
+/// Indentation of an expanded block's arguments and response
+const BODY_INDENT: &str = "        ";
+
+/// Which tool calls are expanded and selected, by call ID
+#[derive(Debug, Clone, Copy)]
+pub struct NeoToolCallView<'a> {
+    pub expanded: &'a HashSet<String>,
+    /// Tool call jumped to with `t`/`T`
+    pub selected: Option<&'a str>,
+}
+
+/// Tool calls of a conversation, paired with their responses by call ID
+pub(super) struct ToolCalls<'a> {
+    view: NeoToolCallView<'a>,
+    calls: HashSet<&'a str>,
+    responses: HashMap<&'a str, &'a NeoMessage>,
+}
+
+impl<'a> ToolCalls<'a> {
+    pub(super) fn new(messages: &'a [NeoMessage], view: NeoToolCallView<'a>) -> Self {
+        let calls = messages
+            .iter()
+            .flat_map(|msg| &msg.tool_calls)
+            .map(|call| call.id.as_str())
+            .filter(|id| !id.is_empty())
+            .collect();
+        let responses = messages
+            .iter()
+            .filter(|msg| {
+                matches!(
+                    msg.message_type,
+                    NeoMessageType::ToolResponse | NeoMessageType::ToolError
+                )
+            })
+            .filter_map(|msg| Some((msg.tool_call_id.as_deref()?, msg)))
+            .collect();
+        Self {
+            view,
+            calls,
+            responses,
+        }
+    }
+
+    /// Whether `msg` runs or answers a call drawn as a block, so is not drawn on its own
+    pub(super) fn is_paired(&self, msg: &NeoMessage) -> bool {
+        matches!(
+            msg.message_type,
+            NeoMessageType::ToolCall | NeoMessageType::ToolResponse | NeoMessageType::ToolError
+        ) && msg
+            .tool_call_id
+            .as_deref()
+            .is_some_and(|id| self.calls.contains(id))
+    }
+
+    fn response(&self, call: &NeoToolCall) -> Option<&'a NeoMessage> {
+        self.responses.get(call.id.as_str()).copied()
+    }
+
+    fn is_expanded(&self, call: &NeoToolCall) -> bool {
+        self.view.expanded.contains(&call.id)
+    }
+
+    fn is_selected(&self, call: &NeoToolCall) -> bool {
+        !call.id.is_empty() && self.view.selected == Some(call.id.as_str())
+    }
+
+    /// Hash everything the block of `call` is drawn from
+    pub(super) fn hash_call<H: Hasher>(&self, call: &NeoToolCall, state: &mut H) {
+        call.id.hash(state);
+        call.name.hash(state);
+        call.args.as_ref().map(|a| a.to_string()).hash(state);
+        self.is_expanded(call).hash(state);
+        self.is_selected(call).hash(state);
+        if let Some(response) = self.response(call) {
+            std::mem::discriminant(&response.message_type).hash(state);
+            response.content.hash(state);
+        }
+    }
+}
+
+/// One-line summary of tool call arguments, e.g. `limit=10, stack=prod`
+fn args_summary(args: Option<&serde_json::Value>) -> String {
+    let summary = match args {
+        Some(serde_json::Value::Object(map)) => map
+            .iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(s) => format!("{}={}", key, s),
+                other => format!("{}={}", key, other),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(serde_json::Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    };
+    let summary = summary.replace('\n', " ");
+    if summary.chars().count() > SUMMARY_CHARS {
+        let cut: String = summary.chars().take(SUMMARY_CHARS - 1).collect();
+        format!("{}…", cut)
+    } else {
+        summary
+    }
+}
+
+/// `lines` indented under a block, at most `limit` of them
+fn body_lines(lines: Vec<Line<'static>>, limit: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let hidden = lines.len().saturating_sub(limit);
+    let mut body: Vec<Line<'static>> = lines
+        .into_iter()
+        .take(limit)
+        .map(|line| {
+            let mut spans = vec![Span::raw(BODY_INDENT)];
+            spans.extend(line.spans);
+            Line::from(spans)
+        })
+        .collect();
+    if hidden > 0 {
+        body.push(Line::from(Span::styled(
+            format!("{}… {} more lines", BODY_INDENT, hidden),
+            theme.text_muted(),
+        )));
+    }
+    body
+}
+
+/// Pretty-printed, highlighted JSON, or `text` as is when it is not JSON
+fn json_or_text(text: &str, style: ratatui::style::Style) -> Vec<Line<'static>> {
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(json) if json.is_object() || json.is_array() => {
+            highlight_json(&serde_json::to_string_pretty(&json).unwrap_or_default())
+        }
+        _ => text
+            .lines()
+            .map(|line| Line::from(Span::styled(line.to_string(), style)))
+            .collect(),
+    }
+}
+
+/// Lines of the block for `call`: a header, and when expanded the
+/// arguments and the paired response
+pub(super) fn tool_call_lines(
+    call: &NeoToolCall,
+    tools: &ToolCalls,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let expanded = tools.is_expanded(call);
+    let response = tools.response(call);
+
+    let mut header = vec![
+        Span::raw("    "),
+        Span::styled(if expanded { "▾ " } else { "▸ " }, theme.text_secondary()),
+        Span::styled(format!("{} ", TOOL_ICON), theme.warning()),
+        Span::styled(
+            call.name.clone(),
+            theme.accent().add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if !expanded {
+        let summary = args_summary(call.args.as_ref());
+        if !summary.is_empty() {
+            header.push(Span::styled(format!("  {}", summary), theme.text_muted()));
+        }
+    }
+    header.push(match response.map(|r| &r.message_type) {
+        Some(NeoMessageType::ToolError) => Span::styled("  ✗ failed", theme.error()),
+        Some(_) => Span::styled("  ✓", theme.success()),
+        None => Span::styled("  …", theme.text_muted()),
+    });
+    let mut header = Line::from(header);
+    if tools.is_selected(call) {
+        header = header.style(theme.selected());
+    }
+
+    let mut lines = vec![header];
+    if !expanded {
+        return lines;
+    }
+
+    lines.push(Line::from(Span::styled(
+        "      Arguments",
+        theme.text_secondary(),
+    )));
+    match call.args.as_ref().filter(|args| !args.is_null()) {
+        Some(args) => lines.extend(body_lines(
+            highlight_json(&serde_json::to_string_pretty(args).unwrap_or_default()),
+            usize::MAX,
+            theme,
+        )),
+        None => lines.push(Line::from(Span::styled(
+            format!("{}(none)", BODY_INDENT),
+            theme.text_muted(),
+        ))),
+    }
+
+    match response {
+        Some(response) if response.message_type == NeoMessageType::ToolError => {
+            lines.push(Line::from(Span::styled(
+                "      Error",
+                theme.error().add_modifier(Modifier::BOLD),
+            )));
+            lines.extend(body_lines(
+                json_or_text(&response.content, theme.error()),
+                RESPONSE_LINES,
+                theme,
+            ));
+        }
+        Some(response) => {
+            lines.push(Line::from(Span::styled("      Result", theme.success())));
+            lines.extend(body_lines(
+                json_or_text(&response.content, theme.text_muted()),
+                RESPONSE_LINES,
+                theme,
+            ));
+        }
+        None => lines.push(Line::from(Span::styled(
+            "      Running…",
+            theme.text_muted(),
+        ))),
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(message_type: NeoMessageType, tool_call_id: Option<&str>) -> NeoMessage {
+        NeoMessage {
+            role: String::new(),
+            content: "{\"ok\":true}".to_string(),
+            message_type,
+            timestamp: None,
+            tool_calls: vec![],
+            tool_name: Some("get_stack".to_string()),
+            tool_call_id: tool_call_id.map(String::from),
+            approval_request_id: None,
+        }
+    }
+
+    #[test]
+    fn responses_pair_with_calls_by_id() {
+        let mut assistant = message(NeoMessageType::AssistantMessage, None);
+        assistant.tool_calls = serde_json::from_value(serde_json::json!([
+            {"id": "call-1", "name": "get_stack", "args": {"stack": "prod"}},
+            {"id": "call-2", "name": "get_stack"},
+        ]))
+        .unwrap();
+        let messages = vec![
+            assistant,
+            message(NeoMessageType::ToolCall, Some("call-1")),
+            message(NeoMessageType::ToolError, Some("call-1")),
+            // A response for a call that is not in the conversation
+            message(NeoMessageType::ToolResponse, Some("call-9")),
+            message(NeoMessageType::ToolResponse, None),
+        ];
+        let expanded = HashSet::new();
+        let tools = ToolCalls::new(
+            &messages,
+            NeoToolCallView {
+                expanded: &expanded,
+                selected: None,
+            },
+        );
+
+        let paired: Vec<bool> = messages.iter().map(|m| tools.is_paired(m)).collect();
+        assert_eq!(paired, vec![false, true, true, false, false]);
+        let calls = &messages[0].tool_calls;
+        assert_eq!(
+            tools.response(&calls[0]).map(|r| &r.message_type),
+            Some(&NeoMessageType::ToolError)
+        );
+        assert!(tools.response(&calls[1]).is_none());
+    }
+
+    #[test]
+    fn args_summary_fits_on_one_line() {
+        let args = serde_json::json!({"stack": "prod", "limit": 10, "tags": ["a"]});
+        assert_eq!(
+            args_summary(Some(&args)),
+            "limit=10, stack=prod, tags=[\"a\"]"
+        );
+        assert_eq!(args_summary(None), "");
+
+        let long = serde_json::json!({"query": "x".repeat(100)});
+        let summary = args_summary(Some(&long));
+        assert_eq!(summary.chars().count(), SUMMARY_CHARS);
+        assert!(summary.ends_with('…'));
+    }
+}
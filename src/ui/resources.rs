@@ -0,0 +1,325 @@
+//! Resources view rendering
+//!
+//! Displays a page of resource search results as a sortable table, with a
+//! query input on top and the selected resource's details on the right.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    prelude::*,
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::api::Resource;
+use crate::app::{ResourceSearchState, ResourceSortColumn};
+use crate::components::{StatefulList, TextInput};
+use crate::theme::{symbols, Theme};
+
+/// Props for rendering the resources view
+pub struct ResourcesViewProps<'a> {
+    pub resources: &'a mut StatefulList<Resource>,
+    pub table_state: &'a mut TableState,
+    pub search: &'a ResourceSearchState,
+    pub query_input: &'a TextInput,
+    pub is_editing_query: bool,
+}
+
+/// Render the resources view with query input, results table and details
+pub fn render_resources_view(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    props: ResourcesViewProps<'_>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(5)])
+        .split(area);
+
+    render_query_input(
+        frame,
+        theme,
+        chunks[0],
+        props.query_input,
+        props.search,
+        props.is_editing_query,
+    );
+
+    let content_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(chunks[1]);
+
+    render_results_table(
+        frame,
+        theme,
+        content_chunks[0],
+        props.resources,
+        props.table_state,
+        props.search,
+    );
+    render_resource_details(frame, theme, content_chunks[1], props.resources.selected());
+}
+
+fn render_query_input(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    input: &TextInput,
+    search: &ResourceSearchState,
+    is_editing: bool,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(if is_editing {
+            theme.border_focused()
+        } else {
+            theme.border()
+        })
+        .title(" / Query ")
+        .title_style(if is_editing {
+            theme.title()
+        } else {
+            theme.subtitle()
+        });
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // While not editing, show the query the results were searched with
+    let value = if is_editing {
+        input.value()
+    } else {
+        search.query.as_str()
+    };
+    let text = if value.is_empty() && !is_editing {
+        Paragraph::new(
+            "Type / to search, e.g. type:aws:s3/bucket:Bucket stack:prod modified:>2024-01-01",
+        )
+        .style(theme.text_muted())
+    } else {
+        Paragraph::new(value).style(theme.text())
+    };
+    frame.render_widget(text, inner);
+
+    if is_editing {
+        let cursor_x = inner.x + input.cursor() as u16;
+        if cursor_x < inner.x + inner.width {
+            frame.set_cursor_position((cursor_x, inner.y));
+        }
+    }
+}
+
+fn render_results_table(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    resources: &StatefulList<Resource>,
+    table_state: &mut TableState,
+    search: &ResourceSearchState,
+) {
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(" Resources ")
+        .title_style(theme.subtitle());
+
+    let position = match (search.total, search.page_count()) {
+        (Some(total), Some(pages)) => {
+            format!(" Page {}/{} · {} resources ", search.page, pages, total)
+        }
+        _ => format!(" Page {} ", search.page),
+    };
+    block = block.title_bottom(Line::styled(position, theme.text_muted()));
+    if search.loading {
+        block = block.title_bottom(Line::styled(" Searching... ", theme.info()).right_aligned());
+    }
+
+    if let Some(ref error) = search.error {
+        let paragraph = Paragraph::new(error.as_str())
+            .style(theme.error())
+            .wrap(Wrap { trim: true })
+            .block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    if resources.is_empty() {
+        let message = if search.loading {
+            "Searching..."
+        } else {
+            "No resources found"
+        };
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let empty = Paragraph::new(message)
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+        return;
+    }
+
+    let header: Vec<Cell> = ResourceSortColumn::all()
+        .iter()
+        .map(|column| {
+            if *column == search.sort {
+                let arrow = if search.ascending {
+                    symbols::ARROW_UP
+                } else {
+                    symbols::ARROW_DOWN
+                };
+                Cell::from(format!("{} {}", column.title(), arrow)).style(theme.primary())
+            } else {
+                Cell::from(column.title()).style(theme.subtitle())
+            }
+        })
+        .collect();
+
+    let rows: Vec<Row> = resources
+        .items()
+        .iter()
+        .map(|resource| {
+            Row::new(vec![
+                Cell::from(Span::styled(resource.name.as_str(), theme.text())),
+                Cell::from(Span::styled(
+                    resource.resource_type.as_str(),
+                    theme.text_secondary(),
+                )),
+                Cell::from(resource.stack.as_deref().unwrap_or("-")),
+                Cell::from(resource.project.as_deref().unwrap_or("-")),
+                Cell::from(resource.package.as_deref().unwrap_or("-")),
+                Cell::from(Span::styled(
+                    format_timestamp(resource.modified.as_deref()),
+                    theme.text_muted(),
+                )),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(22),
+            Constraint::Percentage(28),
+            Constraint::Percentage(12),
+            Constraint::Percentage(14),
+            Constraint::Percentage(8),
+            Constraint::Min(16),
+        ],
+    )
+    .header(Row::new(header).bottom_margin(1))
+    .row_highlight_style(theme.selected())
+    .block(block);
+
+    table_state.select(resources.selected_index());
+    frame.render_stateful_widget(table, area, table_state);
+}
+
+fn render_resource_details(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    selected: Option<&Resource>,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title(" Resource Details ")
+        .title_style(theme.subtitle());
+
+    let Some(resource) = selected else {
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let empty = Paragraph::new("Select a resource to view details")
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+        return;
+    };
+
+    let field = |label: &'static str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("{:<10}", label), theme.text_secondary()),
+            Span::styled(value, style),
+        ])
+    };
+    let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec![
+        field("Name:", resource.name.clone(), theme.highlight()),
+        field("Type:", resource.resource_type.clone(), theme.text()),
+        field("Package:", optional(&resource.package), theme.text()),
+        field("Project:", optional(&resource.project), theme.text()),
+        field("Stack:", optional(&resource.stack), theme.text()),
+        field("ID:", optional(&resource.id), theme.text()),
+        field(
+            "Created:",
+            format_timestamp(resource.created.as_deref()),
+            theme.text(),
+        ),
+        field(
+            "Modified:",
+            format_timestamp(resource.modified.as_deref()),
+            theme.text(),
+        ),
+        field(
+            "Protected:",
+            if resource.protected { "yes" } else { "no" }.to_string(),
+            if resource.protected {
+                theme.warning()
+            } else {
+                theme.text()
+            },
+        ),
+    ];
+
+    for (label, urn) in [
+        ("URN", &resource.urn),
+        ("Parent", &resource.parent_urn),
+        ("Provider", &resource.provider_urn),
+    ] {
+        if let Some(urn) = urn {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(label, theme.text_secondary()));
+            lines.push(Line::styled(urn.as_str(), theme.text_muted()));
+        }
+    }
+
+    for (label, urns) in [
+        ("Dependencies", &resource.dependencies),
+        ("Dependents", &resource.dependents),
+    ] {
+        if !urns.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                format!("{} ({})", label, urns.len()),
+                theme.text_secondary(),
+            ));
+            lines.extend(urns.iter().map(|urn| {
+                Line::from(vec![
+                    Span::styled(format!("{} ", symbols::BULLET), theme.primary()),
+                    Span::styled(urn_name(urn), theme.text()),
+                ])
+            }));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+/// Shorten an RFC 3339 timestamp to date and minutes
+fn format_timestamp(timestamp: Option<&str>) -> String {
+    match timestamp {
+        Some(ts) if ts.len() >= 16 => ts[..16].replace('T', " "),
+        Some(ts) => ts.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Resource name from the last URN segment
+fn urn_name(urn: &str) -> &str {
+    urn.rsplit("::").next().unwrap_or(urn)
+}
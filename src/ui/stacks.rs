@@ -3,34 +3,82 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
-    text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Row, Table},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, TableState},
 };
+use tui_scrollview::{ScrollView, ScrollViewState};
 
-use crate::api::Stack;
+use super::centered_rect;
+use crate::api::{
+    ResourceChanges, Stack, StackUpdate, UpdateDetails, UpdateEvent, UpdateEventKind,
+};
+use crate::app::{StackResourceRow, StacksPane};
 use crate::components::StatefulList;
 use crate::theme::{symbols, Theme};
 
+/// Props for rendering the stacks view
+pub struct StacksViewProps<'a> {
+    pub stacks: &'a mut StatefulList<Stack>,
+    /// Update history of the selected stack
+    pub updates: &'a mut StatefulList<StackUpdate>,
+    pub resources: &'a mut StatefulList<StackResourceRow>,
+    /// Cached syntax-highlighted inputs/outputs of the selected resource
+    pub resource_highlighted: Option<&'a Vec<Line<'static>>>,
+    pub focused_pane: StacksPane,
+    pub properties_scroll: &'a mut ScrollViewState,
+}
+
 /// Render the stacks view
 pub fn render_stacks_view(
     frame: &mut Frame,
     theme: &Theme,
     area: Rect,
-    stacks: &mut StatefulList<Stack>,
-    selected_stack_updates: &[(i32, String, String)], // (version, result, time)
+    props: StacksViewProps<'_>,
 ) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
         .split(area);
 
-    render_stacks_list(frame, theme, chunks[0], stacks);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(9), Constraint::Min(8)])
+        .split(chunks[1]);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(right[1]);
+
+    render_stacks_list(
+        frame,
+        theme,
+        chunks[0],
+        props.stacks,
+        props.focused_pane == StacksPane::Stacks,
+    );
     render_stack_details(
         frame,
         theme,
-        chunks[1],
-        stacks.selected(),
-        selected_stack_updates,
+        right[0],
+        props.stacks.selected(),
+        props.updates,
+        props.focused_pane == StacksPane::Updates,
+    );
+    render_resource_tree(
+        frame,
+        theme,
+        bottom[0],
+        props.resources,
+        props.focused_pane == StacksPane::Resources,
+    );
+    render_resource_properties(
+        frame,
+        theme,
+        bottom[1],
+        props.resource_highlighted,
+        props.focused_pane == StacksPane::Properties,
+        props.properties_scroll,
     );
 }
 
@@ -39,6 +87,7 @@ fn render_stacks_list(
     theme: &Theme,
     area: Rect,
     stacks: &mut StatefulList<Stack>,
+    is_focused: bool,
 ) {
     // Get selected index and is_empty before borrowing items
     let selected_idx = stacks.selected_index();
@@ -79,10 +128,10 @@ fn render_stacks_list(
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(if is_empty {
-                    theme.border()
-                } else {
+                .border_style(if is_focused && !is_empty {
                     theme.border_focused()
+                } else {
+                    theme.border()
                 })
                 .title(" Stacks ")
                 .title_style(theme.title()),
@@ -98,11 +147,12 @@ fn render_stack_details(
     theme: &Theme,
     area: Rect,
     selected: Option<&Stack>,
-    updates: &[(i32, String, String)],
+    updates: &mut StatefulList<StackUpdate>,
+    is_focused: bool,
 ) {
     let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Length(10), Constraint::Min(5)])
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
         .split(area);
 
     // Stack info
@@ -160,30 +210,45 @@ fn render_stack_details(
     // Updates history
     let updates_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(theme.border())
+        .border_style(if is_focused {
+            theme.border_focused()
+        } else {
+            theme.border()
+        })
         .title(" Update History ")
-        .title_style(theme.subtitle());
-
-    let updates_inner = updates_block.inner(chunks[1]);
-    frame.render_widget(updates_block, chunks[1]);
+        .title_style(if is_focused {
+            theme.title()
+        } else {
+            theme.subtitle()
+        });
 
     if updates.is_empty() {
+        let updates_inner = updates_block.inner(chunks[1]);
+        frame.render_widget(updates_block, chunks[1]);
         let empty = Paragraph::new("No updates yet")
             .style(theme.text_muted())
             .alignment(Alignment::Center);
         frame.render_widget(empty, updates_inner);
     } else {
         let rows: Vec<Row> = updates
+            .items()
             .iter()
-            .map(|(version, result, time)| {
-                let result_style = match result.to_lowercase().as_str() {
-                    "succeeded" => theme.success(),
-                    "failed" => theme.error(),
-                    _ => theme.warning(),
-                };
-
-                Row::new(vec![format!("v{}", version), result.clone(), time.clone()])
-                    .style(result_style)
+            .map(|update| {
+                let result = update.result.as_deref().unwrap_or("Unknown");
+                let result_style = result_style(theme, result);
+                let changes = update
+                    .resource_changes
+                    .as_ref()
+                    .map(changes_summary)
+                    .unwrap_or_default();
+
+                Row::new(vec![
+                    format!("v{}", update.version),
+                    result.to_string(),
+                    update.start_time_formatted(),
+                    changes,
+                ])
+                .style(result_style)
             })
             .collect();
 
@@ -191,16 +256,416 @@ fn render_stack_details(
             rows,
             [
                 Constraint::Length(6),
-                Constraint::Length(12),
-                Constraint::Min(10),
+                Constraint::Length(11),
+                Constraint::Length(17),
+                Constraint::Min(8),
             ],
         )
         .header(
-            Row::new(vec!["Ver", "Result", "Time"])
+            Row::new(vec!["Ver", "Result", "Time", "Changes"])
                 .style(theme.subtitle())
                 .bottom_margin(1),
-        );
+        )
+        .block(updates_block)
+        .row_highlight_style(if is_focused {
+            theme.selected()
+        } else {
+            Style::default()
+        });
+
+        let mut table_state = TableState::default()
+            .with_offset(updates.state.offset())
+            .with_selected(updates.selected_index());
+        frame.render_stateful_widget(table, chunks[1], &mut table_state);
+        *updates.state.offset_mut() = table_state.offset();
+    }
+}
+
+/// Style for an update result (succeeded, failed, in-progress, ...)
+fn result_style(theme: &Theme, result: &str) -> Style {
+    match result.to_lowercase().as_str() {
+        "succeeded" => theme.success(),
+        "failed" => theme.error(),
+        _ => theme.warning(),
+    }
+}
+
+/// Compact resource change counts, e.g. `+2 ~1 -0`
+fn changes_summary(changes: &ResourceChanges) -> String {
+    format!(
+        "+{} ~{} -{}",
+        changes.create.unwrap_or(0),
+        changes.update.unwrap_or(0),
+        changes.delete.unwrap_or(0)
+    )
+}
+
+fn render_resource_tree(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    resources: &mut StatefulList<StackResourceRow>,
+    is_focused: bool,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(if is_focused {
+            theme.border_focused()
+        } else {
+            theme.border()
+        })
+        .title(format!(" Resources ({}) ", resources.items().len()))
+        .title_style(if is_focused {
+            theme.title()
+        } else {
+            theme.subtitle()
+        });
+
+    if resources.is_empty() {
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let empty = Paragraph::new("Press Enter on a stack to load its resources")
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+        return;
+    }
+
+    let rows: Vec<Row> = resources
+        .items()
+        .iter()
+        .map(|row| {
+            let resource = &row.resource;
+            let indent = if row.depth == 0 {
+                String::new()
+            } else {
+                format!("{}└ ", "  ".repeat(row.depth - 1))
+            };
+            let name = Line::from(vec![
+                Span::styled(indent, theme.text_muted()),
+                Span::styled(
+                    resource.name().to_string(),
+                    if resource.custom {
+                        theme.text()
+                    } else {
+                        theme.primary()
+                    },
+                ),
+            ]);
+
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(Span::styled(
+                    resource.resource_type.clone(),
+                    theme.text_secondary(),
+                )),
+                Cell::from(Span::styled(
+                    resource.provider_label().unwrap_or_default(),
+                    theme.info(),
+                )),
+                Cell::from(Span::styled(
+                    resource.id.clone().unwrap_or_default(),
+                    theme.text_muted(),
+                )),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+            Constraint::Percentage(12),
+            Constraint::Percentage(28),
+        ],
+    )
+    .header(
+        Row::new(vec!["Name", "Type", "Provider", "ID"])
+            .style(theme.subtitle())
+            .bottom_margin(1),
+    )
+    .block(block)
+    .row_highlight_style(theme.selected());
+
+    // The list keeps selection and scroll offset; the table only borrows them per frame
+    let mut table_state = TableState::default()
+        .with_offset(resources.state.offset())
+        .with_selected(resources.selected_index());
+    frame.render_stateful_widget(table, area, &mut table_state);
+    *resources.state.offset_mut() = table_state.offset();
+}
+
+fn render_resource_properties(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    highlighted: Option<&Vec<Line<'static>>>,
+    is_focused: bool,
+    scroll_state: &mut ScrollViewState,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(if is_focused {
+            theme.border_focused()
+        } else {
+            theme.border()
+        })
+        .title(" Inputs & Outputs ")
+        .title_style(if is_focused {
+            theme.title()
+        } else {
+            theme.subtitle()
+        });
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(lines) = highlighted else {
+        let empty = Paragraph::new("Select a resource to view its properties")
+            .style(theme.text_muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, inner);
+        return;
+    };
+
+    let content_height = (lines.len() as u16).max(inner.height);
+    let content_width = inner.width.saturating_sub(1);
+    let mut scroll_view = ScrollView::new(Size::new(content_width, content_height));
+    scroll_view.render_widget(
+        Paragraph::new(Text::from(lines.clone())),
+        Rect::new(0, 0, content_width, content_height),
+    );
+    frame.render_stateful_widget(scroll_view, inner, scroll_state);
+}
+
+/// Render the details of a single update: summary, resource changes, people and the event log
+pub fn render_update_details_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    details: &UpdateDetails,
+    events: &[UpdateEvent],
+    events_error: Option<&str>,
+    scroll_state: &mut ScrollViewState,
+) {
+    let area = centered_rect(80, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .title(format!(" Update v{} ", details.version))
+        .title_style(theme.title());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let section = |lines: &mut Vec<Line<'static>>, title: &str| {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {}", title),
+            theme.subtitle().add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            " ─".repeat(18),
+            theme.text_muted(),
+        )));
+    };
+    let field = |label: &str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("  {:<14}", label), theme.text_secondary()),
+            Span::styled(value, style),
+        ])
+    };
+
+    // Summary
+    section(&mut lines, "Summary");
+    lines.push(field(
+        "Result:",
+        details.result.clone(),
+        result_style(theme, &details.result),
+    ));
+    lines.push(field("Kind:", details.kind.clone(), theme.text()));
+    let started = details
+        .start_time
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    lines.push(field("Started:", started, theme.text()));
+    if let Some(duration) = details.duration_formatted() {
+        lines.push(field("Duration:", duration, theme.text()));
+    }
+    if let Some(count) = details.resource_count {
+        lines.push(field("Resources:", count.to_string(), theme.info()));
+    }
+    if !details.message.is_empty() {
+        lines.push(field("Message:", details.message.clone(), theme.text()));
+    }
+
+    // Resource changes
+    section(&mut lines, "Resource Changes");
+    match details.resource_changes {
+        Some(ref changes) => {
+            let counts = [
+                ("+ create", changes.create, theme.success()),
+                ("~ update", changes.update, theme.warning()),
+                ("- delete", changes.delete, theme.error()),
+                ("  same", changes.same, theme.text_muted()),
+            ];
+            lines.push(Line::from(
+                counts
+                    .into_iter()
+                    .map(|(label, count, style)| {
+                        Span::styled(format!("  {} {}  ", label, count.unwrap_or(0)), style)
+                    })
+                    .collect::<Vec<_>>(),
+            ));
+        }
+        None => lines.push(Line::from(Span::styled(
+            "  No changes recorded",
+            theme.text_muted(),
+        ))),
+    }
+
+    // People involved
+    section(&mut lines, "People");
+    let people = details.people();
+    if people.is_empty() {
+        lines.push(Line::from(Span::styled("  Unknown", theme.text_muted())));
+    }
+    for (role, person) in people {
+        lines.push(field(
+            &format!("{}:", role),
+            person.display_name(),
+            theme.primary(),
+        ));
+    }
+    if let Some(ref commit) = details.commit {
+        let short_sha: String = commit.sha.chars().take(8).collect();
+        let subject = commit.message.lines().next().unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<14}", "Commit:"), theme.text_secondary()),
+            Span::styled(short_sha, theme.accent()),
+            Span::styled(format!(" {}", subject), theme.text()),
+        ]));
+    }
+
+    // Engine event timeline
+    section(&mut lines, &format!("Timeline ({} events)", events.len()));
+    if let Some(error) = events_error {
+        lines.push(Line::from(Span::styled(
+            format!("  Failed to load engine events: {}", error),
+            theme.error(),
+        )));
+    } else if events.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No engine events recorded",
+            theme.text_muted(),
+        )));
+    }
+    let start = details
+        .start_time
+        .or_else(|| events.first().map(|e| e.timestamp))
+        .unwrap_or(0);
+    for event in events {
+        lines.extend(event_lines(theme, event, start));
+    }
+
+    let content_height = (lines.len() as u16).max(inner.height);
+    let content_width = inner.width.saturating_sub(1);
+    let mut scroll_view = ScrollView::new(Size::new(content_width, content_height));
+    scroll_view.render_widget(
+        Paragraph::new(Text::from(lines)),
+        Rect::new(0, 0, content_width, content_height),
+    );
+    frame.render_stateful_widget(scroll_view, inner, scroll_state);
+}
 
-        frame.render_widget(table, updates_inner);
+/// Lines for one timeline entry, offset from the start of the update
+fn event_lines(theme: &Theme, event: &UpdateEvent, start: i64) -> Vec<Line<'static>> {
+    let offset = event.timestamp.saturating_sub(start).max(0);
+    let time = Span::styled(
+        format!("  +{:02}:{:02}  ", offset / 60, offset % 60),
+        theme.text_muted(),
+    );
+
+    match event.kind {
+        UpdateEventKind::ResourcePre
+        | UpdateEventKind::ResourceOutputs
+        | UpdateEventKind::ResourceFailed => {
+            let op = event.op.as_deref().unwrap_or("");
+            let (marker, style) = match (event.kind, op) {
+                (UpdateEventKind::ResourceFailed, _) => (symbols::CROSS_MARK, theme.error()),
+                (_, "create" | "create-replacement") => ("+", theme.success()),
+                (_, "update") => ("~", theme.warning()),
+                (_, "delete" | "delete-replaced") => ("-", theme.error()),
+                (_, "replace") => ("±", theme.warning()),
+                _ => (" ", theme.text_muted()),
+            };
+            let status = match event.kind {
+                UpdateEventKind::ResourcePre => "start",
+                UpdateEventKind::ResourceOutputs => "done",
+                _ => "failed",
+            };
+
+            let mut spans = vec![
+                time,
+                Span::styled(format!("{} {:<8} ", marker, op), style),
+                Span::styled(
+                    event.resource_name().unwrap_or_default().to_string(),
+                    theme.text(),
+                ),
+                Span::styled(
+                    format!(" ({}) ", event.resource_type.as_deref().unwrap_or("")),
+                    theme.text_muted(),
+                ),
+                Span::styled(status, style),
+            ];
+            if !event.diffs.is_empty() {
+                spans.push(Span::styled(
+                    format!("  [{}]", event.diffs.join(", ")),
+                    theme.info(),
+                ));
+            }
+            vec![Line::from(spans)]
+        }
+        UpdateEventKind::Diagnostic | UpdateEventKind::Stdout | UpdateEventKind::Summary => {
+            let style = match (event.kind, event.severity.as_deref()) {
+                (UpdateEventKind::Summary, _) => theme.accent(),
+                (_, Some("error")) => theme.error(),
+                (_, Some("warning")) => theme.warning(),
+                (_, Some("info" | "info#err")) => theme.info(),
+                _ => theme.text_secondary(),
+            };
+            let prefix = match (event.resource_name(), event.severity.as_deref()) {
+                (Some(name), Some(severity)) => format!("{} {}: ", name, severity),
+                (None, Some(severity)) => format!("{}: ", severity),
+                (Some(name), None) => format!("{}: ", name),
+                (None, None) => String::new(),
+            };
+
+            event
+                .message
+                .lines()
+                .enumerate()
+                .map(|(i, line)| {
+                    let lead = if i == 0 {
+                        time.clone()
+                    } else {
+                        Span::raw(" ".repeat(10))
+                    };
+                    let label = if i == 0 {
+                        prefix.clone()
+                    } else {
+                        String::new()
+                    };
+                    Line::from(vec![
+                        lead,
+                        Span::styled(format!("{}{}", label, line), style),
+                    ])
+                })
+                .collect()
+        }
     }
 }